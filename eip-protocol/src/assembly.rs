@@ -0,0 +1,257 @@
+use crate::attr::{AccessCode, Uint};
+use crate::eip::{EipResult, Serializing};
+use crate::error_code::{
+    ATTRIBUTE_NOT_GETTABLE, ATTRIBUTE_NOT_SETTABLE, ATTRIBUTE_NOT_SUPPORTED, NOT_ENOUGH_DATA,
+    REPLY_DATA_TOO_LARGE,
+};
+use crate::message_router::Object;
+use alloc::vec;
+use alloc::vec::Vec;
+use bytes::{Buf, BufMut, BytesMut};
+
+/// The Assembly object binds the I/O data of a connection.
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Attr(u16);
+
+impl From<u16> for Attr {
+    fn from(val: u16) -> Self {
+        Self(val)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Assembly {
+    data: Vec<u8>,   // The I/O data, fixed size
+    acc: AccessCode, // Accessibility of the data
+    pub size: Uint,  // Number of bytes of the data
+}
+
+impl Assembly {
+    pub const CLASS: u32 = 0x04;
+
+    pub const DATA: Attr = Attr(3);
+    pub const SIZE: Attr = Attr(4);
+
+    /// Create an instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The number of bytes of the data
+    /// * `acc` - The accessibility of the data
+    ///
+    /// # Returns
+    ///
+    /// * The created instance
+    ///
+    pub fn new(size: u16, acc: AccessCode) -> Self {
+        Assembly {
+            data: vec![0; size as usize],
+            acc,
+            size: Uint::new(size, AccessCode::get_only()),
+        }
+    }
+
+    /// Create an input assembly, produced by this device and only retrieved by others.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The number of bytes of the data
+    ///
+    /// # Returns
+    ///
+    /// * The created instance
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytes::BytesMut;
+    /// use eip_protocol::assembly::Assembly;
+    ///
+    /// let mut input = Assembly::input(2);
+    /// input.data_mut().copy_from_slice(&[1, 2]);
+    /// let mut buf = BytesMut::with_capacity(10);
+    ///
+    /// assert_eq!(
+    ///    Ok(()),
+    ///    input.serialize_attribute_single(&mut buf, Assembly::DATA)
+    /// );
+    /// assert_eq!(&b"\x01\x02"[..], buf);
+    /// ```
+    pub fn input(size: u16) -> Self {
+        Self::new(size, AccessCode::get_only())
+    }
+
+    /// Create an output assembly, consumed by this device and changed by others.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The number of bytes of the data
+    ///
+    /// # Returns
+    ///
+    /// * The created instance
+    ///
+    pub fn output(size: u16) -> Self {
+        Self::new(size, AccessCode::get_set())
+    }
+
+    /// Get the data
+    ///
+    /// # Returns
+    ///
+    /// * The I/O data
+    ///
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Get the data to change it
+    ///
+    /// # Returns
+    ///
+    /// * The I/O data
+    ///
+    pub fn data_mut(&mut self) -> &mut [u8] {
+        &mut self.data
+    }
+
+    /// Serialize one specific attribute
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to write to
+    /// * `attr` - The attribute identifier number
+    ///
+    /// # Errors
+    ///
+    /// If the attribute is non existent or is not getable, an error variant will be returned.
+    ///
+    pub fn serialize_attribute_single(&self, buf: &mut BytesMut, attr: Attr) -> EipResult {
+        match attr {
+            Self::DATA => {
+                if !self.acc.getable() {
+                    return Err(ATTRIBUTE_NOT_GETTABLE);
+                }
+                if buf.remaining_mut() < self.data.len() {
+                    return Err(REPLY_DATA_TOO_LARGE);
+                }
+                buf.put_slice(&self.data);
+            }
+            Self::SIZE => self.size.serialize(buf)?,
+            _ => return Err(ATTRIBUTE_NOT_SUPPORTED),
+        };
+        Ok(())
+    }
+
+    /// Deserialize one specific attribute
+    /// Note: the data must be provided completely
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to read from
+    /// * `attr` - The attribute identifier number
+    ///
+    /// # Errors
+    ///
+    /// If the attribute is non existent or is not set-able, an error variant will be returned.
+    ///
+    pub fn deserialize_attribute_single(&mut self, buf: &mut dyn Buf, attr: Attr) -> EipResult {
+        match attr {
+            Self::DATA => {
+                if !self.acc.settable() {
+                    return Err(ATTRIBUTE_NOT_SETTABLE);
+                }
+                if buf.remaining() < self.data.len() {
+                    return Err(NOT_ENOUGH_DATA);
+                }
+                buf.copy_to_slice(&mut self.data);
+            }
+            Self::SIZE => self.size.deserialize(buf)?,
+            _ => return Err(ATTRIBUTE_NOT_SUPPORTED),
+        };
+        Ok(())
+    }
+}
+
+impl Object for Assembly {
+    fn get_attribute(&self, buf: &mut BytesMut, attr: u16) -> EipResult {
+        self.serialize_attribute_single(buf, attr.into())
+    }
+
+    fn set_attribute(&mut self, buf: &mut dyn Buf, attr: u16) -> EipResult {
+        self.deserialize_attribute_single(buf, attr.into())
+    }
+}
+
+#[cfg(test)]
+fn setup_test_assembly() -> Assembly {
+    let mut assembly = Assembly::output(4);
+    assembly.data_mut().copy_from_slice(&[1, 2, 3, 4]);
+    assembly
+}
+
+#[test]
+fn get_data() {
+    let mut buf = BytesMut::with_capacity(10);
+    assert_eq!(
+        Ok(()),
+        setup_test_assembly().serialize_attribute_single(&mut buf, Assembly::DATA)
+    );
+    assert_eq!(&b"\x01\x02\x03\x04"[..], buf);
+}
+
+#[test]
+fn set_data() {
+    let mut assembly = setup_test_assembly();
+    let mut buf = &b"\x05\x06\x07\x08"[..];
+    assert_eq!(
+        Ok(()),
+        assembly.deserialize_attribute_single(&mut buf, Assembly::DATA)
+    );
+    assert_eq!(0, buf.len());
+    assert_eq!(&[5, 6, 7, 8], assembly.data());
+
+    buf = &b"\x05\x06\x07"[..];
+    assert_eq!(
+        Err(NOT_ENOUGH_DATA),
+        assembly.deserialize_attribute_single(&mut buf, Assembly::DATA)
+    );
+
+    let mut input = Assembly::input(4);
+    buf = &b"\x05\x06\x07\x08"[..];
+    assert_eq!(
+        Err(ATTRIBUTE_NOT_SETTABLE),
+        input.deserialize_attribute_single(&mut buf, Assembly::DATA)
+    );
+}
+
+#[test]
+fn get_size() {
+    let mut assembly = setup_test_assembly();
+    let mut buf = BytesMut::with_capacity(10);
+    assert_eq!(
+        Ok(()),
+        assembly.serialize_attribute_single(&mut buf, Assembly::SIZE)
+    );
+    assert_eq!(&b"\x04\0"[..], buf);
+
+    assert_eq!(
+        Err(ATTRIBUTE_NOT_SETTABLE),
+        assembly.deserialize_attribute_single(&mut &b"\x02\0"[..], Assembly::SIZE)
+    );
+    assert_eq!(
+        Err(ATTRIBUTE_NOT_SUPPORTED),
+        assembly.serialize_attribute_single(&mut buf, Attr(5))
+    );
+}
+
+#[test]
+fn auto_traits() {
+    use crate::eip::check_auto_traits;
+
+    check_auto_traits::<Attr>();
+    check_auto_traits::<Assembly>();
+}
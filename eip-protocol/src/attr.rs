@@ -1,16 +1,23 @@
 #![allow(dead_code)]
-use crate::eip::{EipResult, Serializing};
+use crate::data_type::{self, DataType};
+use crate::eip::{self, EipResult, Serializing};
 use crate::error_code::{
-    ATTRIBUTE_NOT_GETTABLE, ATTRIBUTE_NOT_SETTABLE, INVALID_PARAMETER, NOT_ENOUGH_DATA,
+    ErrorCode, ATTRIBUTE_NOT_GETTABLE, ATTRIBUTE_NOT_SETTABLE, INVALID_PARAMETER, NOT_ENOUGH_DATA,
     REPLY_DATA_TOO_LARGE, TOO_MUCH_DATA,
 };
-use bytes::{Buf, BufMut, BytesMut};
+use crate::message_router::Request;
+use alloc::string::String;
+use alloc::vec::Vec;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use core::cmp::Ordering;
 use core::mem::size_of;
+use core::ops::BitOr;
 use log::warn;
 
 // Attribute access levels
 #[non_exhaustive]
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AccessCode(u8);
 
 impl AccessCode {
@@ -32,6 +39,51 @@ impl AccessCode {
         AccessCode(code)
     }
 
+    /// Create an instance that can only be retrieved
+    ///
+    /// # Returns
+    ///
+    /// * The created instance
+    ///
+    pub const fn get_only() -> Self {
+        AccessCode(AccessCode::GET)
+    }
+
+    /// Create an instance that can only be changed
+    ///
+    /// # Returns
+    ///
+    /// * The created instance
+    ///
+    pub const fn set_only() -> Self {
+        AccessCode(AccessCode::SET)
+    }
+
+    /// Create an instance that can be both retrieved and changed
+    ///
+    /// # Returns
+    ///
+    /// * The created instance
+    ///
+    pub const fn get_set() -> Self {
+        AccessCode(AccessCode::GET | AccessCode::SET)
+    }
+
+    /// Test if all access levels of another instance are allowed
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The access levels to test
+    ///
+    /// # Returns
+    ///
+    /// * true if all levels of `other` are also in this instance
+    ///
+    #[inline]
+    pub fn contains(&self, other: &AccessCode) -> bool {
+        (self.0 & other.0) == other.0
+    }
+
     /// Can the attribute be retrieved
     ///
     /// # Returns
@@ -58,20 +110,110 @@ impl AccessCode {
 /// Default is access code both GET and SET
 impl Default for AccessCode {
     fn default() -> Self {
-        AccessCode(AccessCode::GET | AccessCode::SET)
+        AccessCode::get_set()
     }
 }
 
-/// Attribute that holds an signed 8 bit integer
-#[derive(Clone, Debug, Default, PartialEq)]
-pub struct Sint {
+impl BitOr for AccessCode {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// A primitive value that can be held by an `Attribute`
+pub trait AttrPrimitive: Copy + Default + PartialOrd {
+    /// The number of bytes when serialized
+    const SIZE: usize;
+
+    /// The elementary data type identification
+    const DATA_TYPE: DataType;
+
+    /// Read a value from a message buffer.
+    /// The caller must make sure there are at least `SIZE` bytes remaining.
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to read from
+    ///
+    fn read(buf: &mut dyn Buf) -> Self;
+
+    /// Write a value to a message buffer.
+    /// The caller must make sure there is room for at least `SIZE` bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to write to
+    /// * `val` - The value to write
+    ///
+    fn write(buf: &mut BytesMut, val: Self);
+}
+
+macro_rules! attr_primitive {
+    ($t:ty, $get:ident, $put:ident, $dt:ident) => {
+        impl AttrPrimitive for $t {
+            const SIZE: usize = size_of::<$t>();
+            const DATA_TYPE: DataType = data_type::$dt;
+
+            #[inline]
+            fn read(buf: &mut dyn Buf) -> Self {
+                buf.$get()
+            }
+
+            #[inline]
+            fn write(buf: &mut BytesMut, val: Self) {
+                buf.$put(val);
+            }
+        }
+    };
+}
+
+attr_primitive!(i8, get_i8, put_i8, SINT);
+attr_primitive!(i16, get_i16_le, put_i16_le, INT);
+attr_primitive!(i32, get_i32_le, put_i32_le, DINT);
+attr_primitive!(i64, get_i64_le, put_i64_le, LINT);
+attr_primitive!(u8, get_u8, put_u8, USINT);
+attr_primitive!(u16, get_u16_le, put_u16_le, UINT);
+attr_primitive!(u32, get_u32_le, put_u32_le, UDINT);
+attr_primitive!(u64, get_u64_le, put_u64_le, ULINT);
+attr_primitive!(f32, get_f32_le, put_f32_le, REAL);
+attr_primitive!(f64, get_f64_le, put_f64_le, LREAL);
+
+/// A boolean is serialized as one byte, any non-zero value is interpreted as true.
+impl AttrPrimitive for bool {
+    const SIZE: usize = size_of::<u8>();
+    const DATA_TYPE: DataType = data_type::BOOL;
+
+    #[inline]
+    fn read(buf: &mut dyn Buf) -> Self {
+        buf.get_u8() != 0
+    }
+
+    #[inline]
+    fn write(buf: &mut BytesMut, val: Self) {
+        buf.put_u8(val as u8);
+    }
+}
+
+/// Attribute that holds a primitive value
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Attribute<T: AttrPrimitive> {
     /// The internal value
-    val: i8,
+    val: T,
     /// The allowed access methods
     acc: AccessCode,
+    /// The optional valid range, inclusive
+    bounds: Option<(T, T)>,
+    /// The optional set of valid values, e.g. of an enumeration. Any value if empty.
+    allowed: Vec<T>,
+    /// Changed since the last take_dirty(), not part of the equality
+    #[cfg_attr(feature = "serde", serde(skip))]
+    dirty: bool,
 }
 
-impl Sint {
+impl<T: AttrPrimitive> Attribute<T> {
     /// Creates an attribute.
     ///
     /// # Arguments
@@ -79,8 +221,72 @@ impl Sint {
     /// * `val` - The initial value
     /// * `access` - The accessibility via the eip interface, the internal get/set are not influenced.
     ///
-    pub fn new(val: i8, acc: AccessCode) -> Self {
-        Sint { val, acc }
+    pub fn new(val: T, acc: AccessCode) -> Self {
+        Attribute {
+            val,
+            acc,
+            bounds: None,
+            allowed: Vec::new(),
+            dirty: false,
+        }
+    }
+
+    /// Creates an attribute with a valid range.
+    /// A value outside the range is rejected when written via the eip interface
+    /// and clamped to the range when changed internally.
+    ///
+    /// # Arguments
+    ///
+    /// * `val` - The initial value, clamped to the range
+    /// * `access` - The accessibility via the eip interface, the internal get/set are not influenced.
+    /// * `min` - The minimum valid value, inclusive
+    /// * `max` - The maximum valid value, inclusive
+    ///
+    /// # Errors
+    ///
+    /// If the minimum exceeds the maximum, or they are not comparable, an error variant
+    /// will be returned.
+    ///
+    pub fn try_with_bounds(val: T, acc: AccessCode, min: T, max: T) -> Result<Self, ErrorCode> {
+        if !matches!(min.partial_cmp(&max), Some(o) if o.is_le()) {
+            return Err(INVALID_PARAMETER);
+        }
+        let mut attr = Attribute {
+            val,
+            acc,
+            bounds: Some((min, max)),
+            allowed: Vec::new(),
+            dirty: false,
+        };
+        attr.set(val);
+        attr.dirty = false;
+        Ok(attr)
+    }
+
+    /// Creates an attribute with a set of valid values, e.g. an enumeration.
+    /// A value outside the set is rejected, both via the eip interface and internally.
+    ///
+    /// # Arguments
+    ///
+    /// * `val` - The initial value
+    /// * `access` - The accessibility via the eip interface, the internal get/set are not influenced.
+    /// * `allowed` - The valid values
+    ///
+    /// # Errors
+    ///
+    /// If the initial value is not in the set an error variant will be returned.
+    ///
+    pub fn try_with_allowed(val: T, acc: AccessCode, allowed: &[T]) -> Result<Self, ErrorCode> {
+        if !allowed.contains(&val) {
+            return Err(INVALID_PARAMETER);
+        }
+        Ok(Attribute {
+            val,
+            acc,
+            bounds: None,
+            allowed: allowed.to_vec(),
+            dirty: false,
+        })
     }
 
     /// Retrieves the value from an attribute.
@@ -90,33 +296,92 @@ impl Sint {
     /// * The internal value
     ///
     #[inline]
-    pub fn get(&self) -> i8 {
+    pub fn get(&self) -> T {
         self.val
     }
 
-    /// Changes the value to an attribute.
+    /// Retrieves the accessibility via the eip interface.
+    ///
+    /// # Returns
+    ///
+    /// * The access code
+    ///
+    pub fn access(&self) -> &AccessCode {
+        &self.acc
+    }
+
+    /// Changes the value to an attribute and marks it dirty.
+    /// If the attribute has a valid range the value is clamped to it.
+    /// If the attribute has a set of valid values another value is ignored.
     ///
     /// # Arguments
     ///
     /// * `val` - The value to copy
     ///
     #[inline]
-    pub fn set(&mut self, val: i8) {
-        self.val = val;
+    pub fn set(&mut self, val: T) {
+        if !self.allowed.is_empty() && !self.allowed.contains(&val) {
+            warn!("Attribute::set() value not allowed, ignored");
+            return;
+        }
+        self.val = match self.bounds {
+            Some((min, _)) if val < min => min,
+            Some((_, max)) if val > max => max,
+            _ => val,
+        };
+        self.dirty = true;
     }
 
-    /// Get the serialized size in Bytes.
+    /// Test and clear the change flag, e.g. to apply a value written by a client once.
     ///
     /// # Returns
     ///
-    /// * The number of bytes when serialized
+    /// * true if the value is set or deserialized since the last call
     ///
-    pub const fn serial_size(&self) -> usize {
-        size_of::<i8>()
+    pub fn take_dirty(&mut self) -> bool {
+        core::mem::take(&mut self.dirty)
+    }
+
+    /// Test if a value is within the valid range.
+    ///
+    /// # Arguments
+    ///
+    /// * `val` - The value to test
+    ///
+    /// # Returns
+    ///
+    /// * true if there is no range or the value is within the range
+    ///
+    pub fn in_bounds(&self, val: T) -> bool {
+        let in_range = match self.bounds {
+            Some((min, max)) => min <= val && val <= max,
+            None => true,
+        };
+        in_range && (self.allowed.is_empty() || self.allowed.contains(&val))
+    }
+
+    /// Changes the value to an attribute, without clamping.
+    ///
+    /// # Arguments
+    ///
+    /// * `val` - The value to copy
+    ///
+    /// # Errors
+    ///
+    /// If the value is outside the valid range or set an error variant will be returned
+    /// and the current value is left untouched.
+    ///
+    pub fn try_set(&mut self, val: T) -> EipResult {
+        if !self.in_bounds(val) {
+            return Err(INVALID_PARAMETER);
+        }
+        self.val = val;
+        self.dirty = true;
+        Ok(())
     }
 }
 
-impl Serializing for Sint {
+impl<T: AttrPrimitive> Serializing for Attribute<T> {
     /// Read the value from a message buffer.
     ///
     /// # Arguments
@@ -130,7 +395,21 @@ impl Serializing for Sint {
         if buf.remaining() < self.serial_size() {
             return Err(NOT_ENOUGH_DATA);
         }
-        self.val = buf.get_i8();
+        // peek the value so an out of bounds value is not consumed
+        let mut bytes = [0; size_of::<u64>()]; // the largest primitive
+        let peeked = eip::peek(buf, &mut bytes[..T::SIZE]);
+        if !peeked {
+            buf.copy_to_slice(&mut bytes[..T::SIZE]); // split over chunks without std
+        }
+        let val = T::read(&mut &bytes[..T::SIZE]);
+        if !self.in_bounds(val) {
+            return Err(INVALID_PARAMETER);
+        }
+        if peeked {
+            buf.advance(T::SIZE);
+        }
+        self.val = val;
+        self.dirty = true;
         Ok(())
     }
 
@@ -147,21 +426,107 @@ impl Serializing for Sint {
         if buf.remaining_mut() < self.serial_size() {
             return Err(REPLY_DATA_TOO_LARGE);
         }
-        buf.put_i8(self.val);
+        T::write(buf, self.val);
         Ok(())
     }
+
+    /// Get the serialized size in Bytes.
+    ///
+    /// # Returns
+    ///
+    /// * The number of bytes when serialized
+    ///
+    fn serial_size(&self) -> usize {
+        T::SIZE
+    }
 }
 
+impl<T: AttrPrimitive> PartialEq for Attribute<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.val == other.val
+            && self.acc == other.acc
+            && self.bounds == other.bounds
+            && self.allowed == other.allowed
+    }
+}
+
+impl<T: AttrPrimitive> PartialOrd for Attribute<T> {
+    /// Compare the values.
+    /// Attributes with an equal value but a different access or validity are not comparable,
+    /// consistent with the equality of all fields.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match self.val.partial_cmp(&other.val) {
+            Some(Ordering::Equal) if self != other => None,
+            ord => ord,
+        }
+    }
+}
+
+/// Attribute that holds a boolean
+pub type Bool = Attribute<bool>;
+/// Attribute that holds an signed 8 bit integer
+pub type Sint = Attribute<i8>;
 /// Attribute that holds an signed 16 bit integer
-#[derive(Clone, Debug, Default, PartialEq)]
-pub struct Int {
-    /// The internal value
-    val: i16,
-    /// The allowed access methods
-    acc: AccessCode,
+pub type Int = Attribute<i16>;
+/// Attribute that holds an signed 32 bit integer
+pub type DInt = Attribute<i32>;
+/// Attribute that holds an signed 64 bit integer
+pub type Lint = Attribute<i64>;
+/// Attribute that holds an unsigned 8 bit integer
+pub type Usint = Attribute<u8>;
+/// Attribute that holds an unsigned 16 bit integer
+pub type Uint = Attribute<u16>;
+/// Attribute that holds an unsigned 32 bit integer
+pub type Duint = Attribute<u32>;
+/// Attribute that holds an unsigned 64 bit integer
+pub type Ulint = Attribute<u64>;
+/// Attribute that holds a 32 bit floating point value
+pub type Real = Attribute<f32>;
+/// Attribute that holds a 64 bit floating point value
+pub type Lreal = Attribute<f64>;
+
+/// An unsigned primitive that can be used as a bit string
+pub trait BitPrimitive: AttrPrimitive {
+    /// The number of bits
+    const BITS: u32;
+
+    /// Widen to the largest bit string
+    fn to_u64(self) -> u64;
+
+    /// Narrow from the largest bit string, the upper bits are discarded
+    fn from_u64(val: u64) -> Self;
+}
+
+macro_rules! bit_primitive {
+    ($t:ty) => {
+        impl BitPrimitive for $t {
+            const BITS: u32 = <$t>::BITS;
+
+            #[inline]
+            fn to_u64(self) -> u64 {
+                self as u64
+            }
+
+            #[inline]
+            fn from_u64(val: u64) -> Self {
+                val as $t
+            }
+        }
+    };
 }
 
-impl Int {
+bit_primitive!(u8);
+bit_primitive!(u16);
+bit_primitive!(u32);
+bit_primitive!(u64);
+
+/// Attribute that holds a bit string.
+/// Identical on the wire to the unsigned integer of the same width.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BitString<T: BitPrimitive>(Attribute<T>);
+
+impl<T: BitPrimitive> BitString<T> {
     /// Creates an attribute.
     ///
     /// # Arguments
@@ -169,8 +534,8 @@ impl Int {
     /// * `val` - The initial value
     /// * `access` - The accessibility via the eip interface, the internal get/set are not influenced.
     ///
-    pub fn new(val: i16, acc: AccessCode) -> Self {
-        Int { val, acc }
+    pub fn new(val: T, acc: AccessCode) -> Self {
+        BitString(Attribute::new(val, acc))
     }
 
     /// Retrieves the value from an attribute.
@@ -180,8 +545,18 @@ impl Int {
     /// * The internal value
     ///
     #[inline]
-    pub fn get(&self) -> i16 {
-        self.val
+    pub fn get(&self) -> T {
+        self.0.get()
+    }
+
+    /// Retrieves the accessibility via the eip interface.
+    ///
+    /// # Returns
+    ///
+    /// * The access code
+    ///
+    pub fn access(&self) -> &AccessCode {
+        self.0.access()
     }
 
     /// Changes the value to an attribute.
@@ -191,22 +566,57 @@ impl Int {
     /// * `val` - The value to copy
     ///
     #[inline]
-    pub fn set(&mut self, val: i16) {
-        self.val = val;
+    pub fn set(&mut self, val: T) {
+        self.0.set(val);
     }
 
-    /// Get the serialized size in Bytes.
+    /// Test and clear the change flag, e.g. to apply a value written by a client once.
     ///
     /// # Returns
     ///
-    /// * The number of bytes when serialized
+    /// * true if the value is set or deserialized since the last call
+    ///
+    pub fn take_dirty(&mut self) -> bool {
+        self.0.take_dirty()
+    }
+
+    /// Retrieves a single bit from the attribute.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The bit number, 0 is the least significant bit
+    ///
+    /// # Returns
+    ///
+    /// * The bit value, false if the index is out of range
+    ///
+    pub fn get_bit(&self, index: u32) -> bool {
+        index < T::BITS && (self.get().to_u64() >> index) & 1 != 0
+    }
+
+    /// Changes a single bit of the attribute.
+    /// An out of range index is ignored.
+    ///
+    /// # Arguments
     ///
-    pub const fn serial_size(&self) -> usize {
-        size_of::<i16>()
+    /// * `index` - The bit number, 0 is the least significant bit
+    /// * `val` - The bit value
+    ///
+    pub fn set_bit(&mut self, index: u32, val: bool) {
+        if index >= T::BITS {
+            return;
+        }
+        let bits = self.get().to_u64();
+        let bits = if val {
+            bits | (1 << index)
+        } else {
+            bits & !(1 << index)
+        };
+        self.set(T::from_u64(bits));
     }
 }
 
-impl Serializing for Int {
+impl<T: BitPrimitive> Serializing for BitString<T> {
     /// Read the value from a message buffer.
     ///
     /// # Arguments
@@ -214,14 +624,7 @@ impl Serializing for Int {
     /// * `buf` - The message buffer to read from
     ///
     fn deserialize(&mut self, buf: &mut dyn Buf) -> EipResult {
-        if !self.acc.settable() {
-            return Err(ATTRIBUTE_NOT_SETTABLE);
-        }
-        if buf.remaining() < self.serial_size() {
-            return Err(NOT_ENOUGH_DATA);
-        }
-        self.val = buf.get_i16_le();
-        Ok(())
+        self.0.deserialize(buf)
     }
 
     /// Write the value to a message buffer.
@@ -231,73 +634,93 @@ impl Serializing for Int {
     /// * `buf` - The message buffer to write to
     ///
     fn serialize(&self, buf: &mut BytesMut) -> EipResult {
-        if !self.acc.getable() {
-            return Err(ATTRIBUTE_NOT_GETTABLE);
-        }
-        if buf.remaining_mut() < self.serial_size() {
-            return Err(REPLY_DATA_TOO_LARGE);
-        }
-        buf.put_i16_le(self.val);
-        Ok(())
+        self.0.serialize(buf)
+    }
+
+    /// Get the serialized size in Bytes.
+    ///
+    /// # Returns
+    ///
+    /// * The number of bytes when serialized
+    ///
+    fn serial_size(&self) -> usize {
+        self.0.serial_size()
     }
 }
 
-/// Attribute that holds an signed 32 bit integer
-#[derive(Clone, Debug, Default, PartialEq)]
-pub struct DInt {
-    /// The internal value
-    val: i32,
-    /// The allowed access methods
+/// Attribute that holds a bit string of 8 bits
+pub type Byte = BitString<u8>;
+/// Attribute that holds a bit string of 16 bits
+pub type Word = BitString<u16>;
+/// Attribute that holds a bit string of 32 bits
+pub type Dword = BitString<u32>;
+/// Attribute that holds a bit string of 64 bits
+pub type Lword = BitString<u64>;
+
+/// Attribute that holds a fixed number of elements, serialized contiguously.
+/// Note: the elements keep their own access codes, these must allow the access as well.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AttrArray<T: Serializing + Clone + Default, const N: usize> {
+    items: [T; N],
     acc: AccessCode,
 }
 
-impl DInt {
+impl<T: Serializing + Clone + Default, const N: usize> AttrArray<T, N> {
     /// Creates an attribute.
     ///
     /// # Arguments
     ///
-    /// * `val` - The initial value
-    /// * `access` - The accessibility via the eip interface, the internal get/set are not influenced.
+    /// * `items` - The initial elements
+    /// * `acc` - The accessibility via the eip interface, the internal get/set are not influenced.
     ///
-    pub fn new(val: i32, acc: AccessCode) -> Self {
-        DInt { val, acc }
+    pub fn new(items: [T; N], acc: AccessCode) -> Self {
+        AttrArray { items, acc }
     }
 
-    /// Retrieves the value from an attribute.
+    /// Retrieves one element.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The element number, starting at 0
     ///
     /// # Returns
     ///
-    /// * The internal value
+    /// * The element, None if the index is out of range
     ///
-    #[inline]
-    pub fn get(&self) -> i32 {
-        self.val
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.items.get(index)
     }
 
-    /// Changes the value to an attribute.
+    /// Retrieves the accessibility via the eip interface.
     ///
-    /// # Arguments
+    /// # Returns
     ///
-    /// * `val` - The value to copy
+    /// * The access code
     ///
-    #[inline]
-    pub fn set(&mut self, val: i32) {
-        self.val = val;
+    pub fn access(&self) -> &AccessCode {
+        &self.acc
     }
 
-    /// Get the serialized size in Bytes.
+    /// Changes one element.
     ///
-    /// # Returns
+    /// # Arguments
     ///
-    /// * The number of bytes when serialized
+    /// * `index` - The element number, starting at 0
+    /// * `val` - The element to copy
+    ///
+    /// # Errors
     ///
-    pub const fn serial_size(&self) -> usize {
-        size_of::<i32>()
+    /// If the index is out of range an error variant will be returned.
+    ///
+    pub fn set(&mut self, index: usize, val: T) -> EipResult {
+        *self.items.get_mut(index).ok_or(INVALID_PARAMETER)? = val;
+        Ok(())
     }
 }
 
-impl Serializing for DInt {
-    /// Read the value from a message buffer.
+impl<T: Serializing + Clone + Default, const N: usize> Serializing for AttrArray<T, N> {
+    /// Read exactly N elements from a message buffer.
+    /// The elements are only changed if all of them are valid, no input is consumed otherwise.
     ///
     /// # Arguments
     ///
@@ -310,11 +733,15 @@ impl Serializing for DInt {
         if buf.remaining() < self.serial_size() {
             return Err(NOT_ENOUGH_DATA);
         }
-        self.val = buf.get_i32_le();
-        Ok(())
+        eip::deserialize_atomic(&mut self.items, buf, |items, buf| {
+            for item in items.iter_mut() {
+                item.deserialize(buf)?;
+            }
+            Ok(())
+        })
     }
 
-    /// Write the value to a message buffer.
+    /// Write all elements to a message buffer.
     ///
     /// # Arguments
     ///
@@ -327,66 +754,195 @@ impl Serializing for DInt {
         if buf.remaining_mut() < self.serial_size() {
             return Err(REPLY_DATA_TOO_LARGE);
         }
-        buf.put_i32_le(self.val);
+        for item in self.items.iter() {
+            item.serialize(buf)?;
+        }
         Ok(())
     }
+
+    /// Get the serialized size in Bytes.
+    ///
+    /// # Returns
+    ///
+    /// * The number of bytes when serialized
+    ///
+    fn serial_size(&self) -> usize {
+        self.items.iter().map(|i| i.serial_size()).sum()
+    }
 }
 
-/// Attribute that holds an unsigned 8 bit integer
-#[derive(Clone, Debug, Default, PartialEq)]
-pub struct Usint {
-    /// The internal value
-    val: u8,
-    /// The allowed access methods
+/// Default is all elements default
+impl<T: Serializing + Clone + Default, const N: usize> Default for AttrArray<T, N> {
+    fn default() -> Self {
+        AttrArray {
+            items: core::array::from_fn(|_| T::default()),
+            acc: Default::default(),
+        }
+    }
+}
+
+/// Read a length prefixed character string.
+/// All checks are done before reading, on an error the buffer is not consumed.
+/// Note: without std a string that is split over several chunks of the buffer is copied first
+/// to check it, the buffer is consumed in that case.
+///
+/// # Arguments
+///
+/// * `buf` - The message buffer to read from
+/// * `len_size` - The size of the little-endian length prefix, 1 or 2 Bytes
+/// * `cap` - The maximum number of Bytes of the string
+///
+/// # Returns
+///
+/// * The string
+///
+/// # Errors
+///
+/// An error variant will be returned if there is not enough data, the string is longer
+/// than the capacity or is not valid UTF-8.
+///
+fn deserialize_string(buf: &mut dyn Buf, len_size: usize, cap: usize) -> Result<String, ErrorCode> {
+    // check if the incoming buffer can have the size
+    if buf.remaining() < len_size {
+        return Err(NOT_ENOUGH_DATA);
+    }
+
+    // peek the size, or read it if it is split over chunks without std
+    let mut size = [0; 2];
+    let mut start = len_size; // not consumed bytes before the string
+    if !eip::peek(buf, &mut size[..len_size]) {
+        buf.copy_to_slice(&mut size[..len_size]);
+        start = 0;
+    }
+    let l = u16::from_le_bytes(size) as usize;
+
+    // check if the size is available in the incoming buffer
+    if buf.remaining() < start + l {
+        return Err(NOT_ENOUGH_DATA);
+    }
+
+    // check if the internal capacity is enough to hold the string
+    if cap < l {
+        return Err(TOO_MUCH_DATA);
+    }
+
+    // check if the incoming is unicode format
+    let mut end = start + l; // not consumed bytes up to the end of the string
+    let s = match buf.chunk().get(start..end) {
+        Some(b) => core::str::from_utf8(b).map(String::from),
+        None => {
+            let mut b = alloc::vec![0; end];
+            if !eip::peek(buf, &mut b) {
+                buf.advance(start);
+                buf.copy_to_slice(&mut b[start..]);
+                end = 0;
+            }
+            String::from_utf8(b.split_off(start)).map_err(|e| e.utf8_error())
+        }
+    };
+    let s = s.map_err(|_| INVALID_PARAMETER)?;
+    buf.advance(end);
+    Ok(s)
+}
+
+/// Attribute that holds an character string. Maximum length is 255 characters.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShortString {
+    buf: String, // Is deliberatly not Cow, favor simplicity over saving bytes in this case.
+    cap: usize,
     acc: AccessCode,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    dirty: bool, // Changed since the last take_dirty(), not part of the equality
 }
 
-impl Usint {
-    /// Creates an attribute.
+impl ShortString {
+    /// Creates an attribute with a maximum capacity
     ///
     /// # Arguments
     ///
     /// * `val` - The initial value
-    /// * `access` - The accessibility via the eip interface, the internal get/set are not influenced.
+    /// * `acc` - The accessibility via the eip interface, the internal get/set are not influenced.
+    /// * `capacity` - The capacity. Maximum is 255. A longer value is truncated as by set().
     ///
-    pub fn new(val: u8, acc: AccessCode) -> Self {
-        Usint { val, acc }
+    pub fn with_capacity(buf: String, acc: AccessCode, capacity: u8) -> Self {
+        let mut attr = ShortString {
+            buf: String::new(),
+            cap: capacity as usize,
+            acc,
+            dirty: false,
+        };
+        attr.set(buf);
+        attr.dirty = false;
+        attr
     }
 
-    /// Retrieves the value from an attribute.
+    /// Retrieves the accessibility via the eip interface.
     ///
     /// # Returns
     ///
-    /// * The internal value
+    /// * The access code
     ///
-    #[inline]
-    pub fn get(&self) -> u8 {
-        self.val
+    pub fn access(&self) -> &AccessCode {
+        &self.acc
     }
 
-    /// Changes the value to an attribute.
+    /// Set a string to the attribute.
     ///
     /// # Arguments
     ///
-    /// * `val` - The value to copy
+    /// * `buf` - The string to copy
     ///
-    #[inline]
-    pub fn set(&mut self, val: u8) {
-        self.val = val;
+    pub fn set(&mut self, mut buf: String) {
+        let len = buf.len();
+        if len > self.cap {
+            warn!(
+                "ShortString::set() String too long, truncated. Length: {}",
+                len
+            );
+            // never cut a multibyte character in half
+            let end = (0..=self.cap)
+                .rev()
+                .find(|&n| buf.is_char_boundary(n))
+                .unwrap_or(0);
+            buf.truncate(end);
+        }
+        self.buf = buf;
+        self.dirty = true;
     }
 
-    /// Get the serialized size in Bytes.
+    /// Test and clear the change flag, e.g. to apply a value written by a client once.
     ///
     /// # Returns
     ///
-    /// * The number of bytes when serialized
+    /// * true if the value is set or deserialized since the last call
+    ///
+    pub fn take_dirty(&mut self) -> bool {
+        core::mem::take(&mut self.dirty)
+    }
+
+    /// Set a string to the attribute, without truncation.
+    ///
+    /// # Arguments
     ///
-    pub const fn serial_size(&self) -> usize {
-        size_of::<u8>()
+    /// * `buf` - The string to copy
+    ///
+    /// # Errors
+    ///
+    /// If the string exceeds the capacity an error variant will be returned
+    /// and the current value is left untouched.
+    ///
+    pub fn try_set(&mut self, buf: String) -> EipResult {
+        if buf.len() > self.cap {
+            return Err(TOO_MUCH_DATA);
+        }
+        self.buf = buf;
+        self.dirty = true;
+        Ok(())
     }
 }
 
-impl Serializing for Usint {
+impl Serializing for ShortString {
     /// Read the value from a message buffer.
     ///
     /// # Arguments
@@ -397,10 +953,9 @@ impl Serializing for Usint {
         if !self.acc.settable() {
             return Err(ATTRIBUTE_NOT_SETTABLE);
         }
-        if buf.remaining() < self.serial_size() {
-            return Err(NOT_ENOUGH_DATA);
-        }
-        self.val = buf.get_u8();
+
+        self.buf = deserialize_string(buf, size_of::<u8>(), self.cap)?;
+        self.dirty = true;
         Ok(())
     }
 
@@ -414,69 +969,144 @@ impl Serializing for Usint {
         if !self.acc.getable() {
             return Err(ATTRIBUTE_NOT_GETTABLE);
         }
+
         if buf.remaining_mut() < self.serial_size() {
             return Err(REPLY_DATA_TOO_LARGE);
         }
-        buf.put_u8(self.val);
+
+        let len = u8::try_from(self.buf.len()).map_err(|_| REPLY_DATA_TOO_LARGE)?;
+        buf.put_u8(len);
+        buf.put(self.buf.as_bytes());
         Ok(())
     }
+
+    /// Get the serialized size in Bytes.
+    ///
+    /// # Returns
+    ///
+    /// * The number of bytes when serialized
+    ///
+    fn serial_size(&self) -> usize {
+        size_of::<u8>() + self.buf.len() // one for the size byte
+    }
 }
 
-/// Attribute that holds an unsigned 16 bit integer
-#[derive(Clone, Debug, Default, PartialEq)]
-pub struct Uint {
-    /// The internal value
-    val: u16,
-    /// The allowed access methods
+impl PartialEq for ShortString {
+    fn eq(&self, other: &Self) -> bool {
+        self.buf == other.buf && self.cap == other.cap && self.acc == other.acc
+    }
+}
+
+/// Default is only capacity set to max
+impl Default for ShortString {
+    fn default() -> Self {
+        ShortString {
+            buf: Default::default(),
+            cap: u8::MAX as usize,
+            acc: Default::default(),
+            dirty: false,
+        }
+    }
+}
+
+/// Attribute that holds an character string. Maximum length is 65535 characters.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CipString {
+    buf: String, // Is deliberatly not Cow, favor simplicity over saving bytes in this case.
+    cap: usize,
     acc: AccessCode,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    dirty: bool, // Changed since the last take_dirty(), not part of the equality
 }
 
-impl Uint {
-    /// Creates an attribute.
+impl CipString {
+    /// Creates an attribute with a maximum capacity
     ///
     /// # Arguments
     ///
     /// * `val` - The initial value
-    /// * `access` - The accessibility via the eip interface, the internal get/set are not influenced.
-    ///
-    pub fn new(val: u16, acc: AccessCode) -> Self {
-        Uint { val, acc }
+    /// * `acc` - The accessibility via the eip interface, the internal get/set are not influenced.
+    /// * `capacity` - The capacity. Maximum is 65535. A longer value is truncated as by set().
+    ///
+    pub fn with_capacity(buf: String, acc: AccessCode, capacity: u16) -> Self {
+        let mut attr = CipString {
+            buf: String::new(),
+            cap: capacity as usize,
+            acc,
+            dirty: false,
+        };
+        attr.set(buf);
+        attr.dirty = false;
+        attr
     }
 
-    /// Retrieves the value from an attribute.
+    /// Retrieves the accessibility via the eip interface.
     ///
     /// # Returns
     ///
-    /// * The internal value
+    /// * The access code
     ///
-    #[inline]
-    pub fn get(&self) -> u16 {
-        self.val
+    pub fn access(&self) -> &AccessCode {
+        &self.acc
     }
 
-    /// Changes the value to an attribute.
+    /// Set a string to the attribute.
     ///
     /// # Arguments
     ///
-    /// * `val` - The value to copy
+    /// * `buf` - The string to copy
     ///
-    #[inline]
-    pub fn set(&mut self, val: u16) {
-        self.val = val;
+    pub fn set(&mut self, mut buf: String) {
+        let len = buf.len();
+        if len > self.cap {
+            warn!(
+                "CipString::set() String too long, truncated. Length: {}",
+                len
+            );
+            // never cut a multibyte character in half
+            let end = (0..=self.cap)
+                .rev()
+                .find(|&n| buf.is_char_boundary(n))
+                .unwrap_or(0);
+            buf.truncate(end);
+        }
+        self.buf = buf;
+        self.dirty = true;
     }
 
-    /// Get the serialized size in Bytes.
+    /// Test and clear the change flag, e.g. to apply a value written by a client once.
     ///
     /// # Returns
     ///
-    /// * The number of bytes when serialized
+    /// * true if the value is set or deserialized since the last call
     ///
-    pub const fn serial_size(&self) -> usize {
-        size_of::<u16>()
+    pub fn take_dirty(&mut self) -> bool {
+        core::mem::take(&mut self.dirty)
+    }
+
+    /// Set a string to the attribute, without truncation.
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The string to copy
+    ///
+    /// # Errors
+    ///
+    /// If the string exceeds the capacity an error variant will be returned
+    /// and the current value is left untouched.
+    ///
+    pub fn try_set(&mut self, buf: String) -> EipResult {
+        if buf.len() > self.cap {
+            return Err(TOO_MUCH_DATA);
+        }
+        self.buf = buf;
+        self.dirty = true;
+        Ok(())
     }
 }
 
-impl Serializing for Uint {
+impl Serializing for CipString {
     /// Read the value from a message buffer.
     ///
     /// # Arguments
@@ -487,10 +1117,9 @@ impl Serializing for Uint {
         if !self.acc.settable() {
             return Err(ATTRIBUTE_NOT_SETTABLE);
         }
-        if buf.remaining() < self.serial_size() {
-            return Err(NOT_ENOUGH_DATA);
-        }
-        self.val = buf.get_u16_le();
+
+        self.buf = deserialize_string(buf, size_of::<u16>(), self.cap)?;
+        self.dirty = true;
         Ok(())
     }
 
@@ -504,164 +1133,180 @@ impl Serializing for Uint {
         if !self.acc.getable() {
             return Err(ATTRIBUTE_NOT_GETTABLE);
         }
+
         if buf.remaining_mut() < self.serial_size() {
             return Err(REPLY_DATA_TOO_LARGE);
         }
-        buf.put_u16_le(self.val);
+
+        let len = u16::try_from(self.buf.len()).map_err(|_| REPLY_DATA_TOO_LARGE)?;
+        buf.put_u16_le(len);
+        buf.put(self.buf.as_bytes());
         Ok(())
     }
+
+    /// Get the serialized size in Bytes.
+    ///
+    /// # Returns
+    ///
+    /// * The number of bytes when serialized
+    ///
+    fn serial_size(&self) -> usize {
+        size_of::<u16>() + self.buf.len() // two for the size word
+    }
 }
 
-/// Attribute that holds an unsigned 32 bit integer
+impl PartialEq for CipString {
+    fn eq(&self, other: &Self) -> bool {
+        self.buf == other.buf && self.cap == other.cap && self.acc == other.acc
+    }
+}
+
+/// Default is only capacity set to max
+impl Default for CipString {
+    fn default() -> Self {
+        CipString {
+            buf: Default::default(),
+            cap: u16::MAX as usize,
+            acc: Default::default(),
+            dirty: false,
+        }
+    }
+}
+
+/// Attribute that holds a path of segments, e.g. a connection path.
+/// The segments are kept as received, decoding is done on request.
+/// The path is preceded by its size, either in bytes or in 16 bit words.
+/// In the word form an odd path is followed by a pad byte.
 #[derive(Clone, Debug, Default, PartialEq)]
-pub struct Duint {
-    /// The internal value
-    val: u32,
-    /// The allowed access methods
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EPath {
+    path: Bytes,
     acc: AccessCode,
+    words: bool, // The size is the number of 16 bit words
 }
 
-impl Duint {
+impl EPath {
     /// Creates an attribute.
     ///
     /// # Arguments
     ///
-    /// * `val` - The initial value
-    /// * `access` - The accessibility via the eip interface, the internal get/set are not influenced.
-    ///
-    pub fn new(val: u32, acc: AccessCode) -> Self {
-        Duint { val, acc }
-    }
-
-    /// Retrieves the value from an attribute.
-    ///
-    /// # Returns
-    ///
-    /// * The internal value
+    /// * `path` - The initial path segments
+    /// * `acc` - The accessibility via the eip interface, the internal get/set are not influenced.
     ///
-    #[inline]
-    pub fn get(&self) -> u32 {
-        self.val
+    pub fn new(path: Bytes, acc: AccessCode) -> Self {
+        EPath {
+            path,
+            acc,
+            words: false,
+        }
     }
 
-    /// Changes the value to an attribute.
+    /// Creates an attribute that is framed by the number of 16 bit words.
     ///
     /// # Arguments
     ///
-    /// * `val` - The value to copy
+    /// * `path` - The initial path segments
+    /// * `acc` - The accessibility via the eip interface, the internal get/set are not influenced.
     ///
-    #[inline]
-    pub fn set(&mut self, val: u32) {
-        self.val = val;
+    pub fn with_word_length(path: Bytes, acc: AccessCode) -> Self {
+        EPath {
+            path,
+            acc,
+            words: true,
+        }
     }
 
-    /// Get the serialized size in Bytes.
+    /// Get the number of pad bytes when serialized
     ///
     /// # Returns
     ///
-    /// * The number of bytes when serialized
+    /// * One for an odd path in the word form, else zero
     ///
-    pub const fn serial_size(&self) -> usize {
-        size_of::<u32>()
+    fn pad(&self) -> usize {
+        if self.words {
+            self.path.len() % 2
+        } else {
+            0
+        }
     }
-}
 
-impl Serializing for Duint {
-    /// Read the value from a message buffer.
+    /// Retrieves the path segments.
     ///
-    /// # Arguments
+    /// # Returns
     ///
-    /// * `buf` - The message buffer to read from
+    /// * The raw segments
     ///
-    fn deserialize(&mut self, buf: &mut dyn Buf) -> EipResult {
-        if !self.acc.settable() {
-            return Err(ATTRIBUTE_NOT_SETTABLE);
-        }
-        if buf.remaining() < self.serial_size() {
-            return Err(NOT_ENOUGH_DATA);
-        }
-        self.val = buf.get_u32_le();
-        Ok(())
+    pub fn get(&self) -> &[u8] {
+        &self.path
     }
 
-    /// Write the value to a message buffer.
+    /// Retrieves the accessibility via the eip interface.
     ///
-    /// # Arguments
-    ///
-    /// * `buf` - The message buffer to write to
+    /// # Returns
     ///
-    fn serialize(&self, buf: &mut BytesMut) -> EipResult {
-        if !self.acc.getable() {
-            return Err(ATTRIBUTE_NOT_GETTABLE);
-        }
-        if buf.remaining_mut() < self.serial_size() {
-            return Err(REPLY_DATA_TOO_LARGE);
-        }
-        buf.put_u32_le(self.val);
-        Ok(())
-    }
-}
-
-/// Attribute that holds an character string. Maximum length is 255 characters.
-#[derive(Clone, Debug, PartialEq)]
-pub struct ShortString {
-    buf: String, // Is deliberatly not Cow, favor simplicity over saving bytes in this case.
-    cap: usize,
-    acc: AccessCode,
-}
+    /// * The access code
+    ///
+    pub fn access(&self) -> &AccessCode {
+        &self.acc
+    }
 
-impl ShortString {
-    /// Creates an attribute with a maximum capacity
+    /// Changes the path segments.
     ///
     /// # Arguments
     ///
-    /// * `val` - The initial value
-    /// * `acc` - The accessibility via the eip interface, the internal get/set are not influenced.
-    /// * `capacity` - The capacity. Maximum is 255.
+    /// * `path` - The segments to copy
     ///
-    pub fn with_capacity(buf: String, acc: AccessCode, capacity: u8) -> Self {
-        let len = buf.len();
-        let cap = capacity as usize;
-        if len > cap {
-            warn!(
-                "ShortString::with_capacity() String too long, truncated. Length: {}",
-                len
-            );
-        }
-        ShortString { buf, cap, acc }
+    pub fn set(&mut self, path: Bytes) {
+        self.path = path;
     }
 
-    /// Set a string to the attribute.
+    /// Decode the segments with the message router path parser
     ///
-    /// # Arguments
+    /// # Returns
     ///
-    /// * `buf` - The string to copy
+    /// * The decoded path, None if a segment is invalid
     ///
-    pub fn set(&mut self, buf: String) {
-        let len = buf.len();
-        if len > self.cap {
-            warn!(
-                "ShortString::set() String too long, truncated. Length: {}",
-                len
-            );
-            self.buf = (&buf[..self.cap]).into();
-        } else {
-            self.buf = buf;
+    fn decode(&self) -> Option<Request> {
+        let mut req = Request::default();
+        let mut buf = &self.path[..];
+        while buf.has_remaining() {
+            req.deserialize_segment(&mut buf).ok()?;
         }
+        Some(req)
     }
 
-    /// Get the serialized size in Bytes.
+    /// Get the class of the path
     ///
     /// # Returns
     ///
-    /// * The number of bytes when serialized
+    /// * The class identifier, None if not present or the path is invalid
     ///
-    pub fn serial_size(&self) -> usize {
-        size_of::<u8>() + self.buf.len() // one for the size byte
+    pub fn class(&self) -> Option<u32> {
+        self.decode()?.class
+    }
+
+    /// Get the instance of the path
+    ///
+    /// # Returns
+    ///
+    /// * The instance number, None if not present or the path is invalid
+    ///
+    pub fn instance(&self) -> Option<u32> {
+        self.decode()?.instance
+    }
+
+    /// Get the attribute of the path
+    ///
+    /// # Returns
+    ///
+    /// * The attribute identifier, None if not present or the path is invalid
+    ///
+    pub fn attribute(&self) -> Option<u32> {
+        self.decode()?.attribute
     }
 }
 
-impl Serializing for ShortString {
+impl Serializing for EPath {
     /// Read the value from a message buffer.
     ///
     /// # Arguments
@@ -672,32 +1317,25 @@ impl Serializing for ShortString {
         if !self.acc.settable() {
             return Err(ATTRIBUTE_NOT_SETTABLE);
         }
-
-        // check if the incoming buffer can have the size byte
-        if buf.remaining() < 1 {
+        let mut size = [0; 2];
+        if buf.remaining() < size.len() {
             return Err(NOT_ENOUGH_DATA);
         }
-
-        let l = buf.get_u8() as usize; // get size
-
-        // check if the size is available in the incoming buffer
-        if buf.remaining() < l {
-            return Err(NOT_ENOUGH_DATA);
+        let mut start = size.len(); // not consumed bytes before the path
+        if !eip::peek(buf, &mut size) {
+            buf.copy_to_slice(&mut size); // split over chunks without std
+            start = 0;
         }
-
-        // check if the internal capacity is enough to hold the string
-        if self.cap < l {
-            return Err(TOO_MUCH_DATA);
+        let mut len = u16::from_le_bytes(size) as usize;
+        if self.words {
+            len *= 2; // a pad byte is kept as part of the path
         }
-
-        // check if the incoming is unicode format
-        let s = match String::from_utf8(buf.copy_to_bytes(l).to_vec()) {
-            Ok(v) => v,
-            Err(_) => return Err(INVALID_PARAMETER),
-        };
-        self.buf = s;
-
-        Ok(()) // one for the size byte
+        if buf.remaining() < start + len {
+            return Err(NOT_ENOUGH_DATA);
+        }
+        buf.advance(start);
+        self.path = buf.copy_to_bytes(len);
+        Ok(())
     }
 
     /// Write the value to a message buffer.
@@ -710,25 +1348,32 @@ impl Serializing for ShortString {
         if !self.acc.getable() {
             return Err(ATTRIBUTE_NOT_GETTABLE);
         }
-
+        let len = if self.words {
+            self.path.len().div_ceil(2)
+        } else {
+            self.path.len()
+        };
+        if len > u16::MAX as usize {
+            return Err(TOO_MUCH_DATA);
+        }
         if buf.remaining_mut() < self.serial_size() {
             return Err(REPLY_DATA_TOO_LARGE);
         }
 
-        buf.put_u8(self.buf.len() as u8); // len is limited during assignment
-        buf.put(self.buf.as_bytes());
-        Ok(()) // one for the size byte
+        buf.put_u16_le(len as u16);
+        buf.put_slice(&self.path);
+        buf.put_bytes(0, self.pad());
+        Ok(())
     }
-}
 
-/// Default is only capacity set to max
-impl Default for ShortString {
-    fn default() -> Self {
-        ShortString {
-            buf: Default::default(),
-            cap: u8::MAX as usize,
-            acc: Default::default(),
-        }
+    /// Get the serialized size in Bytes.
+    ///
+    /// # Returns
+    ///
+    /// * The number of bytes when serialized
+    ///
+    fn serial_size(&self) -> usize {
+        size_of::<u16>() + self.path.len() + self.pad() // two for the size word
     }
 }
 
@@ -737,25 +1382,101 @@ fn auto_traits() {
     use crate::eip::check_auto_traits;
 
     check_auto_traits::<AccessCode>();
+    check_auto_traits::<Bool>();
+    check_auto_traits::<Lint>();
     check_auto_traits::<Usint>();
     check_auto_traits::<Uint>();
     check_auto_traits::<Duint>();
+    check_auto_traits::<Ulint>();
+    check_auto_traits::<Real>();
+    check_auto_traits::<Lreal>();
+    check_auto_traits::<Byte>();
+    check_auto_traits::<Word>();
+    check_auto_traits::<Dword>();
+    check_auto_traits::<Lword>();
     check_auto_traits::<ShortString>();
+    check_auto_traits::<CipString>();
+    check_auto_traits::<EPath>();
+    check_auto_traits::<AttrArray<Uint, 4>>();
 }
 
 #[test]
 fn access_codes() {
     let mut access = AccessCode::new(AccessCode::GET);
-    assert_eq!(true, access.getable());
-    assert_eq!(false, access.settable());
+    assert!(access.getable());
+    assert!(!access.settable());
 
     access = AccessCode::new(AccessCode::SET);
-    assert_eq!(false, access.getable());
-    assert_eq!(true, access.settable());
+    assert!(!access.getable());
+    assert!(access.settable());
 
     access = AccessCode::new(AccessCode::NONE);
-    assert_eq!(false, access.getable());
-    assert_eq!(false, access.settable());
+    assert!(!access.getable());
+    assert!(!access.settable());
+}
+
+#[test]
+fn access_codes_combine() {
+    let access = AccessCode::get_only() | AccessCode::set_only();
+    assert_eq!(AccessCode::get_set(), access);
+    assert_eq!(AccessCode::default(), access);
+    assert!(access.getable());
+    assert!(access.settable());
+
+    assert!(access.contains(&AccessCode::get_only()));
+    assert!(access.contains(&AccessCode::set_only()));
+    assert!(access.contains(&AccessCode::new(AccessCode::NONE)));
+    assert!(!AccessCode::get_only().contains(&AccessCode::set_only()));
+    assert!(!AccessCode::get_only().contains(&AccessCode::get_set()));
+    assert!(AccessCode::set_only().contains(&AccessCode::set_only()));
+}
+
+#[test]
+fn bool() {
+    let mut b = Bool::new(false, AccessCode::new(AccessCode::GET));
+    b.set(true);
+    assert!(b.get());
+
+    let mut buf = &b"\x06\x00"[..];
+    b = Bool::new(false, AccessCode::new(AccessCode::SET));
+    assert_eq!(Ok(()), b.deserialize(&mut buf));
+    assert_eq!(buf.remaining(), 1);
+    assert!(b.get());
+    assert_eq!(Ok(()), b.deserialize(&mut buf));
+    assert!(!b.get());
+    assert_eq!(Err(NOT_ENOUGH_DATA), b.deserialize(&mut buf));
+
+    let mut buf2 = BytesMut::with_capacity(10);
+    b = Bool::new(true, AccessCode::new(AccessCode::SET));
+    assert_eq!(Err(ATTRIBUTE_NOT_GETTABLE), b.serialize(&mut buf2));
+    b = Bool::new(true, AccessCode::new(AccessCode::GET));
+    assert_eq!(
+        Err(ATTRIBUTE_NOT_SETTABLE),
+        b.deserialize(&mut &b"\x00"[..])
+    );
+    assert_eq!(Ok(()), b.serialize(&mut buf2));
+    assert_eq!(1, buf2.len());
+    assert_eq!(&b"\x01"[..], &buf2);
+}
+
+#[test]
+fn bool_bounds() {
+    let mut buf = BytesMut::with_capacity(10);
+    let bounds_list: [bool; 2] = [false, true];
+    let access = AccessCode::new(AccessCode::GET | AccessCode::SET);
+    let mut inst = Bool::new(false, access.clone());
+
+    for &bound in bounds_list.iter() {
+        buf.clear();
+        inst.set(bound);
+        assert_eq!(Ok(()), inst.serialize(&mut buf));
+        assert_eq!(1, buf.len());
+        assert_eq!(bound as u8, buf[0]);
+
+        inst = Bool::new(!bound, access.clone());
+        assert_eq!(Ok(()), inst.deserialize(&mut &buf[..]));
+        assert_eq!(bound, inst.get());
+    }
 }
 
 #[test]
@@ -787,15 +1508,13 @@ fn sint_bounds() {
     let getable = AccessCode::new(AccessCode::GET);
     let mut inst = Sint::new(123, getable.clone());
 
-    for i in 0..bounds_list.len() {
-        let bound = bounds_list[i];
+    for &bound in bounds_list.iter() {
         inst.set(bound);
         assert_eq!(bound, inst.get());
     }
 
-    for i in 0..bounds_list.len() {
+    for &bound in bounds_list.iter() {
         buf.clear();
-        let bound = bounds_list[i];
         inst = Sint::new(bound, getable.clone());
         assert_eq!(bound, inst.get());
         assert_eq!(Ok(()), inst.serialize(&mut buf));
@@ -833,15 +1552,13 @@ fn int_bounds() {
     let getable = AccessCode::new(AccessCode::GET);
     let mut inst = Int::new(12345, getable.clone());
 
-    for i in 0..bounds_list.len() {
-        let bound = bounds_list[i];
+    for &bound in bounds_list.iter() {
         inst.set(bound);
         assert_eq!(bound, inst.get());
     }
 
-    for i in 0..bounds_list.len() {
+    for &bound in bounds_list.iter() {
         buf.clear();
-        let bound = bounds_list[i];
         inst = Int::new(bound, getable.clone());
         assert_eq!(bound, inst.get());
         assert_eq!(Ok(()), inst.serialize(&mut buf));
@@ -880,15 +1597,13 @@ fn dint_bounds() {
     let getable = AccessCode::new(AccessCode::GET);
     let mut inst = DInt::new(123456789, getable.clone());
 
-    for i in 0..bounds_list.len() {
-        let bound = bounds_list[i];
+    for &bound in bounds_list.iter() {
         inst.set(bound);
         assert_eq!(bound, inst.get());
     }
 
-    for i in 0..bounds_list.len() {
+    for &bound in bounds_list.iter() {
         buf.clear();
-        let bound = bounds_list[i];
         inst = DInt::new(bound, getable.clone());
         assert_eq!(bound, inst.get());
         assert_eq!(Ok(()), inst.serialize(&mut buf));
@@ -900,6 +1615,53 @@ fn dint_bounds() {
     }
 }
 
+#[test]
+fn lint() {
+    let mut lint = Lint::new(123, AccessCode::new(AccessCode::GET));
+    lint.set(2222222222222);
+    assert_eq!(2222222222222, lint.get());
+
+    let mut buf = &b"\x01\x02\x03\x04\x05\x06\x07\x08\x09"[..];
+    lint = Lint::new(123, AccessCode::new(AccessCode::SET));
+    assert_eq!(Ok(()), lint.deserialize(&mut buf));
+    assert_eq!(buf.remaining(), 1);
+    assert_eq!(0x0807060504030201, lint.get());
+    assert_eq!(Err(NOT_ENOUGH_DATA), lint.deserialize(&mut buf));
+
+    let mut buf2 = BytesMut::with_capacity(10);
+    lint = Lint::new(0x123456789abcdef0, AccessCode::new(AccessCode::GET));
+    assert_eq!(Ok(()), lint.serialize(&mut buf2));
+    assert_eq!(8, buf2.len());
+    assert_eq!(&b"\xf0\xde\xbc\x9a\x78\x56\x34\x12"[..], &buf2);
+
+    lint = Lint::new(12345678, AccessCode::new(AccessCode::GET | AccessCode::SET));
+    assert_eq!(12345678, lint.get());
+}
+
+#[test]
+fn lint_bounds() {
+    let mut buf = BytesMut::with_capacity(10);
+    let bounds_list: [i64; 3] = [i64::MIN, 0, i64::MAX];
+    let getable = AccessCode::new(AccessCode::GET);
+    let mut inst = Lint::new(123456789, getable.clone());
+
+    for &bound in bounds_list.iter() {
+        inst.set(bound);
+        assert_eq!(bound, inst.get());
+    }
+
+    for &bound in bounds_list.iter() {
+        buf.clear();
+        inst = Lint::new(bound, getable.clone());
+        assert_eq!(bound, inst.get());
+        assert_eq!(Ok(()), inst.serialize(&mut buf));
+        assert_eq!(8, buf.len());
+        for (n, byte) in buf.iter().enumerate() {
+            assert_eq!((bound >> (8 * n)) & 0xff, *byte as i64);
+        }
+    }
+}
+
 #[test]
 fn usint() {
     let mut sint = Usint::new(123, AccessCode::new(AccessCode::GET));
@@ -929,15 +1691,13 @@ fn usint_bounds() {
     let getable = AccessCode::new(AccessCode::GET);
     let mut inst = Usint::new(123, getable.clone());
 
-    for i in 0..bounds_list.len() {
-        let bound = bounds_list[i];
+    for &bound in bounds_list.iter() {
         inst.set(bound);
         assert_eq!(bound, inst.get());
     }
 
-    for i in 0..bounds_list.len() {
+    for &bound in bounds_list.iter() {
         buf.clear();
-        let bound = bounds_list[i];
         inst = Usint::new(bound, getable.clone());
         assert_eq!(bound, inst.get());
         assert_eq!(Ok(()), inst.serialize(&mut buf));
@@ -975,15 +1735,13 @@ fn uint_bounds() {
     let getable = AccessCode::new(AccessCode::GET);
     let mut inst = Uint::new(12345, getable.clone());
 
-    for i in 0..bounds_list.len() {
-        let bound = bounds_list[i];
+    for &bound in bounds_list.iter() {
         inst.set(bound);
         assert_eq!(bound, inst.get());
     }
 
-    for i in 0..bounds_list.len() {
+    for &bound in bounds_list.iter() {
         buf.clear();
-        let bound = bounds_list[i];
         inst = Uint::new(bound, getable.clone());
         assert_eq!(bound, inst.get());
         assert_eq!(Ok(()), inst.serialize(&mut buf));
@@ -1022,15 +1780,13 @@ fn duint_bounds() {
     let getable = AccessCode::new(AccessCode::GET);
     let mut inst = Duint::new(123456789, getable.clone());
 
-    for i in 0..bounds_list.len() {
-        let bound = bounds_list[i];
+    for &bound in bounds_list.iter() {
         inst.set(bound);
         assert_eq!(bound, inst.get());
     }
 
-    for i in 0..bounds_list.len() {
+    for &bound in bounds_list.iter() {
         buf.clear();
-        let bound = bounds_list[i];
         inst = Duint::new(bound, getable.clone());
         assert_eq!(bound, inst.get());
         assert_eq!(Ok(()), inst.serialize(&mut buf));
@@ -1042,6 +1798,210 @@ fn duint_bounds() {
     }
 }
 
+#[test]
+fn ulint() {
+    let mut ulint = Ulint::new(123, AccessCode::new(AccessCode::GET));
+    ulint.set(2222222222222);
+    assert_eq!(2222222222222, ulint.get());
+
+    let mut buf = &b"\x01\x02\x03\x04\x05\x06\x07\x08\x09"[..];
+    ulint = Ulint::new(123, AccessCode::new(AccessCode::SET));
+    assert_eq!(Ok(()), ulint.deserialize(&mut buf));
+    assert_eq!(buf.remaining(), 1);
+    assert_eq!(0x0807060504030201, ulint.get());
+    assert_eq!(Err(NOT_ENOUGH_DATA), ulint.deserialize(&mut buf));
+
+    let mut buf2 = BytesMut::with_capacity(10);
+    ulint = Ulint::new(0x123456789abcdef0, AccessCode::new(AccessCode::GET));
+    assert_eq!(Ok(()), ulint.serialize(&mut buf2));
+    assert_eq!(8, buf2.len());
+    assert_eq!(&b"\xf0\xde\xbc\x9a\x78\x56\x34\x12"[..], &buf2);
+
+    ulint = Ulint::new(12345678, AccessCode::new(AccessCode::GET | AccessCode::SET));
+    assert_eq!(12345678, ulint.get());
+}
+
+#[test]
+fn ulint_bounds() {
+    let mut buf = BytesMut::with_capacity(10);
+    let bounds_list: [u64; 3] = [0, u64::MAX / 2, u64::MAX];
+    let getable = AccessCode::new(AccessCode::GET);
+    let mut inst = Ulint::new(123456789, getable.clone());
+
+    for &bound in bounds_list.iter() {
+        inst.set(bound);
+        assert_eq!(bound, inst.get());
+    }
+
+    for &bound in bounds_list.iter() {
+        buf.clear();
+        inst = Ulint::new(bound, getable.clone());
+        assert_eq!(bound, inst.get());
+        assert_eq!(Ok(()), inst.serialize(&mut buf));
+        assert_eq!(8, buf.len());
+        for (n, byte) in buf.iter().enumerate() {
+            assert_eq!((bound >> (8 * n)) & 0xff, *byte as u64);
+        }
+    }
+}
+
+#[test]
+fn real() {
+    let mut real = Real::new(1.5, AccessCode::new(AccessCode::GET));
+    real.set(-2.25);
+    assert_eq!(-2.25, real.get());
+
+    let mut buf = &b"\x00\x00\xc0\x3f\x00\x00\x80"[..];
+    real = Real::new(0.0, AccessCode::new(AccessCode::SET));
+    assert_eq!(Ok(()), real.deserialize(&mut buf));
+    assert_eq!(buf.remaining(), 3);
+    assert_eq!(1.5, real.get());
+    assert_eq!(Err(NOT_ENOUGH_DATA), real.deserialize(&mut buf));
+
+    let mut buf2 = BytesMut::with_capacity(10);
+    real = Real::new(-2.25, AccessCode::new(AccessCode::GET));
+    assert_eq!(Ok(()), real.serialize(&mut buf2));
+    assert_eq!(4, buf2.len());
+    assert_eq!(&b"\x00\x00\x10\xc0"[..], &buf2);
+}
+
+#[test]
+fn real_bounds() {
+    let mut buf = BytesMut::with_capacity(10);
+    let bounds_list: [f32; 5] = [f32::MIN, 0.0, f32::MAX, f32::INFINITY, f32::NAN];
+    let access = AccessCode::new(AccessCode::GET | AccessCode::SET);
+    let mut inst = Real::new(0.0, access.clone());
+
+    for &bound in bounds_list.iter() {
+        buf.clear();
+        inst.set(bound);
+        assert_eq!(Ok(()), inst.serialize(&mut buf));
+        assert_eq!(4, buf.len());
+        assert_eq!(&bound.to_bits().to_le_bytes()[..], &buf[..]);
+
+        inst = Real::new(1.0, access.clone());
+        assert_eq!(Ok(()), inst.deserialize(&mut &buf[..]));
+        assert_eq!(bound.to_bits(), inst.get().to_bits());
+    }
+}
+
+#[test]
+fn lreal() {
+    let mut lreal = Lreal::new(1.5, AccessCode::new(AccessCode::GET));
+    lreal.set(-2.25);
+    assert_eq!(-2.25, lreal.get());
+
+    let mut buf = &b"\x00\x00\x00\x00\x00\x00\xf8\x3f\x00"[..];
+    lreal = Lreal::new(0.0, AccessCode::new(AccessCode::SET));
+    assert_eq!(Ok(()), lreal.deserialize(&mut buf));
+    assert_eq!(buf.remaining(), 1);
+    assert_eq!(1.5, lreal.get());
+    assert_eq!(Err(NOT_ENOUGH_DATA), lreal.deserialize(&mut buf));
+
+    let mut buf2 = BytesMut::with_capacity(10);
+    lreal = Lreal::new(-2.25, AccessCode::new(AccessCode::GET));
+    assert_eq!(Ok(()), lreal.serialize(&mut buf2));
+    assert_eq!(8, buf2.len());
+    assert_eq!(&b"\x00\x00\x00\x00\x00\x00\x02\xc0"[..], &buf2);
+}
+
+#[test]
+fn lreal_bounds() {
+    let mut buf = BytesMut::with_capacity(10);
+    let bounds_list: [(f64, &[u8]); 5] = [
+        (0.0, b"\x00\x00\x00\x00\x00\x00\x00\x00"),
+        (f64::MIN_POSITIVE / 2.0, b"\x00\x00\x00\x00\x00\x00\x08\x00"),
+        (f64::from_bits(1), b"\x01\x00\x00\x00\x00\x00\x00\x00"),
+        (f64::INFINITY, b"\x00\x00\x00\x00\x00\x00\xf0\x7f"),
+        (f64::NEG_INFINITY, b"\x00\x00\x00\x00\x00\x00\xf0\xff"),
+    ];
+    let access = AccessCode::new(AccessCode::GET | AccessCode::SET);
+    let mut inst = Lreal::new(0.0, access.clone());
+
+    for &(bound, bytes) in bounds_list.iter() {
+        buf.clear();
+        inst.set(bound);
+        assert_eq!(Ok(()), inst.serialize(&mut buf));
+        assert_eq!(8, buf.len());
+        assert_eq!(bytes, &buf[..]);
+
+        inst = Lreal::new(1.0, access.clone());
+        assert_eq!(Ok(()), inst.deserialize(&mut &buf[..]));
+        assert_eq!(bound.to_bits(), inst.get().to_bits());
+    }
+}
+
+#[test]
+fn bit_strings() {
+    let access = AccessCode::new(AccessCode::GET | AccessCode::SET);
+    let mut byte = Byte::new(0, access.clone());
+    byte.set_bit(0, true);
+    byte.set_bit(7, true);
+    byte.set_bit(8, true); // out of range, ignored
+    assert_eq!(0x81, byte.get());
+    assert!(byte.get_bit(7));
+    assert!(!byte.get_bit(6));
+    assert!(!byte.get_bit(8));
+    byte.set_bit(0, false);
+    assert_eq!(0x80, byte.get());
+
+    let mut word = Word::new(0xffff, access.clone());
+    word.set_bit(15, false);
+    word.set_bit(16, false);
+    assert_eq!(0x7fff, word.get());
+    assert!(word.get_bit(14));
+    assert!(!word.get_bit(15));
+
+    let mut dword = Dword::new(0, access.clone());
+    dword.set_bit(31, true);
+    dword.set_bit(32, true);
+    assert_eq!(0x8000_0000, dword.get());
+    assert!(dword.get_bit(31));
+    assert!(!dword.get_bit(32));
+
+    let mut lword = Lword::new(0, access.clone());
+    lword.set_bit(63, true);
+    lword.set_bit(64, true);
+    assert_eq!(0x8000_0000_0000_0000, lword.get());
+    assert!(lword.get_bit(63));
+    assert!(!lword.get_bit(64));
+}
+
+#[test]
+fn bit_strings_serialize() {
+    let access = AccessCode::new(AccessCode::GET | AccessCode::SET);
+    let mut buf = BytesMut::with_capacity(20);
+
+    let byte = Byte::new(0xa5, access.clone());
+    let word = Word::new(0x1234, access.clone());
+    let dword = Dword::new(0x12345678, access.clone());
+    let lword = Lword::new(0x123456789abcdef0, access.clone());
+    assert_eq!(Ok(()), byte.serialize(&mut buf));
+    assert_eq!(Ok(()), word.serialize(&mut buf));
+    assert_eq!(Ok(()), dword.serialize(&mut buf));
+    assert_eq!(Ok(()), lword.serialize(&mut buf));
+    assert_eq!(
+        &b"\xa5\x34\x12\x78\x56\x34\x12\xf0\xde\xbc\x9a\x78\x56\x34\x12"[..],
+        &buf
+    );
+
+    let mut rd = &buf[..];
+    let mut byte2 = Byte::new(0, access.clone());
+    let mut word2 = Word::new(0, access.clone());
+    let mut dword2 = Dword::new(0, access.clone());
+    let mut lword2 = Lword::new(0, access.clone());
+    assert_eq!(Ok(()), byte2.deserialize(&mut rd));
+    assert_eq!(Ok(()), word2.deserialize(&mut rd));
+    assert_eq!(Ok(()), dword2.deserialize(&mut rd));
+    assert_eq!(Ok(()), lword2.deserialize(&mut rd));
+    assert_eq!(0, rd.remaining());
+    assert_eq!(byte, byte2);
+    assert_eq!(word, word2);
+    assert_eq!(dword, dword2);
+    assert_eq!(lword, lword2);
+    assert_eq!(Err(NOT_ENOUGH_DATA), lword2.deserialize(&mut rd));
+}
+
 #[test]
 fn short_string() {
     let mut buf = &b"\x05Hello"[..];
@@ -1059,3 +2019,407 @@ fn short_string() {
     assert_eq!(6, buf2.len());
     assert_eq!(&b"\x05Hello"[..], &buf2);
 }
+
+#[test]
+fn short_string_truncate_multibyte() {
+    // 'ä' is two bytes, a capacity of 5 lands in the middle of the third character
+    let mut ss = ShortString::with_capacity("".into(), AccessCode::default(), 5);
+    ss.set("äääää".into());
+    assert_eq!("ää", ss.buf);
+
+    ss.set("ab€".into());
+    assert_eq!("ab€", ss.buf);
+
+    ss.set("abc€".into());
+    assert_eq!("abc", ss.buf);
+
+    let mut buf = BytesMut::with_capacity(10);
+    assert_eq!(Ok(()), ss.serialize(&mut buf));
+    assert_eq!(&b"\x03abc"[..], &buf);
+
+    // an oversized initial value is truncated the same way
+    let ss = ShortString::with_capacity("abc€".into(), AccessCode::default(), 5);
+    assert_eq!("abc", ss.buf);
+    let ss = ShortString::with_capacity("a".repeat(300), AccessCode::default(), u8::MAX);
+    assert_eq!(256, ss.serial_size());
+}
+
+#[test]
+fn short_string_try_set() {
+    let mut ss = ShortString::with_capacity("Hello".into(), AccessCode::default(), 5);
+    assert_eq!(Ok(()), ss.try_set("World".into()));
+    assert_eq!("World", ss.buf);
+
+    assert_eq!(Err(TOO_MUCH_DATA), ss.try_set("Hello World".into()));
+    assert_eq!("World", ss.buf);
+}
+
+#[test]
+fn short_string_error_not_consumed() {
+    let mut ss = ShortString::with_capacity("".into(), AccessCode::default(), 4);
+    let mut buf = &b"\x05Hello"[..];
+    assert_eq!(Err(TOO_MUCH_DATA), ss.deserialize(&mut buf));
+    assert_eq!(6, buf.remaining());
+
+    buf = &b"\x05Hell"[..];
+    assert_eq!(Err(NOT_ENOUGH_DATA), ss.deserialize(&mut buf));
+    assert_eq!(5, buf.remaining());
+
+    buf = &b"\x02\xff\xfe"[..];
+    assert_eq!(Err(INVALID_PARAMETER), ss.deserialize(&mut buf));
+    assert_eq!(3, buf.remaining());
+    assert_eq!("", ss.buf);
+
+    // a string split over two chunks
+    let mut chain = (&b"\x04\0He"[..]).chain(&b"ll!"[..]);
+    let mut cs = CipString::default();
+    assert_eq!(Ok(()), cs.deserialize(&mut chain));
+    assert_eq!("Hell", cs.buf);
+    assert_eq!(1, chain.remaining());
+
+    // the size split over two chunks
+    let mut chain = (&b"\x04"[..]).chain(&b"\0Hell!"[..]);
+    assert_eq!(Ok(()), cs.deserialize(&mut chain));
+    assert_eq!("Hell", cs.buf);
+    assert_eq!(1, chain.remaining());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn value_chunks_not_consumed() {
+    let mut inst = Uint::try_with_bounds(10, AccessCode::get_set(), 10, 1000).unwrap();
+
+    // out of bounds, split over two chunks
+    let mut chain = (&b"\x01"[..]).chain(&b"\x40\x7f"[..]);
+    assert_eq!(Err(INVALID_PARAMETER), inst.deserialize(&mut chain));
+    assert_eq!(3, chain.remaining());
+    assert_eq!(10, inst.get());
+
+    let mut chain = (&b"\xe8"[..]).chain(&b"\x03\x7f"[..]);
+    assert_eq!(Ok(()), inst.deserialize(&mut chain));
+    assert_eq!(1, chain.remaining());
+    assert_eq!(1000, inst.get());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn string_chunks_not_consumed() {
+    let mut cs = CipString::with_capacity("".into(), AccessCode::default(), 4);
+
+    // the size split over two chunks
+    let mut chain = (&b"\x05"[..]).chain(&b"\0Hello"[..]);
+    assert_eq!(Err(TOO_MUCH_DATA), cs.deserialize(&mut chain));
+    assert_eq!(7, chain.remaining());
+    let mut chain = (&b"\x04"[..]).chain(&b"\0Hel"[..]);
+    assert_eq!(Err(NOT_ENOUGH_DATA), cs.deserialize(&mut chain));
+    assert_eq!(5, chain.remaining());
+    let mut chain = (&b"\x02"[..]).chain(&b"\0\xff\xfe"[..]);
+    assert_eq!(Err(INVALID_PARAMETER), cs.deserialize(&mut chain));
+    assert_eq!(4, chain.remaining());
+
+    // the string split over two chunks
+    let mut chain = (&b"\x02\0\xff"[..]).chain(&b"\xfe"[..]);
+    assert_eq!(Err(INVALID_PARAMETER), cs.deserialize(&mut chain));
+    assert_eq!(4, chain.remaining());
+    assert_eq!("", cs.buf);
+}
+
+#[test]
+fn cip_string() {
+    let mut buf = &b"\x05\0Hello"[..];
+    let mut cs = CipString::default();
+    assert_eq!(Ok(()), cs.deserialize(&mut buf));
+    assert_eq!(buf.remaining(), 0);
+    assert_eq!("Hello", cs.buf);
+
+    let mut buf2 = BytesMut::with_capacity(10);
+    let cs = CipString::with_capacity(
+        "Hello".into(),
+        AccessCode::new(AccessCode::GET | AccessCode::SET),
+        100,
+    );
+    assert_eq!(Ok(()), cs.serialize(&mut buf2));
+    assert_eq!(7, buf2.len());
+    assert_eq!(&b"\x05\0Hello"[..], &buf2);
+}
+
+#[test]
+fn cip_string_bounds() {
+    let mut cs = CipString::with_capacity("".into(), AccessCode::default(), 4);
+    assert_eq!(Err(NOT_ENOUGH_DATA), cs.deserialize(&mut &b"\x05"[..]));
+    assert_eq!(
+        Err(NOT_ENOUGH_DATA),
+        cs.deserialize(&mut &b"\x05\0Hell"[..])
+    );
+    assert_eq!(Err(TOO_MUCH_DATA), cs.deserialize(&mut &b"\x05\0Hello"[..]));
+    assert_eq!(Ok(()), cs.deserialize(&mut &b"\x04\0Hell"[..]));
+    assert_eq!("Hell", cs.buf);
+
+    // an oversized initial value is truncated on a character boundary
+    let mut cs = CipString::with_capacity("Heäll".into(), AccessCode::default(), 3);
+    assert_eq!("He", cs.buf);
+    assert!(!cs.take_dirty());
+    let mut buf = BytesMut::with_capacity(10);
+    assert_eq!(Ok(()), cs.serialize(&mut buf));
+    assert_eq!(&b"\x02\0He"[..], &buf);
+
+    let cs = CipString::with_capacity("a".repeat(70000), AccessCode::default(), u16::MAX);
+    assert_eq!(u16::MAX as usize + 2, cs.serial_size());
+}
+
+#[test]
+fn bounds() {
+    let access = AccessCode::new(AccessCode::GET | AccessCode::SET);
+    let mut usint = Usint::try_with_bounds(0, access.clone(), 1, 255).unwrap();
+    assert_eq!(1, usint.get());
+    assert!(!usint.in_bounds(0));
+    assert!(usint.in_bounds(1));
+
+    let mut buf = &b"\x00\x0a"[..];
+    assert_eq!(Err(INVALID_PARAMETER), usint.deserialize(&mut buf));
+    assert_eq!(1, usint.get());
+    assert_eq!(2, buf.remaining()); // not consumed
+    buf.advance(1);
+    assert_eq!(Ok(()), usint.deserialize(&mut buf));
+    assert_eq!(10, usint.get());
+
+    let mut int = Int::try_with_bounds(5, access.clone(), -10, 10).unwrap();
+    int.set(-11);
+    assert_eq!(-10, int.get());
+    int.set(11);
+    assert_eq!(10, int.get());
+    int.set(3);
+    assert_eq!(3, int.get());
+
+    buf = &b"\x0b\x00"[..];
+    assert_eq!(Err(INVALID_PARAMETER), int.deserialize(&mut buf));
+    assert_eq!(3, int.get());
+
+    let mut real = Real::try_with_bounds(0.5, access.clone(), 0.0, 1.0).unwrap();
+    buf = &b"\x00\x00\xc0\x3f"[..]; // 1.5
+    assert_eq!(Err(INVALID_PARAMETER), real.deserialize(&mut buf));
+    assert_eq!(0.5, real.get());
+    buf = &b"\x00\x00\xc0\x7f"[..]; // NaN
+    assert_eq!(Err(INVALID_PARAMETER), real.deserialize(&mut buf));
+    assert_eq!(0.5, real.get());
+
+    // an empty or not comparable range
+    assert_eq!(
+        Err(INVALID_PARAMETER),
+        Int::try_with_bounds(0, access.clone(), 1, -1)
+    );
+    assert_eq!(
+        Err(INVALID_PARAMETER),
+        Real::try_with_bounds(0.0, access.clone(), f32::NAN, 1.0)
+    );
+
+    let mut uint = Uint::new(0, access);
+    assert!(uint.in_bounds(u16::MAX));
+    uint.set(u16::MAX);
+    assert_eq!(u16::MAX, uint.get());
+}
+
+#[test]
+fn epath() {
+    let path = EPath::new(
+        Bytes::from_static(b"\x20\x04\x24\x65"),
+        AccessCode::get_set(),
+    );
+    assert_eq!(Some(0x04), path.class());
+    assert_eq!(Some(0x65), path.instance());
+    assert_eq!(None, path.attribute());
+
+    let mut buf = BytesMut::with_capacity(10);
+    assert_eq!(Ok(()), path.serialize(&mut buf));
+    assert_eq!(path.serial_size(), buf.len());
+    assert_eq!(&b"\x04\0\x20\x04\x24\x65"[..], buf);
+
+    let mut received = EPath::new(Bytes::new(), AccessCode::get_set());
+    assert_eq!(Ok(()), received.deserialize(&mut &buf[..]));
+    assert_eq!(path, received);
+
+    // invalid segment
+    received.set(Bytes::from_static(b"\x20"));
+    assert_eq!(None, received.class());
+
+    // length beyond the data, not consumed
+    let mut data = &b"\x04\0\x20\x04"[..];
+    assert_eq!(Err(NOT_ENOUGH_DATA), received.deserialize(&mut data));
+    assert_eq!(4, data.len());
+    assert_eq!(&b"\x20"[..], received.get());
+}
+
+#[test]
+fn epath_word_length() {
+    // even
+    let path = EPath::with_word_length(
+        Bytes::from_static(b"\x20\x04\x24\x65"),
+        AccessCode::get_set(),
+    );
+    let mut buf = BytesMut::with_capacity(10);
+    assert_eq!(Ok(()), path.serialize(&mut buf));
+    assert_eq!(path.serial_size(), buf.len());
+    assert_eq!(&b"\x02\0\x20\x04\x24\x65"[..], buf);
+
+    let mut received = EPath::with_word_length(Bytes::new(), AccessCode::get_set());
+    assert_eq!(Ok(()), received.deserialize(&mut &buf[..]));
+    assert_eq!(path, received);
+    assert_eq!(Some(0x65), received.instance());
+
+    // odd, padded
+    let path = EPath::with_word_length(Bytes::from_static(b"\x91\x03abc"), AccessCode::get_set());
+    buf.clear();
+    assert_eq!(Ok(()), path.serialize(&mut buf));
+    assert_eq!(path.serial_size(), buf.len());
+    assert_eq!(&b"\x03\0\x91\x03abc\0"[..], buf);
+
+    let mut data = &buf[..];
+    assert_eq!(Ok(()), received.deserialize(&mut data));
+    assert_eq!(0, data.len());
+    assert_eq!(&b"\x91\x03abc\0"[..], received.get());
+
+    // received pad is kept, round trips exactly
+    let mut out = BytesMut::with_capacity(10);
+    assert_eq!(Ok(()), received.serialize(&mut out));
+    assert_eq!(buf, out);
+
+    assert_eq!(
+        Err(NOT_ENOUGH_DATA),
+        received.deserialize(&mut &b"\x03\0\x91\x03abc"[..])
+    );
+}
+
+#[test]
+fn attr_array() {
+    let acc = AccessCode::get_set();
+    let mut array: AttrArray<Uint, 4> = AttrArray::new(
+        core::array::from_fn(|n| Uint::new(n as u16 + 1, acc.clone())),
+        acc.clone(),
+    );
+    assert_eq!(8, array.serial_size());
+    assert_eq!(Some(3), array.get(2).map(|u| u.get()));
+    assert_eq!(None, array.get(4));
+    assert_eq!(Ok(()), array.set(3, Uint::new(0x1234, acc.clone())));
+    assert_eq!(Err(INVALID_PARAMETER), array.set(4, Uint::default()));
+
+    let mut buf = BytesMut::with_capacity(10);
+    assert_eq!(Ok(()), array.serialize(&mut buf));
+    assert_eq!(&b"\x01\0\x02\0\x03\0\x34\x12"[..], buf);
+
+    let mut received = array.clone();
+    let mut data = &b"\x05\0\x06\0\x07\0\x08\0\x09\0"[..];
+    assert_eq!(Ok(()), received.deserialize(&mut data));
+    assert_eq!(2, data.len()); // exactly four elements
+    assert_eq!(Some(8), received.get(3).map(|u| u.get()));
+
+    // not enough data, nothing changed
+    assert_eq!(
+        Err(NOT_ENOUGH_DATA),
+        received.deserialize(&mut &b"\x01\0\x02\0\x03\0"[..])
+    );
+    assert_eq!(Some(5), received.get(0).map(|u| u.get()));
+
+    // the last element is out of range, nothing changed nor consumed
+    let mut bounded: AttrArray<Uint, 4> = AttrArray::new(
+        core::array::from_fn(|_| Uint::try_with_bounds(1, acc.clone(), 1, 10).unwrap()),
+        acc,
+    );
+    let mut data = &b"\x02\0\x03\0\x04\0\x0b\0"[..];
+    assert_eq!(Err(INVALID_PARAMETER), bounded.deserialize(&mut data));
+    assert_eq!(8, data.len());
+    assert_eq!(Some(1), bounded.get(0).map(|u| u.get()));
+
+    let mut read_only: AttrArray<Uint, 4> =
+        AttrArray::new(Default::default(), AccessCode::get_only());
+    assert_eq!(
+        Err(ATTRIBUTE_NOT_SETTABLE),
+        read_only.deserialize(&mut &buf[..])
+    );
+}
+
+#[test]
+fn usint_allowed() {
+    assert_eq!(
+        Err(INVALID_PARAMETER),
+        Usint::try_with_allowed(2, AccessCode::get_set(), &[0, 1])
+    );
+    let mut reset_type = Usint::try_with_allowed(1, AccessCode::get_set(), &[0, 1]).unwrap();
+
+    let mut buf = &b"\x02"[..];
+    assert_eq!(Err(INVALID_PARAMETER), reset_type.deserialize(&mut buf));
+    assert_eq!(1, reset_type.get());
+    assert_eq!(1, buf.len());
+
+    buf = &b"\x00"[..];
+    assert_eq!(Ok(()), reset_type.deserialize(&mut buf));
+    assert_eq!(0, reset_type.get());
+
+    assert_eq!(Err(INVALID_PARAMETER), reset_type.try_set(2));
+    reset_type.set(2);
+    assert_eq!(0, reset_type.get());
+    assert_eq!(Ok(()), reset_type.try_set(1));
+    assert_eq!(1, reset_type.get());
+}
+
+#[test]
+fn uint_compare() {
+    let low = Uint::new(1, AccessCode::get_only());
+    let high = Uint::new(2, AccessCode::get_only());
+    assert!(low < high);
+    assert!(high >= low);
+    assert_eq!(Some(Ordering::Equal), low.partial_cmp(&low.clone()));
+
+    // equal value, different access
+    let other = Uint::new(1, AccessCode::get_set());
+    assert_eq!(None, low.partial_cmp(&other));
+    assert!(other > Uint::default());
+}
+
+#[test]
+fn access() {
+    let get_only = Uint::new(1, AccessCode::get_only());
+    assert!(get_only.access().getable());
+    assert!(!get_only.access().settable());
+
+    assert!(Word::new(1, AccessCode::get_set()).access().settable());
+    let name = ShortString::with_capacity(String::new(), AccessCode::get_only(), 4);
+    assert_eq!(&AccessCode::get_only(), name.access());
+    let path = EPath::new(Bytes::new(), AccessCode::new(AccessCode::NONE));
+    assert!(!path.access().getable());
+}
+
+#[test]
+fn take_dirty() {
+    let mut attr = Uint::try_with_bounds(5, AccessCode::get_set(), 0, 10).unwrap();
+    assert!(!attr.take_dirty());
+
+    assert_eq!(Ok(()), attr.deserialize(&mut &b"\x07\0"[..]));
+    assert_eq!(
+        attr,
+        Uint::try_with_bounds(7, AccessCode::get_set(), 0, 10).unwrap()
+    ); // not compared
+    assert!(attr.take_dirty());
+    assert!(!attr.take_dirty());
+
+    // a rejected write leaves the flag
+    assert_eq!(
+        Err(INVALID_PARAMETER),
+        attr.deserialize(&mut &b"\x0b\0"[..])
+    );
+    assert!(!attr.take_dirty());
+
+    attr.set(1);
+    assert!(attr.take_dirty());
+
+    let mut word = Word::new(0, AccessCode::get_set());
+    assert_eq!(Ok(()), word.deserialize(&mut &b"\x01\0"[..]));
+    assert!(word.take_dirty());
+
+    let mut name = ShortString::with_capacity(String::new(), AccessCode::get_set(), 8);
+    assert!(!name.take_dirty());
+    assert_eq!(Ok(()), name.deserialize(&mut &b"\x02ab"[..]));
+    assert!(name.take_dirty());
+    assert!(!name.take_dirty());
+    name.set(String::from("cd"));
+    assert!(name.take_dirty());
+}
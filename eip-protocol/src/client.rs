@@ -0,0 +1,353 @@
+use crate::cpf::CommonPacketFormat;
+use crate::eip::{EipResult, Serializing};
+use crate::encapsulation::{
+    parse_register_session_reply, register_session_request, Command, Encapsulation,
+};
+use crate::error_code::{ErrorCode, MESSAGE_FORMAT_ERROR, NOT_ENOUGH_DATA, SUCCESS};
+use crate::item::Item;
+use crate::message::EipMessage;
+use crate::message_router::{Request, Response};
+use bytes::{Bytes, BytesMut};
+use log::warn;
+use std::io::{Read, Write};
+
+/// Client side of explicit messaging, Message Router requests over a registered session.
+/// Every request carries a new sender context that must be echoed by the reply.
+
+#[derive(Debug)]
+pub struct ExplicitClient<S: Read + Write> {
+    stream: S,
+    session: u32, // The registered session handle, 0 if not registered
+    context: u64, // The sender context of the last request
+}
+
+/// Map a failed stream operation
+///
+/// # Arguments
+///
+/// * `e` - The I/O error
+///
+/// # Returns
+///
+/// * The error code reported for a broken connection
+///
+fn io_error(e: std::io::Error) -> ErrorCode {
+    warn!("ExplicitClient connection failed: {}", e);
+    NOT_ENOUGH_DATA
+}
+
+/// Read one complete frame, the encapsulation header and its command specific data
+///
+/// # Arguments
+///
+/// * `stream` - The stream to read from
+///
+/// # Returns
+///
+/// * The frame, the header followed by the data
+///
+/// # Errors
+///
+/// An error variant will be returned if the stream fails or is closed.
+///
+fn read_frame<S: Read>(stream: &mut S) -> Result<BytesMut, ErrorCode> {
+    let mut enc = Encapsulation::default();
+    let mut frame = vec![0; enc.serial_size()];
+    stream.read_exact(&mut frame).map_err(io_error)?;
+    enc.deserialize(&mut &frame[..])?;
+
+    let start = frame.len();
+    frame.resize(start + enc.len as usize, 0);
+    stream.read_exact(&mut frame[start..]).map_err(io_error)?;
+    Ok(BytesMut::from(&frame[..]))
+}
+
+impl<S: Read + Write> ExplicitClient<S> {
+    /// Create an instance, the session is not registered yet.
+    ///
+    /// # Arguments
+    ///
+    /// * `stream` - The connected stream to the server, e.g. a TcpStream
+    ///
+    /// # Returns
+    ///
+    /// * The created instance
+    ///
+    pub fn new(stream: S) -> Self {
+        ExplicitClient {
+            stream,
+            session: 0,
+            context: 0,
+        }
+    }
+
+    /// Get the session handle
+    ///
+    /// # Returns
+    ///
+    /// * The session handle assigned by the server, 0 if not registered
+    ///
+    pub fn session(&self) -> u32 {
+        self.session
+    }
+
+    /// Create the header of the next request, with a new sender context
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - The encapsulation command
+    ///
+    /// # Returns
+    ///
+    /// * The encapsulation header
+    ///
+    fn next_header(&mut self, command: Command) -> Encapsulation {
+        self.context = self.context.wrapping_add(1);
+        Encapsulation {
+            command,
+            session: self.session,
+            context: self.context.to_le_bytes(),
+            ..Default::default()
+        }
+    }
+
+    /// Read the reply to the last request
+    ///
+    /// # Returns
+    ///
+    /// * The reply frame
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if the stream fails or the reply does not echo
+    /// the sender context of the request.
+    ///
+    fn read_reply(&mut self) -> Result<BytesMut, ErrorCode> {
+        let frame = read_frame(&mut self.stream)?;
+        let mut enc = Encapsulation::default();
+        enc.deserialize(&mut &frame[..])?;
+        if enc.context != self.context.to_le_bytes() {
+            return Err(MESSAGE_FORMAT_ERROR);
+        }
+        Ok(frame)
+    }
+
+    /// Register a session
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if the stream fails or the server rejects the session.
+    ///
+    pub fn register(&mut self) -> EipResult {
+        let mut body = BytesMut::with_capacity(4);
+        register_session_request(&mut body)?;
+
+        let mut buf = BytesMut::with_capacity(100);
+        self.next_header(Encapsulation::REGISTER_SESSION)
+            .serialize_with_payload(&mut buf, &body)?;
+        self.stream.write_all(&buf).map_err(io_error)?;
+
+        let frame = self.read_reply()?;
+        let mut enc = Encapsulation::default();
+        enc.deserialize(&mut &frame[..])?;
+        self.session = parse_register_session_reply(&enc)?;
+        Ok(())
+    }
+
+    /// Read one attribute with a Get_Attribute_Single request
+    ///
+    /// # Arguments
+    ///
+    /// * `class` - The class identifier
+    /// * `instance` - The instance number
+    /// * `attribute` - The attribute identifier
+    ///
+    /// # Returns
+    ///
+    /// * The attribute value
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if the stream fails, the reply is invalid or
+    /// the general status of the response.
+    ///
+    pub fn get_attribute_single(
+        &mut self,
+        class: u32,
+        instance: u32,
+        attribute: u32,
+    ) -> Result<BytesMut, ErrorCode> {
+        let req = Request::get_attribute_single(class, instance, attribute);
+        let mut cpf = CommonPacketFormat::default();
+        cpf.push(Item::NULL_ADDRESS, Bytes::new());
+        cpf.push(Item::UNCONNECTED_DATA, req.to_bytes()?.freeze());
+        let msg = EipMessage {
+            encapsulation: self.next_header(Encapsulation::SEND_RR_DATA),
+            cpf,
+            ..Default::default()
+        };
+        self.stream.write_all(&msg.to_bytes()?).map_err(io_error)?;
+
+        let frame = self.read_reply()?;
+        let mut reply = EipMessage::default();
+        reply.deserialize(&mut &frame[..])?;
+        if reply.encapsulation.status != 0 {
+            return Err(u8::try_from(reply.encapsulation.status)
+                .map_or(MESSAGE_FORMAT_ERROR, ErrorCode::from));
+        }
+
+        let data = reply
+            .cpf
+            .find(&Item::UNCONNECTED_DATA)
+            .ok_or(MESSAGE_FORMAT_ERROR)?;
+        let mut res = Response::default();
+        res.deserialize(&mut &data[..])?;
+        if res.service != req.service.as_response() {
+            return Err(MESSAGE_FORMAT_ERROR);
+        }
+        if res.general_status != SUCCESS {
+            return Err(res.general_status);
+        }
+        Ok(res.data)
+    }
+
+    /// Unregister the session, the server sends no reply
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if the stream fails.
+    ///
+    pub fn unregister(&mut self) -> EipResult {
+        let mut buf = BytesMut::with_capacity(100);
+        self.next_header(Encapsulation::UNREGISTER_SESSION)
+            .serialize_with_payload(&mut buf, &[])?;
+        self.stream.write_all(&buf).map_err(io_error)?;
+        self.session = 0;
+        Ok(())
+    }
+}
+
+/// Serve one client with the crate's server side pieces, until the session is unregistered
+#[cfg(test)]
+fn serve(mut stream: std::net::TcpStream, mut router: crate::message_router::Router) {
+    use crate::session::Session;
+
+    let mut session = Session::default();
+    while let Ok(frame) = read_frame(&mut stream) {
+        let mut data = &frame[..];
+        let mut enc = Encapsulation::default();
+        enc.deserialize(&mut data).unwrap();
+
+        let mut reply = BytesMut::with_capacity(600);
+        match enc.command {
+            Encapsulation::REGISTER_SESSION => {
+                let mut body = BytesMut::with_capacity(4);
+                session
+                    .register(&mut data, &mut body, &mut enc.session)
+                    .unwrap();
+                enc.serialize_with_payload(&mut reply, &body).unwrap();
+            }
+            Encapsulation::UNREGISTER_SESSION => {
+                session.unregister(enc.session).unwrap();
+                return;
+            }
+            Encapsulation::SEND_RR_DATA => {
+                assert!(session.check(enc.session));
+                let mut msg = EipMessage::default();
+                msg.deserialize(&mut &frame[..]).unwrap();
+                let mut data = &msg.cpf.find(&Item::UNCONNECTED_DATA).unwrap()[..];
+                let mut req = Request::default();
+                req.deserialize(&mut data).unwrap();
+                let res = router.dispatch(&req, &mut data);
+
+                msg.cpf = CommonPacketFormat::default();
+                msg.cpf.push(Item::NULL_ADDRESS, Bytes::new());
+                msg.cpf
+                    .push(Item::UNCONNECTED_DATA, res.to_bytes().unwrap().freeze());
+                msg.serialize(&mut reply).unwrap();
+            }
+            _ => panic!("unexpected command {:?}", enc.command),
+        }
+        stream.write_all(&reply).unwrap();
+    }
+}
+
+#[test]
+fn loopback() {
+    use crate::error_code::{ATTRIBUTE_NOT_SUPPORTED, PATH_DESTINATION_UNKNOWN};
+    use crate::identity::{Identity, Revision};
+    use crate::message_router::Router;
+    use std::net::{TcpListener, TcpStream};
+    use std::thread;
+
+    let mut router = Router::default();
+    let identity = Identity::new(1, 2, 3, Revision::new(4, 1), 5, String::from("Hello"));
+    router.register(Identity::CLASS, 1, Box::new(identity));
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = thread::spawn(move || serve(listener.accept().unwrap().0, router));
+
+    let mut client = ExplicitClient::new(TcpStream::connect(addr).unwrap());
+    assert_eq!(Ok(()), client.register());
+    assert_eq!(1, client.session());
+
+    assert_eq!(
+        Ok(BytesMut::from(&b"\x01\0"[..])),
+        client.get_attribute_single(Identity::CLASS, 1, 1)
+    );
+    assert_eq!(
+        Ok(BytesMut::from(&b"\x05Hello"[..])),
+        client.get_attribute_single(Identity::CLASS, 1, 7)
+    );
+    assert_eq!(3, client.context); // each request has a new context
+    assert_eq!(
+        Err(ATTRIBUTE_NOT_SUPPORTED),
+        client.get_attribute_single(Identity::CLASS, 1, 99)
+    );
+    assert_eq!(
+        Err(PATH_DESTINATION_UNKNOWN),
+        client.get_attribute_single(0x64, 1, 1)
+    );
+
+    assert_eq!(Ok(()), client.unregister());
+    assert_eq!(0, client.session());
+    server.join().unwrap();
+}
+
+#[test]
+fn context_mismatch() {
+    use std::io::Cursor;
+
+    struct Echo(Cursor<Vec<u8>>, Vec<u8>);
+    impl Read for Echo {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.0.read(buf)
+        }
+    }
+    impl Write for Echo {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.1.write(buf)
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    // a reply with sender context 2 while 1 is expected
+    let reply = b"\x65\0\x04\0\x01\0\0\0\0\0\0\0\x02\0\0\0\0\0\0\0\0\0\0\0\x01\0\0\0";
+    let mut client = ExplicitClient::new(Echo(Cursor::new(reply.to_vec()), Vec::new()));
+    assert_eq!(Err(MESSAGE_FORMAT_ERROR), client.register());
+    assert_eq!(0, client.session());
+    assert_eq!(&b"\x65\0\x04\0"[..], &client.stream.1[..4]);
+
+    // connection closed
+    assert_eq!(Err(NOT_ENOUGH_DATA), client.register());
+}
+
+#[test]
+fn auto_traits() {
+    use crate::eip::check_auto_traits;
+
+    check_auto_traits::<ExplicitClient<std::net::TcpStream>>();
+}
@@ -0,0 +1,104 @@
+use crate::eip::{EipResult, Serializing};
+use crate::error_code::{NOT_ENOUGH_DATA, REPLY_DATA_TOO_LARGE};
+use bytes::{Buf, BufMut, BytesMut};
+use core::mem::size_of;
+
+/// This is the body of the connected address item, used by connected messaging
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConnectedAddress {
+    pub connection_id: u32, // Identification of the connection
+}
+
+impl ConnectedAddress {
+    /// Create an instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `connection_id` - The identification of the connection
+    ///
+    /// # Returns
+    ///
+    /// * The created instance
+    ///
+    pub fn new(connection_id: u32) -> Self {
+        ConnectedAddress { connection_id }
+    }
+}
+
+impl Serializing for ConnectedAddress {
+    /// Deserialize all fields
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to read from
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if there is not enough data.
+    ///
+    fn deserialize(&mut self, buf: &mut dyn Buf) -> EipResult {
+        if buf.remaining() < self.serial_size() {
+            return Err(NOT_ENOUGH_DATA);
+        }
+        self.connection_id = buf.get_u32_le();
+        Ok(())
+    }
+
+    /// Serialize all fields
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to write to
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if there is not enough room.
+    ///
+    fn serialize(&self, buf: &mut BytesMut) -> EipResult {
+        if buf.remaining_mut() < self.serial_size() {
+            return Err(REPLY_DATA_TOO_LARGE);
+        }
+        buf.put_u32_le(self.connection_id);
+        Ok(())
+    }
+
+    /// Get the serialized size in Bytes.
+    ///
+    /// # Returns
+    ///
+    /// * The number of bytes when serialized
+    ///
+    fn serial_size(&self) -> usize {
+        size_of::<u32>() // connection_id
+    }
+}
+
+#[test]
+fn deserialize() {
+    let mut ca = ConnectedAddress::default();
+    let mut buf = &b"\x78\x56\x34\x12"[..];
+    assert_eq!(Ok(()), ca.deserialize(&mut buf));
+    assert_eq!(0, buf.len());
+    assert_eq!(0x12345678, ca.connection_id);
+
+    buf = &b"\x78\x56\x34"[..];
+    assert_eq!(Err(NOT_ENOUGH_DATA), ca.deserialize(&mut buf));
+}
+
+#[test]
+fn serialize() {
+    let ca = ConnectedAddress::new(0x12345678);
+    let mut buf = BytesMut::with_capacity(100);
+    assert_eq!(Ok(()), ca.serialize(&mut buf));
+    assert_eq!(4, buf.len());
+    assert_eq!(&b"\x78\x56\x34\x12"[..], buf);
+}
+
+#[test]
+fn auto_traits() {
+    use crate::eip::check_auto_traits;
+
+    check_auto_traits::<ConnectedAddress>();
+}
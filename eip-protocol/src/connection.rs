@@ -0,0 +1,240 @@
+use crate::attr::{AccessCode, Byte, Duint, Uint, Usint};
+use crate::eip::{EipResult, Serializing};
+use crate::error_code::ATTRIBUTE_NOT_SUPPORTED;
+use crate::message_router::Object;
+use bytes::{Buf, BytesMut};
+
+/// The Connection object exposes the state of one established connection,
+/// the instances are created and deleted by the Connection Manager.
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Attr(u16);
+
+impl From<u16> for Attr {
+    fn from(val: u16) -> Self {
+        Self(val)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Connection {
+    pub state: Usint,                         // State of the connection
+    pub instance_type: Usint,                 // Explicit messaging, I/O or bridged
+    pub transport_class_trigger: Byte,        // Direction, production trigger and transport class
+    pub produced_connection_size: Uint,       // Maximum number of bytes transmitted
+    pub consumed_connection_size: Uint,       // Maximum number of bytes received
+    pub expected_packet_rate: Uint,           // The requested packet interval in ms
+    pub produced_connection_id: Duint,        // Connection identifier of the produced data
+    pub consumed_connection_id: Duint,        // Connection identifier of the consumed data
+    pub watchdog_timeout_action: Usint,       // Handling of an inactivity timeout
+    pub connection_timeout_multiplier: Usint, // Inactivity timeout, 4 times up to 512 times
+}
+
+impl Connection {
+    pub const CLASS: u32 = 0x05;
+
+    pub const STATE: Attr = Attr(1);
+    pub const INSTANCE_TYPE: Attr = Attr(2);
+    pub const TRANSPORT_CLASS_TRIGGER: Attr = Attr(3);
+    pub const PRODUCED_CONNECTION_SIZE: Attr = Attr(7);
+    pub const CONSUMED_CONNECTION_SIZE: Attr = Attr(8);
+    pub const EXPECTED_PACKET_RATE: Attr = Attr(9);
+    pub const PRODUCED_CONNECTION_ID: Attr = Attr(10);
+    pub const CONSUMED_CONNECTION_ID: Attr = Attr(11);
+    pub const WATCHDOG_TIMEOUT_ACTION: Attr = Attr(12);
+    pub const CONNECTION_TIMEOUT_MULTIPLIER: Attr = Attr(18);
+
+    // Values of the state attribute
+    pub const STATE_NONEXISTENT: u8 = 0;
+    pub const STATE_CONFIGURING: u8 = 1;
+    pub const STATE_WAITING_FOR_CONNECTION_ID: u8 = 2;
+    pub const STATE_ESTABLISHED: u8 = 3;
+    pub const STATE_TIMED_OUT: u8 = 4;
+    pub const STATE_DEFERRED_DELETE: u8 = 5;
+    pub const STATE_CLOSING: u8 = 6;
+
+    // Values of the instance type attribute
+    pub const INSTANCE_TYPE_EXPLICIT: u8 = 0;
+    pub const INSTANCE_TYPE_IO: u8 = 1;
+    pub const INSTANCE_TYPE_CIP_BRIDGED: u8 = 2;
+
+    /// Create an established connection.
+    /// The attributes are get only, except the expected packet rate and the watchdog action.
+    ///
+    /// # Arguments
+    ///
+    /// * `instance_type` - Explicit messaging, I/O or bridged
+    /// * `transport_class_trigger` - The transport class and trigger of the Forward_Open
+    /// * `produced_connection_id` - The connection identifier of the produced data
+    /// * `consumed_connection_id` - The connection identifier of the consumed data
+    /// * `expected_packet_rate` - The requested packet interval in ms
+    ///
+    /// # Returns
+    ///
+    /// * The created instance
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytes::BytesMut;
+    /// use eip_protocol::connection::Connection;
+    ///
+    /// let c = Connection::new(Connection::INSTANCE_TYPE_IO, 0x01, 0x1234, 0x5678, 10);
+    /// let mut buf = BytesMut::with_capacity(10);
+    ///
+    /// assert_eq!(
+    ///    Ok(()),
+    ///    c.serialize_attribute_single(&mut buf, Connection::STATE)
+    /// );
+    /// assert_eq!(&b"\x03"[..], buf);
+    /// ```
+    pub fn new(
+        instance_type: u8,
+        transport_class_trigger: u8,
+        produced_connection_id: u32,
+        consumed_connection_id: u32,
+        expected_packet_rate: u16,
+    ) -> Self {
+        let gettable = AccessCode::get_only();
+        let settable = AccessCode::get_set();
+        Connection {
+            state: Usint::new(Self::STATE_ESTABLISHED, gettable.clone()),
+            instance_type: Usint::new(instance_type, gettable.clone()),
+            transport_class_trigger: Byte::new(transport_class_trigger, gettable.clone()),
+            produced_connection_size: Uint::new(0, gettable.clone()),
+            consumed_connection_size: Uint::new(0, gettable.clone()),
+            expected_packet_rate: Uint::new(expected_packet_rate, settable.clone()),
+            produced_connection_id: Duint::new(produced_connection_id, gettable.clone()),
+            consumed_connection_id: Duint::new(consumed_connection_id, gettable.clone()),
+            watchdog_timeout_action: Usint::new(0, settable),
+            connection_timeout_multiplier: Usint::new(0, gettable),
+        }
+    }
+
+    /// Serialize one specific attribute
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to write to
+    /// * `attr` - The attribute identifier number
+    ///
+    /// # Errors
+    ///
+    /// If the attribute is non existent or is not getable, an error variant will be returned.
+    ///
+    pub fn serialize_attribute_single(&self, buf: &mut BytesMut, attr: Attr) -> EipResult {
+        match attr {
+            Self::STATE => self.state.serialize(buf)?,
+            Self::INSTANCE_TYPE => self.instance_type.serialize(buf)?,
+            Self::TRANSPORT_CLASS_TRIGGER => self.transport_class_trigger.serialize(buf)?,
+            Self::PRODUCED_CONNECTION_SIZE => self.produced_connection_size.serialize(buf)?,
+            Self::CONSUMED_CONNECTION_SIZE => self.consumed_connection_size.serialize(buf)?,
+            Self::EXPECTED_PACKET_RATE => self.expected_packet_rate.serialize(buf)?,
+            Self::PRODUCED_CONNECTION_ID => self.produced_connection_id.serialize(buf)?,
+            Self::CONSUMED_CONNECTION_ID => self.consumed_connection_id.serialize(buf)?,
+            Self::WATCHDOG_TIMEOUT_ACTION => self.watchdog_timeout_action.serialize(buf)?,
+            Self::CONNECTION_TIMEOUT_MULTIPLIER => {
+                self.connection_timeout_multiplier.serialize(buf)?
+            }
+            _ => return Err(ATTRIBUTE_NOT_SUPPORTED),
+        };
+        Ok(())
+    }
+
+    /// Deserialize one specific attribute
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to read from
+    /// * `attr` - The attribute identifier number
+    ///
+    /// # Errors
+    ///
+    /// If the attribute is non existent or is not set-able, an error variant will be returned.
+    ///
+    pub fn deserialize_attribute_single(&mut self, buf: &mut dyn Buf, attr: Attr) -> EipResult {
+        match attr {
+            Self::STATE => self.state.deserialize(buf)?,
+            Self::INSTANCE_TYPE => self.instance_type.deserialize(buf)?,
+            Self::TRANSPORT_CLASS_TRIGGER => self.transport_class_trigger.deserialize(buf)?,
+            Self::PRODUCED_CONNECTION_SIZE => self.produced_connection_size.deserialize(buf)?,
+            Self::CONSUMED_CONNECTION_SIZE => self.consumed_connection_size.deserialize(buf)?,
+            Self::EXPECTED_PACKET_RATE => self.expected_packet_rate.deserialize(buf)?,
+            Self::PRODUCED_CONNECTION_ID => self.produced_connection_id.deserialize(buf)?,
+            Self::CONSUMED_CONNECTION_ID => self.consumed_connection_id.deserialize(buf)?,
+            Self::WATCHDOG_TIMEOUT_ACTION => self.watchdog_timeout_action.deserialize(buf)?,
+            Self::CONNECTION_TIMEOUT_MULTIPLIER => {
+                self.connection_timeout_multiplier.deserialize(buf)?
+            }
+            _ => return Err(ATTRIBUTE_NOT_SUPPORTED),
+        };
+        Ok(())
+    }
+}
+
+impl Object for Connection {
+    fn get_attribute(&self, buf: &mut BytesMut, attr: u16) -> EipResult {
+        self.serialize_attribute_single(buf, attr.into())
+    }
+
+    fn set_attribute(&mut self, buf: &mut dyn Buf, attr: u16) -> EipResult {
+        self.deserialize_attribute_single(buf, attr.into())
+    }
+}
+
+#[test]
+fn serialize_attribute_single() {
+    let mut c = Connection::new(Connection::INSTANCE_TYPE_IO, 0x81, 0x1234, 0x5678, 10);
+    let mut buf = BytesMut::with_capacity(100);
+    for attr in [
+        Connection::STATE,
+        Connection::INSTANCE_TYPE,
+        Connection::TRANSPORT_CLASS_TRIGGER,
+        Connection::EXPECTED_PACKET_RATE,
+        Connection::PRODUCED_CONNECTION_ID,
+        Connection::CONSUMED_CONNECTION_ID,
+    ] {
+        assert_eq!(Ok(()), c.serialize_attribute_single(&mut buf, attr));
+    }
+    assert_eq!(&b"\x03\x01\x81\x0a\0\x34\x12\0\0\x78\x56\0\0"[..], buf);
+
+    c.state.set(Connection::STATE_TIMED_OUT);
+    buf.clear();
+    assert_eq!(
+        Ok(()),
+        c.serialize_attribute_single(&mut buf, Connection::STATE)
+    );
+    assert_eq!(&b"\x04"[..], buf);
+
+    assert_eq!(
+        Err(ATTRIBUTE_NOT_SUPPORTED),
+        c.serialize_attribute_single(&mut buf, Attr(4)) // DeviceNet only
+    );
+}
+
+#[test]
+fn deserialize_attribute_single() {
+    use crate::error_code::ATTRIBUTE_NOT_SETTABLE;
+
+    let mut c = Connection::new(Connection::INSTANCE_TYPE_EXPLICIT, 0x83, 1, 2, 2500);
+    assert_eq!(
+        Ok(()),
+        c.deserialize_attribute_single(&mut &b"\x64\0"[..], Connection::EXPECTED_PACKET_RATE)
+    );
+    assert_eq!(100, c.expected_packet_rate.get());
+    assert_eq!(
+        Err(ATTRIBUTE_NOT_SETTABLE),
+        c.deserialize_attribute_single(&mut &b"\x01"[..], Connection::STATE)
+    );
+    assert_eq!(Connection::STATE_ESTABLISHED, c.state.get());
+}
+
+#[test]
+fn auto_traits() {
+    use crate::eip::check_auto_traits;
+
+    check_auto_traits::<Attr>();
+    check_auto_traits::<Connection>();
+}
@@ -0,0 +1,922 @@
+use crate::eip::{self, EipResult, Serializing};
+use crate::error_code::{
+    ErrorCode, INVALID_PARAMETER, NOT_ENOUGH_DATA, OBJECT_DOES_NOT_EXIST, PATH_SEGMENT_ERROR,
+    REPLY_DATA_TOO_LARGE,
+};
+use crate::message_router::Request;
+use alloc::vec::Vec;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use core::mem::size_of;
+
+// The Connection Manager object opens and closes connections
+
+pub const CLASS: u32 = 0x06;
+
+/// Read the fixed part of a message that ends in a path or data of 16 bit words.
+/// The total size is checked first, on an error no input is consumed.
+/// Note: without std a fixed part split over several chunks is read directly, it is consumed
+/// on error then.
+///
+/// # Arguments
+///
+/// * `buf` - The message buffer to read from
+/// * `size_at` - The offset of the word count in the fixed part
+///
+/// # Returns
+///
+/// * The fixed part and the number of bytes that follow it
+///
+/// # Errors
+///
+/// An error variant will be returned if there is not enough data.
+///
+fn read_fixed<const N: usize>(
+    buf: &mut dyn Buf,
+    size_at: usize,
+) -> Result<([u8; N], usize), ErrorCode> {
+    if buf.remaining() < N {
+        return Err(NOT_ENOUGH_DATA);
+    }
+    let mut fixed = [0; N];
+    let start = if eip::peek(buf, &mut fixed) {
+        N
+    } else {
+        buf.copy_to_slice(&mut fixed);
+        0
+    };
+    let len = fixed[size_at] as usize * 2; // 16 bit words
+    if buf.remaining() < start + len {
+        return Err(NOT_ENOUGH_DATA);
+    }
+    buf.advance(start);
+    Ok((fixed, len))
+}
+
+/// The request data of the Forward_Open service
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ForwardOpenRequest {
+    pub priority_time_tick: u8,        // Priority bit and the time per tick
+    pub time_out_ticks: u8,            // Number of ticks to time out the unconnected request
+    pub o_t_connection_id: u32,        // Originator to target network connection identifier
+    pub t_o_connection_id: u32,        // Target to originator network connection identifier
+    pub connection_serial_number: u16, // Identification of the connection by the originator
+    pub originator_vendor_id: u16,     // Vendor identification of the originator
+    pub originator_serial_number: u32, // Serial number of the originator
+    pub connection_timeout_multiplier: u8, // Multiplier of the RPI for the inactivity timeout
+    pub o_t_rpi: u32,                  // Originator to target requested packet interval in µs
+    pub o_t_connection_parameters: u16, // Originator to target network connection parameters
+    pub t_o_rpi: u32,                  // Target to originator requested packet interval in µs
+    pub t_o_connection_parameters: u16, // Target to originator network connection parameters
+    pub transport_type_trigger: u8,    // Transport class and trigger
+    pub connection_path: Bytes,        // Padded EPATH of the target application object
+}
+
+impl ForwardOpenRequest {
+    /// Get the serialized size without the connection path in Bytes.
+    ///
+    /// # Returns
+    ///
+    /// * The number of bytes when serialized
+    ///
+    const fn fixed_size() -> usize {
+        size_of::<u8>() // priority_time_tick
+        + size_of::<u8>() // time_out_ticks
+        + size_of::<u32>() // o_t_connection_id
+        + size_of::<u32>() // t_o_connection_id
+        + size_of::<u16>() // connection_serial_number
+        + size_of::<u16>() // originator_vendor_id
+        + size_of::<u32>() // originator_serial_number
+        + size_of::<u8>() // connection_timeout_multiplier
+        + 3 // reserved
+        + size_of::<u32>() // o_t_rpi
+        + size_of::<u16>() // o_t_connection_parameters
+        + size_of::<u32>() // t_o_rpi
+        + size_of::<u16>() // t_o_connection_parameters
+        + size_of::<u8>() // transport_type_trigger
+        + size_of::<u8>() // connection path size
+    }
+}
+
+impl Serializing for ForwardOpenRequest {
+    /// Deserialize all fields, the size is checked first so on an error nothing changes.
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to read from
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if there is not enough data.
+    ///
+    fn deserialize(&mut self, buf: &mut dyn Buf) -> EipResult {
+        const SIZE: usize = ForwardOpenRequest::fixed_size();
+        let (fixed, len) = read_fixed::<SIZE>(buf, SIZE - 1)?;
+        let mut fixed = &fixed[..];
+        self.priority_time_tick = fixed.get_u8();
+        self.time_out_ticks = fixed.get_u8();
+        self.o_t_connection_id = fixed.get_u32_le();
+        self.t_o_connection_id = fixed.get_u32_le();
+        self.connection_serial_number = fixed.get_u16_le();
+        self.originator_vendor_id = fixed.get_u16_le();
+        self.originator_serial_number = fixed.get_u32_le();
+        self.connection_timeout_multiplier = fixed.get_u8();
+        fixed.advance(3); // reserved
+        self.o_t_rpi = fixed.get_u32_le();
+        self.o_t_connection_parameters = fixed.get_u16_le();
+        self.t_o_rpi = fixed.get_u32_le();
+        self.t_o_connection_parameters = fixed.get_u16_le();
+        self.transport_type_trigger = fixed.get_u8();
+        self.connection_path = buf.copy_to_bytes(len);
+        Ok(())
+    }
+
+    /// Serialize all fields
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to write to
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if there is not enough room or the path is invalid.
+    ///
+    fn serialize(&self, buf: &mut BytesMut) -> EipResult {
+        let words = self.connection_path.len() / 2;
+        if !self.connection_path.len().is_multiple_of(2) || words > u8::MAX as usize {
+            return Err(PATH_SEGMENT_ERROR);
+        }
+        if buf.remaining_mut() < self.serial_size() {
+            return Err(REPLY_DATA_TOO_LARGE);
+        }
+        buf.put_u8(self.priority_time_tick);
+        buf.put_u8(self.time_out_ticks);
+        buf.put_u32_le(self.o_t_connection_id);
+        buf.put_u32_le(self.t_o_connection_id);
+        buf.put_u16_le(self.connection_serial_number);
+        buf.put_u16_le(self.originator_vendor_id);
+        buf.put_u32_le(self.originator_serial_number);
+        buf.put_u8(self.connection_timeout_multiplier);
+        buf.put_bytes(0, 3); // reserved
+        buf.put_u32_le(self.o_t_rpi);
+        buf.put_u16_le(self.o_t_connection_parameters);
+        buf.put_u32_le(self.t_o_rpi);
+        buf.put_u16_le(self.t_o_connection_parameters);
+        buf.put_u8(self.transport_type_trigger);
+        buf.put_u8(words as u8);
+        buf.put_slice(&self.connection_path);
+        Ok(())
+    }
+
+    /// Get the serialized size in Bytes.
+    ///
+    /// # Returns
+    ///
+    /// * The number of bytes when serialized
+    ///
+    fn serial_size(&self) -> usize {
+        Self::fixed_size() + self.connection_path.len()
+    }
+}
+
+/// The reply data of a successful Forward_Open service
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ForwardOpenReply {
+    pub o_t_connection_id: u32, // Originator to target network connection identifier
+    pub t_o_connection_id: u32, // Target to originator network connection identifier
+    pub connection_serial_number: u16, // Identification of the connection by the originator
+    pub originator_vendor_id: u16, // Vendor identification of the originator
+    pub originator_serial_number: u32, // Serial number of the originator
+    pub o_t_api: u32,           // Originator to target actual packet interval in µs
+    pub t_o_api: u32,           // Target to originator actual packet interval in µs
+    pub application_reply: Bytes, // Application specific data, multiple of 16 bit words
+}
+
+impl ForwardOpenReply {
+    /// Create the reply accepting a request with the requested packet intervals.
+    ///
+    /// # Arguments
+    ///
+    /// * `req` - The Forward_Open request
+    /// * `o_t_connection_id` - The originator to target connection identifier
+    /// * `t_o_connection_id` - The target to originator connection identifier
+    ///
+    /// # Returns
+    ///
+    /// * The created instance
+    ///
+    pub fn accept(
+        req: &ForwardOpenRequest,
+        o_t_connection_id: u32,
+        t_o_connection_id: u32,
+    ) -> Self {
+        ForwardOpenReply {
+            o_t_connection_id,
+            t_o_connection_id,
+            connection_serial_number: req.connection_serial_number,
+            originator_vendor_id: req.originator_vendor_id,
+            originator_serial_number: req.originator_serial_number,
+            o_t_api: req.o_t_rpi,
+            t_o_api: req.t_o_rpi,
+            application_reply: Bytes::new(),
+        }
+    }
+
+    /// Get the serialized size without the application reply in Bytes.
+    ///
+    /// # Returns
+    ///
+    /// * The number of bytes when serialized
+    ///
+    const fn fixed_size() -> usize {
+        size_of::<u32>() // o_t_connection_id
+        + size_of::<u32>() // t_o_connection_id
+        + size_of::<u16>() // connection_serial_number
+        + size_of::<u16>() // originator_vendor_id
+        + size_of::<u32>() // originator_serial_number
+        + size_of::<u32>() // o_t_api
+        + size_of::<u32>() // t_o_api
+        + size_of::<u8>() // application reply size
+        + size_of::<u8>() // reserved
+    }
+}
+
+impl Serializing for ForwardOpenReply {
+    /// Deserialize all fields, the size is checked first so on an error nothing changes.
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to read from
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if there is not enough data.
+    ///
+    fn deserialize(&mut self, buf: &mut dyn Buf) -> EipResult {
+        const SIZE: usize = ForwardOpenReply::fixed_size();
+        let (fixed, len) = read_fixed::<SIZE>(buf, SIZE - 2)?;
+        let mut fixed = &fixed[..];
+        self.o_t_connection_id = fixed.get_u32_le();
+        self.t_o_connection_id = fixed.get_u32_le();
+        self.connection_serial_number = fixed.get_u16_le();
+        self.originator_vendor_id = fixed.get_u16_le();
+        self.originator_serial_number = fixed.get_u32_le();
+        self.o_t_api = fixed.get_u32_le();
+        self.t_o_api = fixed.get_u32_le();
+        self.application_reply = buf.copy_to_bytes(len);
+        Ok(())
+    }
+
+    /// Serialize all fields
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to write to
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if there is not enough room or invalid length.
+    ///
+    fn serialize(&self, buf: &mut BytesMut) -> EipResult {
+        let words = self.application_reply.len() / 2;
+        if buf.remaining_mut() < self.serial_size()
+            || !self.application_reply.len().is_multiple_of(2)
+            || words > u8::MAX as usize
+        {
+            return Err(REPLY_DATA_TOO_LARGE);
+        }
+        buf.put_u32_le(self.o_t_connection_id);
+        buf.put_u32_le(self.t_o_connection_id);
+        buf.put_u16_le(self.connection_serial_number);
+        buf.put_u16_le(self.originator_vendor_id);
+        buf.put_u32_le(self.originator_serial_number);
+        buf.put_u32_le(self.o_t_api);
+        buf.put_u32_le(self.t_o_api);
+        buf.put_u8(words as u8);
+        buf.put_u8(0); // reserved
+        buf.put_slice(&self.application_reply);
+        Ok(())
+    }
+
+    /// Get the serialized size in Bytes.
+    ///
+    /// # Returns
+    ///
+    /// * The number of bytes when serialized
+    ///
+    fn serial_size(&self) -> usize {
+        Self::fixed_size() + self.application_reply.len()
+    }
+}
+
+/// The request data of the Forward_Close service
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ForwardCloseRequest {
+    pub priority_time_tick: u8,        // Priority bit and the time per tick
+    pub time_out_ticks: u8,            // Number of ticks to time out the unconnected request
+    pub connection_serial_number: u16, // Identification of the connection by the originator
+    pub originator_vendor_id: u16,     // Vendor identification of the originator
+    pub originator_serial_number: u32, // Serial number of the originator
+    pub connection_path: Bytes,        // Padded EPATH of the target application object
+}
+
+impl ForwardCloseRequest {
+    /// Get the serialized size without the connection path in Bytes.
+    ///
+    /// # Returns
+    ///
+    /// * The number of bytes when serialized
+    ///
+    const fn fixed_size() -> usize {
+        size_of::<u8>() // priority_time_tick
+        + size_of::<u8>() // time_out_ticks
+        + size_of::<u16>() // connection_serial_number
+        + size_of::<u16>() // originator_vendor_id
+        + size_of::<u32>() // originator_serial_number
+        + size_of::<u8>() // connection path size
+        + size_of::<u8>() // reserved
+    }
+}
+
+impl Serializing for ForwardCloseRequest {
+    /// Deserialize all fields, the size is checked first so on an error nothing changes.
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to read from
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if there is not enough data.
+    ///
+    fn deserialize(&mut self, buf: &mut dyn Buf) -> EipResult {
+        const SIZE: usize = ForwardCloseRequest::fixed_size();
+        let (fixed, len) = read_fixed::<SIZE>(buf, SIZE - 2)?;
+        let mut fixed = &fixed[..];
+        self.priority_time_tick = fixed.get_u8();
+        self.time_out_ticks = fixed.get_u8();
+        self.connection_serial_number = fixed.get_u16_le();
+        self.originator_vendor_id = fixed.get_u16_le();
+        self.originator_serial_number = fixed.get_u32_le();
+        self.connection_path = buf.copy_to_bytes(len);
+        Ok(())
+    }
+
+    /// Serialize all fields
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to write to
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if there is not enough room or the path is invalid.
+    ///
+    fn serialize(&self, buf: &mut BytesMut) -> EipResult {
+        let words = self.connection_path.len() / 2;
+        if !self.connection_path.len().is_multiple_of(2) || words > u8::MAX as usize {
+            return Err(PATH_SEGMENT_ERROR);
+        }
+        if buf.remaining_mut() < self.serial_size() {
+            return Err(REPLY_DATA_TOO_LARGE);
+        }
+        buf.put_u8(self.priority_time_tick);
+        buf.put_u8(self.time_out_ticks);
+        buf.put_u16_le(self.connection_serial_number);
+        buf.put_u16_le(self.originator_vendor_id);
+        buf.put_u32_le(self.originator_serial_number);
+        buf.put_u8(words as u8);
+        buf.put_u8(0); // reserved
+        buf.put_slice(&self.connection_path);
+        Ok(())
+    }
+
+    /// Get the serialized size in Bytes.
+    ///
+    /// # Returns
+    ///
+    /// * The number of bytes when serialized
+    ///
+    fn serial_size(&self) -> usize {
+        Self::fixed_size() + self.connection_path.len()
+    }
+}
+
+/// The reply data of a successful Forward_Close service
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ForwardCloseReply {
+    pub connection_serial_number: u16, // Identification of the connection by the originator
+    pub originator_vendor_id: u16,     // Vendor identification of the originator
+    pub originator_serial_number: u32, // Serial number of the originator
+    pub application_reply: Bytes,      // Application specific data, multiple of 16 bit words
+}
+
+impl ForwardCloseReply {
+    /// Get the serialized size without the application reply in Bytes.
+    ///
+    /// # Returns
+    ///
+    /// * The number of bytes when serialized
+    ///
+    const fn fixed_size() -> usize {
+        size_of::<u16>() // connection_serial_number
+        + size_of::<u16>() // originator_vendor_id
+        + size_of::<u32>() // originator_serial_number
+        + size_of::<u8>() // application reply size
+        + size_of::<u8>() // reserved
+    }
+}
+
+impl Serializing for ForwardCloseReply {
+    /// Deserialize all fields, the size is checked first so on an error nothing changes.
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to read from
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if there is not enough data.
+    ///
+    fn deserialize(&mut self, buf: &mut dyn Buf) -> EipResult {
+        const SIZE: usize = ForwardCloseReply::fixed_size();
+        let (fixed, len) = read_fixed::<SIZE>(buf, SIZE - 2)?;
+        let mut fixed = &fixed[..];
+        self.connection_serial_number = fixed.get_u16_le();
+        self.originator_vendor_id = fixed.get_u16_le();
+        self.originator_serial_number = fixed.get_u32_le();
+        self.application_reply = buf.copy_to_bytes(len);
+        Ok(())
+    }
+
+    /// Serialize all fields
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to write to
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if there is not enough room or invalid length.
+    ///
+    fn serialize(&self, buf: &mut BytesMut) -> EipResult {
+        let words = self.application_reply.len() / 2;
+        if buf.remaining_mut() < self.serial_size()
+            || !self.application_reply.len().is_multiple_of(2)
+            || words > u8::MAX as usize
+        {
+            return Err(REPLY_DATA_TOO_LARGE);
+        }
+        buf.put_u16_le(self.connection_serial_number);
+        buf.put_u16_le(self.originator_vendor_id);
+        buf.put_u32_le(self.originator_serial_number);
+        buf.put_u8(words as u8);
+        buf.put_u8(0); // reserved
+        buf.put_slice(&self.application_reply);
+        Ok(())
+    }
+
+    /// Get the serialized size in Bytes.
+    ///
+    /// # Returns
+    ///
+    /// * The number of bytes when serialized
+    ///
+    fn serial_size(&self) -> usize {
+        Self::fixed_size() + self.application_reply.len()
+    }
+}
+
+/// The request data of the Unconnected_Send service, routes an embedded request to a target
+/// behind one or more links
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnconnectedSend {
+    pub priority_time_tick: u8, // Priority bit and the time per tick
+    pub time_out_ticks: u8,     // Number of ticks to time out the unconnected request
+    pub request: Request,       // The embedded request for the target
+    pub data: Bytes,            // The request data following the path of the embedded request
+    pub route_path: Bytes,      // Padded EPATH of the route, port and link address segments
+}
+
+impl UnconnectedSend {
+    /// Get the serialized size without the embedded request and the route path in Bytes.
+    ///
+    /// # Returns
+    ///
+    /// * The number of bytes when serialized
+    ///
+    const fn fixed_size() -> usize {
+        size_of::<u8>() // priority_time_tick
+        + size_of::<u8>() // time_out_ticks
+        + size_of::<u16>() // message request size
+        + size_of::<u8>() // route path size
+        + size_of::<u8>() // reserved
+    }
+
+    /// Get the size of the embedded request in Bytes.
+    ///
+    /// # Returns
+    ///
+    /// * The number of bytes of the embedded request, without the pad byte
+    ///
+    fn message_size(&self) -> usize {
+        self.request.serial_size() + self.data.len()
+    }
+}
+
+impl Serializing for UnconnectedSend {
+    /// Deserialize all fields
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to read from
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if there is not enough data or the embedded request
+    /// is invalid.
+    ///
+    fn deserialize(&mut self, buf: &mut dyn Buf) -> EipResult {
+        if buf.remaining() < Self::fixed_size() {
+            return Err(NOT_ENOUGH_DATA);
+        }
+        self.priority_time_tick = buf.get_u8();
+        self.time_out_ticks = buf.get_u8();
+        let len = buf.get_u16_le() as usize;
+        if buf.remaining() < len + len % 2 + size_of::<u16>() {
+            return Err(NOT_ENOUGH_DATA);
+        }
+        let mut message = buf.copy_to_bytes(len);
+        buf.advance(len % 2); // pad byte
+        self.request = Request::default();
+        self.request.deserialize(&mut message)?;
+        self.data = message;
+
+        let route = buf.get_u8() as usize * 2; // 16 bit words
+        buf.get_u8(); // reserved
+        if buf.remaining() < route {
+            return Err(NOT_ENOUGH_DATA);
+        }
+        self.route_path = buf.copy_to_bytes(route);
+        Ok(())
+    }
+
+    /// Serialize all fields
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to write to
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if there is not enough room or a path is invalid.
+    ///
+    fn serialize(&self, buf: &mut BytesMut) -> EipResult {
+        let words = self.route_path.len() / 2;
+        if !self.route_path.len().is_multiple_of(2) || words > u8::MAX as usize {
+            return Err(PATH_SEGMENT_ERROR);
+        }
+        if self.message_size() > u16::MAX as usize || buf.remaining_mut() < self.serial_size() {
+            return Err(REPLY_DATA_TOO_LARGE);
+        }
+        buf.put_u8(self.priority_time_tick);
+        buf.put_u8(self.time_out_ticks);
+        buf.put_u16_le(self.message_size() as u16);
+        self.request.serialize(buf)?;
+        buf.put_slice(&self.data);
+        buf.put_bytes(0, self.message_size() % 2); // pad to an even number of bytes
+        buf.put_u8(words as u8);
+        buf.put_u8(0); // reserved
+        buf.put_slice(&self.route_path);
+        Ok(())
+    }
+
+    /// Get the serialized size in Bytes.
+    ///
+    /// # Returns
+    ///
+    /// * The number of bytes when serialized
+    ///
+    fn serial_size(&self) -> usize {
+        Self::fixed_size() + self.message_size() + self.message_size() % 2 + self.route_path.len()
+    }
+}
+
+/// Keeps track of the open connections
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConnectionManager {
+    connections: Vec<ForwardOpenReply>,
+}
+
+impl ConnectionManager {
+    /// Find an open connection by the connection serial number and the originator
+    ///
+    /// # Arguments
+    ///
+    /// * `serial_number` - The connection serial number
+    /// * `vendor_id` - The vendor identification of the originator
+    /// * `originator_serial` - The serial number of the originator
+    ///
+    /// # Returns
+    ///
+    /// * The index of the connection, None if it is not open
+    ///
+    fn position(
+        &self,
+        serial_number: u16,
+        vendor_id: u16,
+        originator_serial: u32,
+    ) -> Option<usize> {
+        self.connections.iter().position(|c| {
+            c.connection_serial_number == serial_number
+                && c.originator_vendor_id == vendor_id
+                && c.originator_serial_number == originator_serial
+        })
+    }
+
+    /// Open a connection
+    ///
+    /// # Arguments
+    ///
+    /// * `req` - The Forward_Open request
+    /// * `o_t_connection_id` - The originator to target connection identifier
+    /// * `t_o_connection_id` - The target to originator connection identifier
+    ///
+    /// # Returns
+    ///
+    /// * The reply to send to the originator
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if a connection with the same connection serial number
+    /// and originator is already open.
+    ///
+    pub fn forward_open(
+        &mut self,
+        req: &ForwardOpenRequest,
+        o_t_connection_id: u32,
+        t_o_connection_id: u32,
+    ) -> Result<ForwardOpenReply, ErrorCode> {
+        if self
+            .position(
+                req.connection_serial_number,
+                req.originator_vendor_id,
+                req.originator_serial_number,
+            )
+            .is_some()
+        {
+            return Err(INVALID_PARAMETER);
+        }
+        let reply = ForwardOpenReply::accept(req, o_t_connection_id, t_o_connection_id);
+        self.connections.push(reply.clone());
+        Ok(reply)
+    }
+
+    /// Close a connection, matched by the connection serial number and the originator
+    ///
+    /// # Arguments
+    ///
+    /// * `req` - The Forward_Close request
+    ///
+    /// # Returns
+    ///
+    /// * The reply to send to the originator
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if the connection does not exist.
+    ///
+    pub fn forward_close(
+        &mut self,
+        req: &ForwardCloseRequest,
+    ) -> Result<ForwardCloseReply, ErrorCode> {
+        let index = self
+            .position(
+                req.connection_serial_number,
+                req.originator_vendor_id,
+                req.originator_serial_number,
+            )
+            .ok_or(OBJECT_DOES_NOT_EXIST)?;
+        self.connections.remove(index);
+        Ok(ForwardCloseReply {
+            connection_serial_number: req.connection_serial_number,
+            originator_vendor_id: req.originator_vendor_id,
+            originator_serial_number: req.originator_serial_number,
+            application_reply: Bytes::new(),
+        })
+    }
+
+    /// Get the open connections
+    ///
+    /// # Returns
+    ///
+    /// * The replies of the accepted Forward_Open requests
+    ///
+    pub fn connections(&self) -> &[ForwardOpenReply] {
+        &self.connections
+    }
+}
+
+#[cfg(test)]
+const FORWARD_OPEN: &[u8] = b"\x0a\x0e\0\0\0\0\x01\0\x02\x80\x03\0\x4d\0\x78\x56\x34\x12\x01\0\0\0\x40\x4b\x4c\0\xf4\x43\x40\x4b\x4c\0\xf4\x43\xa3\x03\x01\0\x20\x02\x24\x01";
+
+#[test]
+fn forward_open_request() {
+    let mut req = ForwardOpenRequest::default();
+    let mut buf = FORWARD_OPEN;
+    assert_eq!(Ok(()), req.deserialize(&mut buf));
+    assert_eq!(0, buf.len());
+    assert_eq!(0x0a, req.priority_time_tick);
+    assert_eq!(0x0e, req.time_out_ticks);
+    assert_eq!(0, req.o_t_connection_id);
+    assert_eq!(0x80020001, req.t_o_connection_id);
+    assert_eq!(3, req.connection_serial_number);
+    assert_eq!(0x4d, req.originator_vendor_id);
+    assert_eq!(0x12345678, req.originator_serial_number);
+    assert_eq!(1, req.connection_timeout_multiplier);
+    assert_eq!(5000000, req.o_t_rpi);
+    assert_eq!(0x43f4, req.o_t_connection_parameters);
+    assert_eq!(5000000, req.t_o_rpi);
+    assert_eq!(0x43f4, req.t_o_connection_parameters);
+    assert_eq!(0xa3, req.transport_type_trigger);
+    assert_eq!(&b"\x01\0\x20\x02\x24\x01"[..], req.connection_path);
+
+    let mut out = BytesMut::with_capacity(100);
+    assert_eq!(Ok(()), req.serialize(&mut out));
+    assert_eq!(req.serial_size(), out.len());
+    assert_eq!(FORWARD_OPEN, out);
+
+    // connection path incomplete, nothing changed nor consumed
+    let prior = req.clone();
+    let mut other = FORWARD_OPEN[..FORWARD_OPEN.len() - 1].to_vec();
+    other[0] = 0x0b;
+    buf = &other[..];
+    assert_eq!(Err(NOT_ENOUGH_DATA), req.deserialize(&mut buf));
+    assert_eq!(FORWARD_OPEN.len() - 1, buf.len());
+    assert_eq!(prior, req);
+}
+
+#[test]
+fn forward_open_reply() {
+    let mut req = ForwardOpenRequest::default();
+    assert_eq!(Ok(()), req.deserialize(&mut &FORWARD_OPEN[..]));
+
+    let reply = ForwardOpenReply::accept(&req, 0x11223344, req.t_o_connection_id);
+    let mut buf = BytesMut::with_capacity(100);
+    assert_eq!(Ok(()), reply.serialize(&mut buf));
+    assert_eq!(26, reply.serial_size());
+    assert_eq!(
+        &b"\x44\x33\x22\x11\x01\0\x02\x80\x03\0\x4d\0\x78\x56\x34\x12\x40\x4b\x4c\0\x40\x4b\x4c\0\0\0"[..],
+        buf
+    );
+
+    let mut received = ForwardOpenReply::default();
+    assert_eq!(Ok(()), received.deserialize(&mut &buf[..]));
+    assert_eq!(reply, received);
+
+    // one word of application reply announced but missing, nothing changed nor consumed
+    let mut other = buf.to_vec();
+    other[0] = 0x55;
+    other[24] = 1;
+    let mut data = &other[..];
+    assert_eq!(Err(NOT_ENOUGH_DATA), received.deserialize(&mut data));
+    assert_eq!(26, data.len());
+    assert_eq!(reply, received);
+}
+
+#[cfg(test)]
+const FORWARD_CLOSE: &[u8] = b"\x0a\x0e\x03\0\x4d\0\x78\x56\x34\x12\x03\0\x01\0\x20\x02\x24\x01";
+
+#[test]
+fn forward_close_request() {
+    let mut req = ForwardCloseRequest::default();
+    let mut buf = FORWARD_CLOSE;
+    assert_eq!(Ok(()), req.deserialize(&mut buf));
+    assert_eq!(0, buf.len());
+    assert_eq!(3, req.connection_serial_number);
+    assert_eq!(0x4d, req.originator_vendor_id);
+    assert_eq!(0x12345678, req.originator_serial_number);
+    assert_eq!(&b"\x01\0\x20\x02\x24\x01"[..], req.connection_path);
+
+    let mut out = BytesMut::with_capacity(100);
+    assert_eq!(Ok(()), req.serialize(&mut out));
+    assert_eq!(req.serial_size(), out.len());
+    assert_eq!(FORWARD_CLOSE, out);
+
+    // connection path incomplete, nothing changed nor consumed
+    let prior = req.clone();
+    let mut other = FORWARD_CLOSE[..FORWARD_CLOSE.len() - 1].to_vec();
+    other[2] = 0x04;
+    buf = &other[..];
+    assert_eq!(Err(NOT_ENOUGH_DATA), req.deserialize(&mut buf));
+    assert_eq!(FORWARD_CLOSE.len() - 1, buf.len());
+    assert_eq!(prior, req);
+}
+
+#[test]
+fn forward_close() {
+    let mut open = ForwardOpenRequest::default();
+    assert_eq!(Ok(()), open.deserialize(&mut &FORWARD_OPEN[..]));
+    let mut close = ForwardCloseRequest::default();
+    assert_eq!(Ok(()), close.deserialize(&mut &FORWARD_CLOSE[..]));
+
+    let mut manager = ConnectionManager::default();
+    assert!(manager.forward_open(&open, 0x11223344, 0x80020001).is_ok());
+    assert_eq!(1, manager.connections().len());
+
+    // a duplicate of the open connection
+    assert_eq!(
+        Err(INVALID_PARAMETER),
+        manager.forward_open(&open, 0x55667788, 0x80020002)
+    );
+    assert_eq!(1, manager.connections().len());
+
+    let reply = manager.forward_close(&close).unwrap();
+    assert_eq!(0, manager.connections().len());
+
+    let mut buf = BytesMut::with_capacity(100);
+    assert_eq!(Ok(()), reply.serialize(&mut buf));
+    assert_eq!(&b"\x03\0\x4d\0\x78\x56\x34\x12\0\0"[..], buf);
+
+    let mut received = ForwardCloseReply::default();
+    assert_eq!(Ok(()), received.deserialize(&mut &buf[..]));
+    assert_eq!(reply, received);
+
+    // one word of application reply announced but missing, nothing changed nor consumed
+    let mut other = buf.to_vec();
+    other[0] = 0x04;
+    other[8] = 1;
+    let mut data = &other[..];
+    assert_eq!(Err(NOT_ENOUGH_DATA), received.deserialize(&mut data));
+    assert_eq!(10, data.len());
+    assert_eq!(reply, received);
+
+    // already closed
+    assert_eq!(Err(OBJECT_DOES_NOT_EXIST), manager.forward_close(&close));
+}
+
+#[test]
+fn unconnected_send() {
+    let send = UnconnectedSend {
+        priority_time_tick: 0x0a,
+        time_out_ticks: 0x0e,
+        request: Request::get_attribute_single(0x01, 1, 7),
+        route_path: Bytes::from_static(b"\x01\x00"), // backplane port, slot 0
+        ..Default::default()
+    };
+    let mut buf = BytesMut::with_capacity(100);
+    assert_eq!(Ok(()), send.serialize(&mut buf));
+    assert_eq!(send.serial_size(), buf.len());
+    assert_eq!(
+        &b"\x0a\x0e\x08\0\x0e\x03\x20\x01\x24\x01\x30\x07\x01\0\x01\x00"[..],
+        buf
+    );
+
+    let mut received = UnconnectedSend::default();
+    assert_eq!(Ok(()), received.deserialize(&mut &buf[..]));
+    assert_eq!(send, received);
+
+    // odd sized request with a pad byte
+    let send = UnconnectedSend {
+        request: Request::set_attribute_single(0x01, 1, 8),
+        data: Bytes::from_static(b"\x03"),
+        route_path: Bytes::from_static(b"\x01\x00"),
+        ..Default::default()
+    };
+    buf.clear();
+    assert_eq!(Ok(()), send.serialize(&mut buf));
+    assert_eq!(
+        &b"\x09\0\x0f\x03\x20\x01\x24\x01\x30\x08\x03\0\x01\0\x01\0"[..],
+        &buf[2..]
+    );
+    assert_eq!(Ok(()), received.deserialize(&mut &buf[..]));
+    assert_eq!(send, received);
+
+    assert_eq!(
+        Err(NOT_ENOUGH_DATA),
+        received.deserialize(&mut &buf[..buf.len() - 1])
+    );
+}
+
+#[test]
+fn auto_traits() {
+    use crate::eip::check_auto_traits;
+
+    check_auto_traits::<ForwardOpenRequest>();
+    check_auto_traits::<ForwardOpenReply>();
+    check_auto_traits::<ForwardCloseRequest>();
+    check_auto_traits::<ForwardCloseReply>();
+    check_auto_traits::<UnconnectedSend>();
+    check_auto_traits::<ConnectionManager>();
+}
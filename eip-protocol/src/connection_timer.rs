@@ -0,0 +1,201 @@
+use core::time::Duration;
+use std::collections::HashMap;
+use std::time::Instant;
+
+/// Network connection identifier, as assigned by Forward_Open
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ConnId(u32);
+
+impl From<u32> for ConnId {
+    fn from(val: u32) -> Self {
+        Self(val)
+    }
+}
+
+impl From<ConnId> for u32 {
+    fn from(val: ConnId) -> Self {
+        val.0
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Timer {
+    last_received: Instant,
+    window: Duration, // The inactivity timeout
+}
+
+/// Detects connections of which the peer stopped producing.
+/// A connection times out when nothing is received for the RPI times the timeout multiplier.
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ConnectionTimer {
+    timers: HashMap<ConnId, Timer>,
+}
+
+impl ConnectionTimer {
+    /// Get the inactivity timeout of a connection
+    ///
+    /// # Arguments
+    ///
+    /// * `rpi` - The requested packet interval in µs
+    /// * `multiplier` - The connection timeout multiplier, 0 is 4 times up to 7 is 512 times
+    ///
+    /// # Returns
+    ///
+    /// * The inactivity timeout
+    ///
+    pub fn inactivity_timeout(rpi: u32, multiplier: u8) -> Duration {
+        Duration::from_micros(rpi as u64 * (4 << multiplier.min(7)))
+    }
+
+    /// Start tracking a connection, replaces an existing connection with the same identifier
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The connection identifier of the consumed data
+    /// * `rpi` - The requested packet interval in µs
+    /// * `multiplier` - The connection timeout multiplier
+    /// * `now` - The moment the connection is opened
+    ///
+    pub fn open(&mut self, id: ConnId, rpi: u32, multiplier: u8, now: Instant) {
+        self.timers.insert(
+            id,
+            Timer {
+                last_received: now,
+                window: Self::inactivity_timeout(rpi, multiplier),
+            },
+        );
+    }
+
+    /// Stop tracking a connection
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The connection identifier
+    ///
+    /// # Returns
+    ///
+    /// True if the connection was tracked, false otherwise.
+    ///
+    pub fn close(&mut self, id: ConnId) -> bool {
+        self.timers.remove(&id).is_some()
+    }
+
+    /// Register a received packet, restarts the inactivity timeout
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The connection identifier
+    /// * `now` - The moment the packet is received
+    ///
+    /// # Returns
+    ///
+    /// True if the connection is tracked, false otherwise.
+    ///
+    pub fn received(&mut self, id: ConnId, now: Instant) -> bool {
+        match self.timers.get_mut(&id) {
+            Some(t) => {
+                t.last_received = now;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Get the connections that missed their inactivity timeout.
+    /// Note: the connections are not closed, reclaim the resources and close them.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - The moment to compare the last received packet with
+    ///
+    /// # Returns
+    ///
+    /// The identifiers of the timed out connections, sorted.
+    ///
+    pub fn timed_out(&self, now: Instant) -> Vec<ConnId> {
+        let mut ids: Vec<ConnId> = self
+            .timers
+            .iter()
+            .filter(|(_, t)| now.saturating_duration_since(t.last_received) > t.window)
+            .map(|(id, _)| *id)
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    /// Get the number of tracked connections
+    ///
+    /// # Returns
+    ///
+    /// The number of connections.
+    ///
+    pub fn len(&self) -> usize {
+        self.timers.len()
+    }
+
+    /// Test if there are no tracked connections
+    ///
+    /// # Returns
+    ///
+    /// True if there are no connections, false otherwise.
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.timers.is_empty()
+    }
+}
+
+#[test]
+fn inactivity_timeout() {
+    assert_eq!(
+        Duration::from_millis(40),
+        ConnectionTimer::inactivity_timeout(10_000, 0)
+    );
+    assert_eq!(
+        Duration::from_millis(80),
+        ConnectionTimer::inactivity_timeout(10_000, 1)
+    );
+    assert_eq!(
+        Duration::from_millis(5120),
+        ConnectionTimer::inactivity_timeout(10_000, 7)
+    );
+    assert_eq!(
+        Duration::from_millis(5120),
+        ConnectionTimer::inactivity_timeout(10_000, 8) // reserved
+    );
+}
+
+#[test]
+fn timed_out() {
+    let start = Instant::now();
+    let ms = Duration::from_millis;
+    let mut timer = ConnectionTimer::default();
+    timer.open(ConnId(1), 10_000, 0, start); // 40 ms
+    timer.open(ConnId(2), 20_000, 0, start); // 80 ms
+    assert_eq!(2, timer.len());
+
+    assert!(timer.timed_out(start + ms(40)).is_empty());
+    assert_eq!(vec![ConnId(1)], timer.timed_out(start + ms(41)));
+
+    // a received packet restarts the window
+    assert!(timer.received(ConnId(1), start + ms(30)));
+    assert!(timer.timed_out(start + ms(70)).is_empty());
+    assert_eq!(vec![ConnId(1), ConnId(2)], timer.timed_out(start + ms(81)));
+
+    assert!(timer.close(ConnId(1)));
+    assert!(!timer.close(ConnId(1)));
+    assert!(!timer.received(ConnId(1), start));
+    assert_eq!(vec![ConnId(2)], timer.timed_out(start + ms(81)));
+
+    // a moment before the last packet never times out
+    assert!(timer.timed_out(start).is_empty());
+}
+
+#[test]
+fn auto_traits() {
+    use crate::eip::check_auto_traits;
+
+    check_auto_traits::<ConnId>();
+    check_auto_traits::<ConnectionTimer>();
+}
@@ -0,0 +1,291 @@
+use crate::connected_address::ConnectedAddress;
+use crate::eip::{self, EipResult, Serializing};
+use crate::error_code::{ErrorCode, NOT_ENOUGH_DATA, REPLY_DATA_TOO_LARGE};
+use crate::item::{Id, Item};
+use crate::sequenced_address::SequencedAddress;
+use alloc::vec::Vec;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use core::mem::size_of;
+
+/// This is the Common Packet Format, the item count followed by the items with their body
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CommonPacketFormat {
+    pub items: Vec<(Item, Bytes)>,
+}
+
+impl CommonPacketFormat {
+    /// Add an item
+    ///
+    /// # Arguments
+    ///
+    /// * `type_id` - The type identification of the item
+    /// * `body` - The item data
+    ///
+    pub fn push(&mut self, type_id: Id, body: Bytes) {
+        self.items.push((Item::new(type_id, body.len()), body));
+    }
+
+    /// Find the body of the first item of a type
+    ///
+    /// # Arguments
+    ///
+    /// * `type_id` - The type identification of the item
+    ///
+    /// # Returns
+    ///
+    /// * The item body if present
+    ///
+    pub fn find(&self, type_id: &Id) -> Option<&Bytes> {
+        self.items
+            .iter()
+            .find(|(item, _)| item.type_id == *type_id)
+            .map(|(_, body)| body)
+    }
+
+    /// Get the sequenced address item
+    ///
+    /// # Returns
+    ///
+    /// * The sequenced address if the item is present
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if the item body is invalid.
+    ///
+    pub fn sequenced_address(&self) -> Result<Option<SequencedAddress>, ErrorCode> {
+        match self.find(&Item::SEQUENCED_ADDRESS) {
+            Some(body) => {
+                let mut sa = SequencedAddress::default();
+                sa.deserialize(&mut &body[..])?;
+                Ok(Some(sa))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Get the connected address item
+    ///
+    /// # Returns
+    ///
+    /// * The connected address if the item is present
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if the item body is invalid.
+    ///
+    pub fn connected_address(&self) -> Result<Option<ConnectedAddress>, ErrorCode> {
+        match self.find(&Item::CONNECTED_ADDRESS) {
+            Some(body) => {
+                let mut ca = ConnectedAddress::default();
+                ca.deserialize(&mut &body[..])?;
+                Ok(Some(ca))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Add a connected data item, the sequence count is prepended to the data
+    ///
+    /// # Arguments
+    ///
+    /// * `sequence_count` - The sequence count of the connected message
+    /// * `data` - The message data
+    ///
+    pub fn push_connected_data(&mut self, sequence_count: u16, data: &[u8]) {
+        let mut body = BytesMut::with_capacity(size_of::<u16>() + data.len());
+        body.put_u16_le(sequence_count);
+        body.put_slice(data);
+        self.push(Item::CONNECTED_DATA, body.freeze());
+    }
+
+    /// Get the connected data item, the sequence count is stripped from the data
+    ///
+    /// # Returns
+    ///
+    /// * The sequence count and the message data if the item is present
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if the sequence count is missing.
+    ///
+    pub fn connected_data(&self) -> Result<Option<(u16, Bytes)>, ErrorCode> {
+        match self.find(&Item::CONNECTED_DATA) {
+            Some(body) => {
+                if body.len() < size_of::<u16>() {
+                    return Err(NOT_ENOUGH_DATA);
+                }
+                let sequence_count = u16::from_le_bytes([body[0], body[1]]);
+                Ok(Some((sequence_count, body.slice(size_of::<u16>()..))))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+impl Serializing for CommonPacketFormat {
+    /// Deserialize all items, all or nothing.
+    /// On an error the items are unchanged and no input is consumed.
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to read from
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if there is not enough data.
+    ///
+    fn deserialize(&mut self, buf: &mut dyn Buf) -> EipResult {
+        if buf.remaining() < size_of::<u16>() {
+            return Err(NOT_ENOUGH_DATA);
+        }
+        self.items = eip::read_atomic(buf, |buf| {
+            let count = buf.get_u16_le() as usize;
+            let mut items =
+                Vec::with_capacity(count.min(buf.remaining() / Item::default().serial_size()));
+            for _ in 0..count {
+                let mut item = Item::default();
+                item.deserialize_checked(buf)?;
+                let body = buf.copy_to_bytes(item.len);
+                items.push((item, body));
+            }
+            Ok(items)
+        })?;
+        Ok(())
+    }
+
+    /// Serialize all items
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to write to
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if there is not enough room or too many items.
+    ///
+    fn serialize(&self, buf: &mut BytesMut) -> EipResult {
+        if buf.remaining_mut() < self.serial_size() || self.items.len() > u16::MAX as usize {
+            return Err(REPLY_DATA_TOO_LARGE);
+        }
+        buf.put_u16_le(self.items.len() as u16);
+        for (item, body) in &self.items {
+            Item::new(item.type_id.clone(), body.len()).serialize(buf)?;
+            buf.put_slice(body);
+        }
+        Ok(())
+    }
+
+    /// Get the serialized size in Bytes.
+    ///
+    /// # Returns
+    ///
+    /// * The number of bytes when serialized
+    ///
+    fn serial_size(&self) -> usize {
+        size_of::<u16>() // item count
+        + self
+            .items
+            .iter()
+            .map(|(item, body)| item.serial_size() + body.len())
+            .sum::<usize>()
+    }
+}
+
+#[test]
+fn deserialize() {
+    let mut cpf = CommonPacketFormat::default();
+    let mut buf = &b"\x02\0\x02\x80\x08\0\x78\x56\x34\x12\x04\x03\x02\x01\xb1\0\x02\0\x01\x02"[..];
+    assert_eq!(Ok(()), cpf.deserialize(&mut buf));
+    assert_eq!(0, buf.len());
+    assert_eq!(2, cpf.items.len());
+    assert_eq!(
+        Ok(Some(SequencedAddress::new(0x12345678, 0x01020304))),
+        cpf.sequenced_address()
+    );
+    assert_eq!(
+        Some(&Bytes::from_static(b"\x01\x02")),
+        cpf.find(&Item::CONNECTED_DATA)
+    );
+
+    // body shorter than the declared length
+    buf = &b"\x01\0\x02\x80\x08\0\x78\x56\x34\x12"[..];
+    assert_eq!(Err(NOT_ENOUGH_DATA), cpf.deserialize(&mut buf));
+    assert_eq!(10, buf.len());
+    assert_eq!(2, cpf.items.len());
+
+    // the second item is truncated, the first one is not kept
+    buf = &b"\x02\0\0\0\0\0\xb1\0\x02\0\x01"[..];
+    assert_eq!(Err(NOT_ENOUGH_DATA), cpf.deserialize(&mut buf));
+    assert_eq!(11, buf.len());
+    assert_eq!(
+        Some(&Bytes::from_static(b"\x01\x02")),
+        cpf.find(&Item::CONNECTED_DATA)
+    );
+}
+
+#[test]
+fn serialize() {
+    let mut body = BytesMut::with_capacity(8);
+    assert_eq!(
+        Ok(()),
+        SequencedAddress::new(0x12345678, 0x01020304).serialize(&mut body)
+    );
+
+    let mut cpf = CommonPacketFormat::default();
+    cpf.push(Item::SEQUENCED_ADDRESS, body.freeze());
+    cpf.push(Item::CONNECTED_DATA, Bytes::from_static(b"\x01\x02"));
+
+    let mut buf = BytesMut::with_capacity(100);
+    assert_eq!(Ok(()), cpf.serialize(&mut buf));
+    assert_eq!(cpf.serial_size(), buf.len());
+    assert_eq!(
+        &b"\x02\0\x02\x80\x08\0\x78\x56\x34\x12\x04\x03\x02\x01\xb1\0\x02\0\x01\x02"[..],
+        buf
+    );
+}
+
+#[test]
+fn connected() {
+    let mut body = BytesMut::with_capacity(4);
+    assert_eq!(
+        Ok(()),
+        ConnectedAddress::new(0x12345678).serialize(&mut body)
+    );
+
+    let mut cpf = CommonPacketFormat::default();
+    cpf.push(Item::CONNECTED_ADDRESS, body.freeze());
+    cpf.push_connected_data(0x0102, b"\x8e\0\0\0");
+
+    let mut buf = BytesMut::with_capacity(100);
+    assert_eq!(Ok(()), cpf.serialize(&mut buf));
+    assert_eq!(
+        &b"\x02\0\xa1\0\x04\0\x78\x56\x34\x12\xb1\0\x06\0\x02\x01\x8e\0\0\0"[..],
+        buf
+    );
+
+    let mut received = CommonPacketFormat::default();
+    assert_eq!(Ok(()), received.deserialize(&mut &buf[..]));
+    assert_eq!(
+        Ok(Some(ConnectedAddress::new(0x12345678))),
+        received.connected_address()
+    );
+    assert_eq!(
+        Ok(Some((0x0102, Bytes::from_static(b"\x8e\0\0\0")))),
+        received.connected_data()
+    );
+
+    // sequence count missing
+    cpf = CommonPacketFormat::default();
+    cpf.push(Item::CONNECTED_DATA, Bytes::from_static(b"\x01"));
+    assert_eq!(Err(NOT_ENOUGH_DATA), cpf.connected_data());
+    assert_eq!(Ok(None), cpf.connected_address());
+}
+
+#[test]
+fn auto_traits() {
+    use crate::eip::check_auto_traits;
+
+    check_auto_traits::<CommonPacketFormat>();
+}
@@ -1,7 +1,29 @@
 #![allow(dead_code)]
+use crate::attr::AttrPrimitive;
+use crate::eip::{EipResult, Serializing};
+use crate::error_code::{INVALID_PARAMETER, NOT_ENOUGH_DATA, REPLY_DATA_TOO_LARGE};
+#[cfg(test)]
+use alloc::string::ToString;
+use bytes::{Buf, BufMut, BytesMut};
+use core::fmt;
+use core::mem::size_of;
+
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DataType(u8);
 
+impl From<u8> for DataType {
+    fn from(val: u8) -> Self {
+        Self(val)
+    }
+}
+
+impl From<DataType> for u8 {
+    fn from(val: DataType) -> Self {
+        val.0
+    }
+}
+
 pub const BOOL: DataType = DataType(0xc1); // boolean
 pub const SINT: DataType = DataType(0xc2); // signed 8 bit integer
 pub const INT: DataType = DataType(0xc3); // signed 16 bit integer
@@ -17,12 +39,287 @@ pub const BYTE: DataType = DataType(0xd1); // bit string 8 bit
 pub const WORD: DataType = DataType(0xd2); // bit string 16 bit
 pub const DWORD: DataType = DataType(0xd3); // bit string 32 bit
 pub const LWORD: DataType = DataType(0xd4); // bit string 64 bit
-pub const SHORT_STRING: DataType = DataType(0xda); // Path segments
-pub const EPATH: DataType = DataType(0xdc); // Character string, 1 byte character, 1 byte length
+pub const SHORT_STRING: DataType = DataType(0xda); // Character string, 1 byte character, 1 byte length
+pub const EPATH: DataType = DataType(0xdc); // Path segments
+
+impl DataType {
+    /// Get the serialized size of a value of this type in Bytes.
+    ///
+    /// # Returns
+    ///
+    /// * The number of bytes when serialized, None for variable length types
+    ///
+    pub fn serial_size(&self) -> Option<usize> {
+        match *self {
+            BOOL | SINT | USINT | BYTE => Some(1),
+            INT | UINT | WORD => Some(2),
+            DINT | UDINT | REAL | DWORD => Some(4),
+            LINT | ULINT | LREAL | LWORD => Some(8),
+            _ => None,
+        }
+    }
+
+    /// Get the symbolic name of the type
+    ///
+    /// # Returns
+    ///
+    /// * The type name, "Unknown" if the type is not known
+    ///
+    pub fn name(&self) -> &'static str {
+        match *self {
+            BOOL => "BOOL",
+            SINT => "SINT",
+            INT => "INT",
+            DINT => "DINT",
+            LINT => "LINT",
+            USINT => "USINT",
+            UINT => "UINT",
+            UDINT => "UDINT",
+            ULINT => "ULINT",
+            REAL => "REAL",
+            LREAL => "LREAL",
+            BYTE => "BYTE",
+            WORD => "WORD",
+            DWORD => "DWORD",
+            LWORD => "LWORD",
+            SHORT_STRING => "SHORT_STRING",
+            EPATH => "EPATH",
+            _ => "Unknown",
+        }
+    }
+
+    /// Test if this is one of the elementary data types of this crate
+    ///
+    /// # Returns
+    ///
+    /// True if the type is known, false otherwise.
+    ///
+    pub fn is_known(&self) -> bool {
+        matches!(
+            *self,
+            BOOL | SINT
+                | INT
+                | DINT
+                | LINT
+                | USINT
+                | UINT
+                | UDINT
+                | ULINT
+                | REAL
+                | LREAL
+                | BYTE
+                | WORD
+                | DWORD
+                | LWORD
+                | SHORT_STRING
+                | EPATH
+        )
+    }
+}
+
+impl fmt::Display for DataType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_known() {
+            f.write_str(self.name())
+        } else {
+            write!(f, "Unknown({:#04x})", self.0)
+        }
+    }
+}
+
+macro_rules! typed_value {
+    ($($variant:ident($t:ty) = $dt:ident),*) => {
+        /// A scalar value preceded by its data type code,
+        /// for values of which the type is not known in advance.
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        pub enum TypedValue {
+            $($variant($t)),*
+        }
+
+        impl TypedValue {
+            /// Get the data type of the value
+            ///
+            /// # Returns
+            ///
+            /// * The data type code
+            ///
+            pub fn data_type(&self) -> DataType {
+                match self {
+                    $(Self::$variant(_) => $dt),*
+                }
+            }
+        }
+
+        impl Serializing for TypedValue {
+            /// Read the type code and the value from a message buffer.
+            ///
+            /// # Arguments
+            ///
+            /// * `buf` - The message buffer to read from
+            ///
+            /// # Errors
+            ///
+            /// An error variant will be returned if there is not enough data
+            /// or the type is not a scalar.
+            ///
+            fn deserialize(&mut self, buf: &mut dyn Buf) -> EipResult {
+                if buf.remaining() < size_of::<u8>() {
+                    return Err(NOT_ENOUGH_DATA);
+                }
+                let dt = DataType(buf.chunk()[0]);
+                let size = dt.serial_size().ok_or(INVALID_PARAMETER)?;
+                if buf.remaining() < size_of::<u8>() + size {
+                    return Err(NOT_ENOUGH_DATA);
+                }
+                buf.advance(size_of::<u8>());
+                *self = match dt {
+                    $($dt => Self::$variant(<$t>::read(buf)),)*
+                    _ => return Err(INVALID_PARAMETER),
+                };
+                Ok(())
+            }
+
+            /// Write the type code and the value to a message buffer.
+            ///
+            /// # Arguments
+            ///
+            /// * `buf` - The message buffer to write to
+            ///
+            /// # Errors
+            ///
+            /// An error variant will be returned if there is not enough room.
+            ///
+            fn serialize(&self, buf: &mut BytesMut) -> EipResult {
+                if buf.remaining_mut() < self.serial_size() {
+                    return Err(REPLY_DATA_TOO_LARGE);
+                }
+                buf.put_u8(self.data_type().0);
+                match *self {
+                    $(Self::$variant(v) => <$t>::write(buf, v)),*
+                }
+                Ok(())
+            }
+
+            /// Get the serialized size in Bytes.
+            ///
+            /// # Returns
+            ///
+            /// * The number of bytes when serialized
+            ///
+            fn serial_size(&self) -> usize {
+                size_of::<u8>() + self.data_type().serial_size().unwrap_or_default()
+            }
+        }
+    };
+}
+
+typed_value!(
+    Bool(bool) = BOOL,
+    Sint(i8) = SINT,
+    Int(i16) = INT,
+    Dint(i32) = DINT,
+    Lint(i64) = LINT,
+    Usint(u8) = USINT,
+    Uint(u16) = UINT,
+    Udint(u32) = UDINT,
+    Ulint(u64) = ULINT,
+    Real(f32) = REAL,
+    Lreal(f64) = LREAL,
+    Byte(u8) = BYTE,
+    Word(u16) = WORD,
+    Dword(u32) = DWORD,
+    Lword(u64) = LWORD
+);
+
+/// Default is a false boolean
+impl Default for TypedValue {
+    fn default() -> Self {
+        Self::Bool(false)
+    }
+}
+
+#[test]
+fn serial_size() {
+    for t in [BOOL, SINT, USINT, BYTE] {
+        assert_eq!(Some(1), t.serial_size());
+    }
+    for t in [INT, UINT, WORD] {
+        assert_eq!(Some(2), t.serial_size());
+    }
+    for t in [DINT, UDINT, REAL, DWORD] {
+        assert_eq!(Some(4), t.serial_size());
+    }
+    for t in [LINT, ULINT, LREAL, LWORD] {
+        assert_eq!(Some(8), t.serial_size());
+    }
+    assert_eq!(None, SHORT_STRING.serial_size());
+    assert_eq!(None, EPATH.serial_size());
+}
+
+#[test]
+fn name() {
+    for t in [BOOL, DINT, LREAL, LWORD, SHORT_STRING, EPATH] {
+        let code = u8::from(t.clone());
+        assert_eq!(t, DataType::from(code));
+        assert!(t.is_known());
+        assert_eq!(t.name(), DataType::from(code).to_string());
+    }
+    assert_eq!("UDINT", UDINT.to_string());
+    assert!(!DataType::from(0x12).is_known());
+    assert_eq!("Unknown", DataType::from(0x12).name());
+    assert_eq!("Unknown(0x12)", DataType::from(0x12).to_string());
+    assert_eq!(0x12, u8::from(DataType::from(0x12)));
+}
 
 #[test]
 fn auto_traits() {
     use crate::eip::check_auto_traits;
 
     check_auto_traits::<DataType>();
+    check_auto_traits::<TypedValue>();
+}
+
+#[test]
+fn typed_value() {
+    let mut buf = BytesMut::with_capacity(10);
+    let uint = TypedValue::Uint(0x1234);
+    assert_eq!(UINT, uint.data_type());
+    assert_eq!(Ok(()), uint.serialize(&mut buf));
+    assert_eq!(uint.serial_size(), buf.len());
+    assert_eq!(&b"\xc7\x34\x12"[..], buf);
+
+    let mut received = TypedValue::default();
+    assert_eq!(Ok(()), received.deserialize(&mut &buf[..]));
+    assert_eq!(uint, received);
+
+    buf.clear();
+    let real = TypedValue::Real(1.5);
+    assert_eq!(Ok(()), real.serialize(&mut buf));
+    assert_eq!(&b"\xca\0\0\xc0\x3f"[..], buf);
+    assert_eq!(Ok(()), received.deserialize(&mut &buf[..]));
+    assert_eq!(TypedValue::Real(1.5), received);
+
+    // bit strings share the unsigned integer width
+    let mut data = &b"\xd2\x34\x12"[..];
+    assert_eq!(Ok(()), received.deserialize(&mut data));
+    assert_eq!(TypedValue::Word(0x1234), received);
+    assert_eq!(0, data.len());
+}
+
+#[test]
+fn typed_value_bounds() {
+    let mut received = TypedValue::default();
+
+    let mut data = &b"\xca\0\0\xc0"[..];
+    assert_eq!(Err(NOT_ENOUGH_DATA), received.deserialize(&mut data));
+    assert_eq!(4, data.len());
+
+    data = &b"\xda\x01a"[..];
+    assert_eq!(Err(INVALID_PARAMETER), received.deserialize(&mut data));
+    assert_eq!(3, data.len());
+
+    data = &b""[..];
+    assert_eq!(Err(NOT_ENOUGH_DATA), received.deserialize(&mut data));
+    assert_eq!(TypedValue::default(), received);
 }
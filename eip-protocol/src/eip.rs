@@ -1,4 +1,5 @@
-use crate::error_code::{ErrorCode, REPLY_DATA_TOO_LARGE};
+use crate::error_code::{ErrorCode, MESSAGE_FORMAT_ERROR, REPLY_DATA_TOO_LARGE};
+use alloc::vec::Vec;
 use bytes::{Buf, BufMut, BytesMut};
 
 pub type EipResult = Result<(), ErrorCode>;
@@ -6,12 +7,147 @@ pub type EipResult = Result<(), ErrorCode>;
 /// EIP marshalling functions
 pub trait Serializing {
     /// Un-marshalling
+    /// Note: an aggregate, e.g. Identity, deserializes all or nothing. On error its value is
+    /// unchanged and no input is consumed.
     fn deserialize(&mut self, buf: &mut dyn Buf) -> EipResult;
     /// Marshalling
     fn serialize(&self, buf: &mut BytesMut) -> EipResult;
+    /// The number of bytes when marshalled
+    fn serial_size(&self) -> usize;
+
+    /// Marshalling into a new buffer of exactly the serialized size
+    fn to_bytes(&self) -> Result<BytesMut, ErrorCode> {
+        let mut buf = BytesMut::with_capacity(self.serial_size());
+        self.serialize(&mut buf)?;
+        Ok(buf)
+    }
+}
+
+impl<T: Serializing + Default> Serializing for Vec<T> {
+    /// Deserialize elements until the buffer is exhausted, all or nothing.
+    /// Note: consumes all remaining data, use deserialize_count() for a known number of elements
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to read from
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if an element can not be deserialized.
+    ///
+    fn deserialize(&mut self, buf: &mut dyn Buf) -> EipResult {
+        let mut elements = Vec::with_capacity(max_count::<T>(buf));
+        read_atomic(buf, |buf| {
+            while buf.has_remaining() {
+                let before = buf.remaining();
+                let mut element = T::default();
+                element.deserialize(buf)?;
+                if buf.remaining() == before {
+                    return Err(MESSAGE_FORMAT_ERROR); // no progress, e.g. an empty element
+                }
+                elements.push(element);
+            }
+            Ok(())
+        })?;
+        *self = elements;
+        Ok(())
+    }
+
+    /// Serialize all elements in order
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to write to
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if an element can not be serialized.
+    ///
+    fn serialize(&self, buf: &mut BytesMut) -> EipResult {
+        if buf.remaining_mut() < self.serial_size() {
+            return Err(REPLY_DATA_TOO_LARGE);
+        }
+        for element in self {
+            element.serialize(buf)?;
+        }
+        Ok(())
+    }
+
+    /// Get the serialized size in Bytes.
+    ///
+    /// # Returns
+    ///
+    /// * The number of bytes when serialized
+    ///
+    fn serial_size(&self) -> usize {
+        self.iter().map(|e| e.serial_size()).sum()
+    }
+}
+
+/// Deserialize a known number of elements
+///
+/// # Arguments
+///
+/// * `buf` - The message buffer to read from
+/// * `count` - The number of elements to read
+///
+/// # Returns
+///
+/// * The deserialized elements, the remaining data is not consumed
+///
+/// # Errors
+///
+/// An error variant will be returned if an element can not be deserialized.
+///
+pub fn deserialize_count<T: Serializing + Default>(
+    buf: &mut dyn Buf,
+    count: usize,
+) -> Result<Vec<T>, ErrorCode> {
+    let mut elements = Vec::with_capacity(count.min(max_count::<T>(buf)));
+    read_atomic(buf, |buf| {
+        for _ in 0..count {
+            let mut element = T::default();
+            element.deserialize(buf)?;
+            elements.push(element);
+        }
+        Ok(())
+    })?;
+    Ok(elements)
+}
+
+/// Get the maximum number of elements the buffer can hold, to limit an allocation
+/// to the received data instead of a count from the message.
+///
+/// # Arguments
+///
+/// * `buf` - The message buffer to read from
+///
+/// # Returns
+///
+/// * The number of elements of the minimum size that fit in the remaining data
+///
+fn max_count<T: Serializing + Default>(buf: &dyn Buf) -> usize {
+    buf.remaining() / T::default().serial_size().max(1)
+}
+
+/// Get the shortfall of a buffer to reserve room with split_off().
+/// Grow the buffer with `buf.reserve(buf.capacity() - buf.len() + needed)`.
+///
+/// # Arguments
+///
+/// * `buf` - The message buffer to write to
+/// * `s` - the number of bytes to reserve
+///
+/// # Returns
+///
+/// * The number of bytes the capacity is short, 0 if the reservation fits
+///
+pub fn remaining_needed(buf: &BytesMut, s: usize) -> usize {
+    s.saturating_sub(buf.capacity())
 }
 
 /// Reserve room in a buffer to serialize some object later.
+/// Note: the reservation starts at the beginning of the buffer.
 ///
 /// # Returns
 ///
@@ -24,14 +160,300 @@ pub trait Serializing {
 ///
 /// # Errors
 ///
-/// An error variant will be returned if there is not enough room for the reservation.
+/// An error variant will be returned if there is not enough room for the reservation,
+/// remaining_needed() reports the shortfall.
 ///
 pub fn split_off(buf: &mut BytesMut, s: usize) -> Result<BytesMut, ErrorCode> {
-    if buf.remaining_mut() < s {
+    if remaining_needed(buf, s) > 0 {
         return Err(REPLY_DATA_TOO_LARGE);
     }
     Ok(buf.split_off(s))
 }
 
+/// Copy the first bytes of a buffer without consuming them, also if they are split over chunks.
+/// Note: without std only the first chunk is available.
+///
+/// # Arguments
+///
+/// * `buf` - The message buffer to read from
+/// * `dst` - Filled with the first bytes of the buffer
+///
+/// # Returns
+///
+/// * True if dst is filled, false if the bytes are not available without consuming them
+///
+pub(crate) fn peek(buf: &dyn Buf, dst: &mut [u8]) -> bool {
+    if let Some(b) = buf.chunk().get(..dst.len()) {
+        dst.copy_from_slice(b);
+        return true;
+    }
+    #[cfg(feature = "std")]
+    {
+        let mut chunks = [std::io::IoSlice::new(&[]); 16];
+        let n = buf.chunks_vectored(&mut chunks);
+        let mut filled = 0;
+        for chunk in &chunks[..n] {
+            let k = chunk.len().min(dst.len() - filled);
+            dst[filled..filled + k].copy_from_slice(&chunk[..k]);
+            filled += k;
+            if filled == dst.len() {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+/// Read from a view of the buffer, the bytes are only consumed on success.
+/// Note: without std a buffer split over several chunks is read directly, it is consumed
+/// on error then.
+///
+/// # Arguments
+///
+/// * `buf` - The message buffer to read from
+/// * `read` - Reads the view
+///
+/// # Returns
+///
+/// * The result of `read`
+///
+/// # Errors
+///
+/// The error variant of `read` will be returned.
+///
+pub(crate) fn read_atomic<R, F>(buf: &mut dyn Buf, read: F) -> Result<R, ErrorCode>
+where
+    F: FnOnce(&mut dyn Buf) -> Result<R, ErrorCode>,
+{
+    let copy;
+    let mut data = if buf.chunk().len() == buf.remaining() {
+        buf.chunk()
+    } else {
+        let mut bytes = alloc::vec![0; buf.remaining()];
+        if !peek(buf, &mut bytes) {
+            return read(buf);
+        }
+        copy = bytes;
+        &copy[..]
+    };
+    let size = data.len();
+    let result = read(&mut data)?;
+    let consumed = size - data.len();
+    buf.advance(consumed);
+    Ok(result)
+}
+
+/// Deserialize into a copy of a value from a view of the buffer, all or nothing.
+/// On success the value is replaced and the bytes are consumed, on error neither changes.
+///
+/// # Arguments
+///
+/// * `value` - The value to replace
+/// * `buf` - The message buffer to read from
+/// * `read` - Deserializes the copy of the value
+///
+/// # Errors
+///
+/// The error variant of `read` will be returned.
+///
+pub(crate) fn deserialize_atomic<T, F>(value: &mut T, buf: &mut dyn Buf, read: F) -> EipResult
+where
+    T: Clone,
+    F: FnOnce(&mut T, &mut dyn Buf) -> EipResult,
+{
+    let mut received = value.clone();
+    read_atomic(buf, |buf| read(&mut received, buf))?;
+    *value = received;
+    Ok(())
+}
+
 #[cfg(test)]
 pub fn check_auto_traits<T: Sized + Send + Sync + Unpin>() {}
+
+#[cfg(test)]
+pub fn check_hash_key<T: core::hash::Hash + Eq>(key: T) {
+    let mut map = std::collections::HashMap::new();
+    map.insert(key, ());
+    assert_eq!(1, map.len());
+}
+
+#[test]
+fn vec() {
+    use crate::attr::{AccessCode, Uint};
+    use crate::error_code::NOT_ENOUGH_DATA;
+
+    let list: Vec<Uint> = (1..4)
+        .map(|n| Uint::new(n, AccessCode::default()))
+        .collect();
+    let mut buf = BytesMut::with_capacity(10);
+    assert_eq!(Ok(()), list.serialize(&mut buf));
+    assert_eq!(6, list.serial_size());
+    assert_eq!(&b"\x01\0\x02\0\x03\0"[..], buf);
+
+    let mut received: Vec<Uint> = Vec::new();
+    assert_eq!(Ok(()), received.deserialize(&mut &buf[..]));
+    assert_eq!(list, received);
+
+    let mut data = &buf[..];
+    let first: Vec<Uint> = deserialize_count(&mut data, 2).unwrap();
+    assert_eq!(&list[..2], &first[..]);
+    assert_eq!(2, data.len());
+
+    // half an element, nothing changed nor consumed
+    let mut data = &b"\x01\0\x02"[..];
+    assert_eq!(Err(NOT_ENOUGH_DATA), received.deserialize(&mut data));
+    assert_eq!(3, data.len());
+    assert_eq!(list, received);
+    let mut data = &buf[..];
+    assert_eq!(
+        Err(NOT_ENOUGH_DATA),
+        deserialize_count::<Uint>(&mut data, 4).map(|v| v.len())
+    );
+    assert_eq!(6, data.len());
+
+    // the allocation is limited by the received data, not the count
+    let all: Vec<Uint> = deserialize_count(&mut &buf[..], 3).unwrap();
+    assert!(all.capacity() < 10);
+    assert_eq!(
+        Err(NOT_ENOUGH_DATA),
+        deserialize_count::<Uint>(&mut &buf[..], usize::MAX).map(|v| v.len())
+    );
+}
+
+/// Deserialize arbitrary data as every message type, must only return Ok or an error
+#[cfg(test)]
+fn deserialize_all(data: &[u8]) {
+    use crate::attr::{
+        AttrArray, Bool, CipString, Duint, Dword, EPath, Lreal, ShortString, Uint, Usint,
+    };
+    use crate::connected_address::ConnectedAddress;
+    use crate::connection_manager::{
+        ForwardCloseReply, ForwardCloseRequest, ForwardOpenReply, ForwardOpenRequest,
+        UnconnectedSend,
+    };
+    use crate::cpf::CommonPacketFormat;
+    use crate::data_type::TypedValue;
+    use crate::encapsulation::Encapsulation;
+    use crate::identity::Identity;
+    use crate::interfaces::ListInterfaces;
+    use crate::item::Item;
+    use crate::message::EipMessage;
+    use crate::message_router::{ElectronicKey, Request, Response};
+    use crate::multiple_service::MultipleServicePacket;
+    use crate::send_data::SendData;
+    use crate::sequenced_address::SequencedAddress;
+    use crate::services::{ServiceList, Services};
+    use crate::socket_address::SocketAddress;
+    use crate::static_attr::StaticAttr;
+
+    fn check<T: Serializing + Default>(data: &[u8]) {
+        let mut buf = data;
+        let _ = T::default().deserialize(&mut buf);
+        assert!(buf.len() <= data.len());
+
+        let mut buf = data;
+        let _ = Vec::<T>::default().deserialize(&mut buf);
+    }
+
+    check::<Bool>(data);
+    check::<Usint>(data);
+    check::<Uint>(data);
+    check::<Duint>(data);
+    check::<Lreal>(data);
+    check::<Dword>(data);
+    check::<ShortString>(data);
+    check::<CipString>(data);
+    check::<EPath>(data);
+    check::<AttrArray<Uint, 4>>(data);
+    check::<ConnectedAddress>(data);
+    check::<ForwardOpenRequest>(data);
+    check::<ForwardOpenReply>(data);
+    check::<ForwardCloseRequest>(data);
+    check::<ForwardCloseReply>(data);
+    check::<UnconnectedSend>(data);
+    check::<CommonPacketFormat>(data);
+    check::<TypedValue>(data);
+    check::<Encapsulation>(data);
+    check::<Identity>(data);
+    check::<ListInterfaces>(data);
+    check::<Item>(data);
+    check::<EipMessage>(data);
+    check::<ElectronicKey>(data);
+    check::<Request>(data);
+    check::<Response>(data);
+    check::<MultipleServicePacket>(data);
+    check::<SendData>(data);
+    check::<SequencedAddress>(data);
+    check::<ServiceList>(data);
+    check::<Services>(data);
+    check::<SocketAddress>(data);
+    check::<StaticAttr>(data);
+}
+
+#[cfg(test)]
+const SEEDS: &[&[u8]] = &[
+    b"",
+    // attr: string length beyond the data
+    b"\xff\xffab",
+    // connected_address, sequenced_address: item length beyond the data
+    b"\xa1\0\xff\xff\x01\x02",
+    // connection_manager: path size beyond the data
+    b"\x0a\x0e\x01\x02\x03\x04\x05\x06\x07\x08\x09\x0a\x0b\x0c\x0d\x0e\x0f\x10\x11\x12\x13\x14\x15\x16\x17\x18\x19\x1a\x1b\x1c\x1d\x1e\x1f\xff",
+    // cpf: item count without items
+    b"\xff\xff\0\0\0\0",
+    // encapsulation, message: length beyond the data
+    b"\x6f\0\xff\xff\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\x02\0",
+    // identity: product name length beyond the data
+    b"\x01\0\x02\0\x03\0\x04\0\0\0\x05\0\0\0\xffHello",
+    // interfaces, services: item count without items
+    b"\x02\0\0\x01\x14\0\x01\0",
+    // message_router: path size without the path
+    b"\x0e\xff\x20",
+    // multiple_service: offsets out of order and beyond the data
+    b"\x0a\x02\x20\x02\x24\x01\x03\0\x08\0\xff\x7f\x02\0",
+    // socket_address: truncated address
+    b"\0\x02\xaf\x12\x12",
+];
+
+#[cfg(test)]
+proptest::proptest! {
+    #[test]
+    fn deserialize_arbitrary(data in proptest::collection::vec(proptest::num::u8::ANY, 0..600)) {
+        deserialize_all(&data);
+    }
+
+    #[test]
+    fn deserialize_arbitrary_tail(
+        head in proptest::sample::select(SEEDS),
+        data in proptest::collection::vec(proptest::num::u8::ANY, 0..100),
+    ) {
+        let mut buf = head.to_vec();
+        buf.extend(data);
+        deserialize_all(&buf);
+    }
+}
+
+#[test]
+fn split_off_shortfall() {
+    let mut buf = BytesMut::with_capacity(4);
+    assert_eq!(0, remaining_needed(&buf, 4));
+    assert_eq!(6, remaining_needed(&buf, 10));
+    assert_eq!(
+        Err(REPLY_DATA_TOO_LARGE),
+        split_off(&mut buf, 10).map(|b| b.len())
+    );
+
+    let needed = remaining_needed(&buf, 10);
+    buf.reserve(buf.capacity() - buf.len() + needed);
+    assert_eq!(0, remaining_needed(&buf, 10));
+    let rest = split_off(&mut buf, 10).unwrap();
+    assert_eq!(10, buf.capacity()); // the reservation
+    assert_eq!(0, rest.len());
+}
+
+#[test]
+fn deserialize_seeds() {
+    for seed in SEEDS {
+        deserialize_all(seed);
+    }
+}
@@ -1,15 +1,75 @@
 use crate::eip;
 use crate::eip::{EipResult, Serializing};
-use crate::error_code::{ErrorCode, NOT_ENOUGH_DATA, REPLY_DATA_TOO_LARGE};
+use crate::error_code::{
+    ErrorCode, INVALID_SESSION, MESSAGE_FORMAT_ERROR, NOT_ENOUGH_DATA, REPLY_DATA_TOO_LARGE,
+    UNSUPPORTED_COMMAND,
+};
+#[cfg(test)]
+use alloc::string::ToString;
 use bytes::{Buf, BufMut, BytesMut};
+use core::fmt;
 use core::mem::size_of;
 const CONTEXT_LEN: usize = 8;
 pub const VERSION: u16 = 1;
 
-#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Command(u16);
 
+impl Command {
+    /// Get the name of the command
+    ///
+    /// # Returns
+    ///
+    /// * The command name, "Unknown" if the command is not known
+    ///
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Encapsulation::NOP => "NOP",
+            Encapsulation::LIST_SERVICES => "ListServices",
+            Encapsulation::LIST_IDENTITY => "ListIdentity",
+            Encapsulation::LIST_INTERFACES => "ListInterfaces",
+            Encapsulation::REGISTER_SESSION => "RegisterSession",
+            Encapsulation::UNREGISTER_SESSION => "UnregisterSession",
+            Encapsulation::SEND_RR_DATA => "SendRRData",
+            Encapsulation::SEND_UNIT_DATA => "SendUnitData",
+            _ => "Unknown",
+        }
+    }
+
+    /// Test if this is one of the standard commands
+    ///
+    /// # Returns
+    ///
+    /// True if the command is known, false otherwise, e.g. vendor specific.
+    ///
+    pub fn is_known(&self) -> bool {
+        matches!(
+            *self,
+            Encapsulation::NOP
+                | Encapsulation::LIST_SERVICES
+                | Encapsulation::LIST_IDENTITY
+                | Encapsulation::LIST_INTERFACES
+                | Encapsulation::REGISTER_SESSION
+                | Encapsulation::UNREGISTER_SESSION
+                | Encapsulation::SEND_RR_DATA
+                | Encapsulation::SEND_UNIT_DATA
+        )
+    }
+}
+
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_known() {
+            f.write_str(self.name())
+        } else {
+            write!(f, "Unknown({:#06x})", self.0)
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Encapsulation {
     pub command: Command,           // Request
     pub len: u16,                   // Number of bytes
@@ -23,44 +83,192 @@ impl Encapsulation {
     pub const NOP: Command = Command(0x00);
     pub const LIST_SERVICES: Command = Command(0x04);
     pub const LIST_IDENTITY: Command = Command(0x63);
+    pub const LIST_INTERFACES: Command = Command(0x64);
     pub const REGISTER_SESSION: Command = Command(0x65);
     pub const UNREGISTER_SESSION: Command = Command(0x66);
     pub const SEND_RR_DATA: Command = Command(0x6f);
+    pub const SEND_UNIT_DATA: Command = Command(0x70);
 
-    /// Get the serialized size in Bytes.
+    /// Reserve room in a buffer to serialize this later.
     ///
     /// # Returns
     ///
-    /// * The number of bytes when serialized
+    /// * The rest of the buffer after the reservation
     ///
-    pub const fn serial_size(&self) -> usize {
-        size_of::<u16>() // command
-        + size_of::<u16>() // len
-        + size_of::<u32>() // session
-        + size_of::<u32>() // status
-        + CONTEXT_LEN // context
-        + size_of::<u32>() // options
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to write to
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if there is not enough room for the reservation.
+    ///
+    pub fn split_off(&self, buf: &mut BytesMut) -> Result<BytesMut, ErrorCode> {
+        eip::split_off(buf, self.serial_size())
     }
 
-    /// Reserve room in a buffer to serialize this later.
+    /// Check that the length field matches the payload following the header
+    ///
+    /// # Arguments
+    ///
+    /// * `remaining` - The number of bytes following the header
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if the frame is truncated or longer than claimed.
+    ///
+    pub fn validate_len(&self, remaining: usize) -> EipResult {
+        if self.len as usize != remaining {
+            return Err(MESSAGE_FORMAT_ERROR);
+        }
+        Ok(())
+    }
+
+    /// Create the header of the reply to this request.
+    /// The command, session and sender context are echoed, the length is filled in
+    /// when serialized with the payload.
+    ///
+    /// # Arguments
+    ///
+    /// * `status` - The status of the reply, SUCCESS or the error
     ///
     /// # Returns
     ///
-    /// * The rest of the buffer after the reservation
+    /// * The reply header
+    ///
+    pub fn to_reply(&self, status: ErrorCode) -> Self {
+        Encapsulation {
+            command: self.command.clone(),
+            len: 0,
+            session: self.session,
+            status: u8::from(status) as u32,
+            context: self.context,
+            options: 0,
+        }
+    }
+
+    /// Test if this is a reply by the status field.
+    /// Note: a successful reply has status zero like a request, use is_reply_to()
+    /// when the request is known.
+    ///
+    /// # Returns
+    ///
+    /// True if the status is not zero, false otherwise.
+    ///
+    pub fn is_reply(&self) -> bool {
+        self.status != 0
+    }
+
+    /// Test if this is the reply to a request, by the echoed command and sender context.
+    /// The session is only compared when the request has one, RegisterSession assigns it.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The header of the request
+    ///
+    /// # Returns
+    ///
+    /// True if this replies to the request, false otherwise.
+    ///
+    pub fn is_reply_to(&self, request: &Encapsulation) -> bool {
+        self.command == request.command
+            && self.context == request.context
+            && (request.session == 0 || self.session == request.session)
+    }
+
+    /// Check the header of a received request before processing it.
+    /// The options shall be zero for the standard commands and the status shall be zero.
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if the options or the status are not zero.
+    ///
+    pub fn validate(&self) -> EipResult {
+        if self.command.is_known() && self.options != 0 {
+            return Err(MESSAGE_FORMAT_ERROR);
+        }
+        if self.status != 0 {
+            return Err(MESSAGE_FORMAT_ERROR);
+        }
+        Ok(())
+    }
+
+    /// Serialize all fields followed by the payload.
+    /// The length field is set to the payload size.
     ///
     /// # Arguments
     ///
     /// * `buf` - The message buffer to write to
+    /// * `payload` - The command specific data
     ///
     /// # Errors
     ///
-    /// An error variant will be returned if there is not enough room for the reservation.
+    /// An error variant will be returned if there is not enough room or the payload is too large.
     ///
-    pub fn split_off(&self, buf: &mut BytesMut) -> Result<BytesMut, ErrorCode> {
-        eip::split_off(buf, self.serial_size())
+    pub fn serialize_with_payload(&self, buf: &mut BytesMut, payload: &[u8]) -> EipResult {
+        if payload.len() > u16::MAX as usize
+            || buf.remaining_mut() < self.serial_size() + payload.len()
+        {
+            return Err(REPLY_DATA_TOO_LARGE);
+        }
+        let header = Encapsulation {
+            len: payload.len() as u16,
+            ..self.clone()
+        };
+        header.serialize(buf)?;
+        buf.put_slice(payload);
+        Ok(())
     }
 }
 
+/// Serialize the client side RegisterSession body,
+/// the protocol version followed by the options flags.
+///
+/// # Arguments
+///
+/// * `buf` - The message buffer to write to
+///
+/// # Errors
+///
+/// An error variant will be returned if there is not enough room.
+///
+pub fn register_session_request(buf: &mut BytesMut) -> EipResult {
+    if buf.remaining_mut() < size_of::<u16>() + size_of::<u16>() {
+        return Err(REPLY_DATA_TOO_LARGE);
+    }
+    buf.put_u16_le(VERSION);
+    buf.put_u16_le(0); // OptionFlags
+    Ok(())
+}
+
+/// Get the session handle from a RegisterSession reply
+///
+/// # Arguments
+///
+/// * `enc` - The received encapsulation header
+///
+/// # Returns
+///
+/// * The session handle assigned by the server
+///
+/// # Errors
+///
+/// An error variant will be returned if the header is not a RegisterSession reply,
+/// the server reported an error status or no session was assigned.
+///
+pub fn parse_register_session_reply(enc: &Encapsulation) -> Result<u32, ErrorCode> {
+    if enc.command != Encapsulation::REGISTER_SESSION {
+        return Err(UNSUPPORTED_COMMAND);
+    }
+    if enc.status != 0 {
+        return Err(u8::try_from(enc.status).map_or(MESSAGE_FORMAT_ERROR, ErrorCode::from));
+    }
+    if enc.session == 0 {
+        return Err(INVALID_SESSION);
+    }
+    Ok(enc.session)
+}
+
 impl Serializing for Encapsulation {
     /// Deserialize all fields
     ///
@@ -109,6 +317,21 @@ impl Serializing for Encapsulation {
         buf.put_u32_le(self.options);
         Ok(())
     }
+
+    /// Get the serialized size in Bytes.
+    ///
+    /// # Returns
+    ///
+    /// * The number of bytes when serialized
+    ///
+    fn serial_size(&self) -> usize {
+        size_of::<u16>() // command
+        + size_of::<u16>() // len
+        + size_of::<u32>() // session
+        + size_of::<u32>() // status
+        + CONTEXT_LEN // context
+        + size_of::<u32>() // options
+    }
 }
 
 #[test]
@@ -132,13 +355,14 @@ fn deserialize() {
 
 #[test]
 fn serialize() {
-    let mut enc = Encapsulation::default();
-    enc.command = Command(1);
-    enc.len = 2;
-    enc.session = 3;
-    enc.status = 4;
-    enc.context = [1, 2, 3, 4, 5, 6, 7, 8];
-    enc.options = 5;
+    let enc = Encapsulation {
+        command: Command(1),
+        len: 2,
+        session: 3,
+        status: 4,
+        context: [1, 2, 3, 4, 5, 6, 7, 8],
+        options: 5,
+    };
     let mut buf = BytesMut::with_capacity(100);
     assert_eq!(Ok(()), enc.serialize(&mut buf));
     assert_eq!(24, buf.len());
@@ -148,6 +372,144 @@ fn serialize() {
     );
 }
 
+#[test]
+fn serialize_with_payload() {
+    let enc = Encapsulation {
+        command: Encapsulation::REGISTER_SESSION,
+        len: 0x1234, // overwritten
+        session: 3,
+        ..Default::default()
+    };
+    let payload = BytesMut::from(&b"\x01\0\0\0"[..]);
+    let mut buf = BytesMut::with_capacity(100);
+    assert_eq!(Ok(()), enc.serialize_with_payload(&mut buf, &payload));
+    assert_eq!(28, buf.len());
+    assert_eq!(
+        &b"\x65\0\x04\0\x03\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\x01\0\0\0"[..],
+        buf
+    );
+
+    let mut received = Encapsulation::default();
+    assert_eq!(Ok(()), received.deserialize(&mut &buf[..]));
+    assert_eq!(payload.len(), received.len as usize);
+}
+
+#[test]
+fn validate_len() {
+    let mut enc = Encapsulation::default();
+    let mut buf = &b"\x6f\0\x04\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\x01\x02\x03\x04"[..];
+    assert_eq!(Ok(()), enc.deserialize(&mut buf));
+    assert_eq!(Ok(()), enc.validate_len(buf.remaining()));
+    assert_eq!(
+        Err(MESSAGE_FORMAT_ERROR),
+        enc.validate_len(buf.remaining() - 1)
+    );
+    assert_eq!(
+        Err(MESSAGE_FORMAT_ERROR),
+        enc.validate_len(buf.remaining() + 1)
+    );
+}
+
+#[test]
+fn register_session() {
+    use crate::error_code::UNSUPPORTED_VERSION;
+
+    let mut buf = BytesMut::with_capacity(4);
+    assert_eq!(Ok(()), register_session_request(&mut buf));
+    assert_eq!(&b"\x01\0\0\0"[..], buf);
+
+    let mut enc = Encapsulation::default();
+    let mut data = &b"\x65\0\x04\0\x78\x56\x34\x12\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0"[..];
+    assert_eq!(Ok(()), enc.deserialize(&mut data));
+    assert_eq!(Ok(0x12345678), parse_register_session_reply(&enc));
+
+    enc.status = 0x69;
+    assert_eq!(Err(UNSUPPORTED_VERSION), parse_register_session_reply(&enc));
+
+    enc.status = 0;
+    enc.session = 0;
+    assert_eq!(Err(INVALID_SESSION), parse_register_session_reply(&enc));
+
+    enc.command = Encapsulation::LIST_IDENTITY;
+    assert_eq!(Err(UNSUPPORTED_COMMAND), parse_register_session_reply(&enc));
+}
+
+#[test]
+fn command_display() {
+    assert_eq!("NOP", Encapsulation::NOP.to_string());
+    assert_eq!("ListServices", Encapsulation::LIST_SERVICES.to_string());
+    assert_eq!("ListIdentity", Encapsulation::LIST_IDENTITY.to_string());
+    assert_eq!("ListInterfaces", Encapsulation::LIST_INTERFACES.to_string());
+    assert_eq!(
+        "RegisterSession",
+        Encapsulation::REGISTER_SESSION.to_string()
+    );
+    assert_eq!(
+        "UnregisterSession",
+        Encapsulation::UNREGISTER_SESSION.to_string()
+    );
+    assert_eq!("SendRRData", Encapsulation::SEND_RR_DATA.to_string());
+    assert_eq!("SendUnitData", Encapsulation::SEND_UNIT_DATA.to_string());
+    assert_eq!("Unknown(0x1234)", Command(0x1234).to_string());
+    assert_eq!("Unknown", Command(0x1234).name());
+}
+
+#[test]
+fn validate() {
+    let mut enc = Encapsulation::default();
+    let mut buf = &b"\x6f\0\0\0\x01\0\0\0\0\0\0\0\x01\x02\x03\x04\x05\x06\x07\x08\0\0\0\0"[..];
+    assert_eq!(Ok(()), enc.deserialize(&mut buf));
+    assert_eq!(Ok(()), enc.validate());
+    assert!(enc.command.is_known());
+
+    enc.options = 1;
+    assert_eq!(Err(MESSAGE_FORMAT_ERROR), enc.validate());
+    enc.command = Command(0x1234); // vendor specific, options are not checked
+    assert!(!enc.command.is_known());
+    assert_eq!(Ok(()), enc.validate());
+
+    enc.status = 1;
+    assert_eq!(Err(MESSAGE_FORMAT_ERROR), enc.validate());
+}
+
+#[test]
+fn reply() {
+    use crate::error_code::SUCCESS;
+
+    let request = Encapsulation {
+        command: Encapsulation::SEND_RR_DATA,
+        len: 10,
+        session: 3,
+        context: *b"_pycomm_",
+        ..Default::default()
+    };
+    assert!(!request.is_reply());
+
+    let reply = request.to_reply(SUCCESS);
+    assert!(!reply.is_reply()); // by the header alone
+    assert!(reply.is_reply_to(&request));
+    assert_eq!(0, reply.len);
+
+    let reply = request.to_reply(INVALID_SESSION);
+    assert!(reply.is_reply());
+    assert_eq!(0x64, reply.status);
+    assert!(reply.is_reply_to(&request));
+
+    let mut other = request.to_reply(SUCCESS);
+    other.context[0] = 0;
+    assert!(!other.is_reply_to(&request));
+
+    // the session is assigned by the reply to RegisterSession
+    let register = Encapsulation {
+        command: Encapsulation::REGISTER_SESSION,
+        ..Default::default()
+    };
+    let mut reply = register.to_reply(SUCCESS);
+    reply.session = 1;
+    assert!(reply.is_reply_to(&register));
+    assert!(!reply.is_reply_to(&request));
+}
+
 #[test]
 fn auto_traits() {
     use crate::eip::check_auto_traits;
@@ -155,3 +517,10 @@ fn auto_traits() {
     check_auto_traits::<Command>();
     check_auto_traits::<Encapsulation>();
 }
+
+#[test]
+fn hash_key() {
+    use crate::eip::check_hash_key;
+
+    check_hash_key(Encapsulation::SEND_RR_DATA);
+}
@@ -1,4 +1,11 @@
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg(test)]
+use alloc::boxed::Box;
+#[cfg(test)]
+use alloc::string::ToString;
+use core::fmt;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ErrorCode(u8);
 
 pub const SUCCESS: ErrorCode = ErrorCode(0x00);
@@ -7,6 +14,8 @@ pub const INSUFFICIENT_MEMORY: ErrorCode = ErrorCode(0x02);
 pub const INCORRECT_DATA: ErrorCode = ErrorCode(0x03);
 pub const PATH_SEGMENT_ERROR: ErrorCode = ErrorCode(0x04);
 pub const PATH_DESTINATION_UNKNOWN: ErrorCode = ErrorCode(0x05);
+pub const SERVICE_NOT_SUPPORTED: ErrorCode = ErrorCode(0x08);
+pub const ATTRIBUTE_LIST_ERROR: ErrorCode = ErrorCode(0x0a);
 pub const ATTRIBUTE_NOT_SETTABLE: ErrorCode = ErrorCode(0x0e);
 pub const REPLY_DATA_TOO_LARGE: ErrorCode = ErrorCode(0x11);
 pub const NOT_ENOUGH_DATA: ErrorCode = ErrorCode(0x13);
@@ -37,9 +46,136 @@ impl From<ErrorCode> for u32 {
     }
 }
 
+impl ErrorCode {
+    /// Test if this is the success status
+    ///
+    /// # Returns
+    ///
+    /// True if SUCCESS, false otherwise.
+    ///
+    pub fn is_success(&self) -> bool {
+        *self == SUCCESS
+    }
+
+    /// Get the symbolic name of the status
+    ///
+    /// # Returns
+    ///
+    /// * The name of the constant, "UNKNOWN" if the status is not known
+    ///
+    pub fn name(&self) -> &'static str {
+        match *self {
+            SUCCESS => "SUCCESS",
+            UNSUPPORTED_COMMAND => "UNSUPPORTED_COMMAND",
+            INSUFFICIENT_MEMORY => "INSUFFICIENT_MEMORY",
+            INCORRECT_DATA => "INCORRECT_DATA",
+            PATH_SEGMENT_ERROR => "PATH_SEGMENT_ERROR",
+            PATH_DESTINATION_UNKNOWN => "PATH_DESTINATION_UNKNOWN",
+            SERVICE_NOT_SUPPORTED => "SERVICE_NOT_SUPPORTED",
+            ATTRIBUTE_LIST_ERROR => "ATTRIBUTE_LIST_ERROR",
+            ATTRIBUTE_NOT_SETTABLE => "ATTRIBUTE_NOT_SETTABLE",
+            REPLY_DATA_TOO_LARGE => "REPLY_DATA_TOO_LARGE",
+            NOT_ENOUGH_DATA => "NOT_ENOUGH_DATA",
+            ATTRIBUTE_NOT_SUPPORTED => "ATTRIBUTE_NOT_SUPPORTED",
+            TOO_MUCH_DATA => "TOO_MUCH_DATA",
+            OBJECT_DOES_NOT_EXIST => "OBJECT_DOES_NOT_EXIST",
+            INVALID_PARAMETER => "INVALID_PARAMETER",
+            MESSAGE_FORMAT_ERROR => "MESSAGE_FORMAT_ERROR",
+            ATTRIBUTE_NOT_GETTABLE => "ATTRIBUTE_NOT_GETTABLE",
+            INVALID_SESSION => "INVALID_SESSION",
+            UNSUPPORTED_VERSION => "UNSUPPORTED_VERSION",
+            _ => "UNKNOWN",
+        }
+    }
+
+    /// Get the description of the status
+    ///
+    /// # Returns
+    ///
+    /// * The description if the status is known
+    ///
+    fn description(&self) -> Option<&'static str> {
+        match *self {
+            SUCCESS => Some("Success"),
+            UNSUPPORTED_COMMAND => Some("Unsupported command"),
+            INSUFFICIENT_MEMORY => Some("Insufficient memory"),
+            INCORRECT_DATA => Some("Incorrect data"),
+            PATH_SEGMENT_ERROR => Some("Path segment error"),
+            PATH_DESTINATION_UNKNOWN => Some("Path destination unknown"),
+            SERVICE_NOT_SUPPORTED => Some("Service not supported"),
+            ATTRIBUTE_LIST_ERROR => Some("Attribute list error"),
+            ATTRIBUTE_NOT_SETTABLE => Some("Attribute not settable"),
+            REPLY_DATA_TOO_LARGE => Some("Reply data too large"),
+            NOT_ENOUGH_DATA => Some("Not enough data"),
+            ATTRIBUTE_NOT_SUPPORTED => Some("Attribute not supported"),
+            TOO_MUCH_DATA => Some("Too much data"),
+            OBJECT_DOES_NOT_EXIST => Some("Object does not exist"),
+            INVALID_PARAMETER => Some("Invalid parameter"),
+            MESSAGE_FORMAT_ERROR => Some("Message format error"),
+            ATTRIBUTE_NOT_GETTABLE => Some("Attribute not gettable"),
+            INVALID_SESSION => Some("Invalid session handle"),
+            UNSUPPORTED_VERSION => Some("Unsupported protocol version"),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.description() {
+            Some(d) => f.write_str(d),
+            None => write!(f, "CIP error {:#04x}", self.0),
+        }
+    }
+}
+
+impl core::error::Error for ErrorCode {}
+
+#[test]
+fn display() {
+    assert_eq!("Success", SUCCESS.to_string());
+    assert_eq!("Path segment error", PATH_SEGMENT_ERROR.to_string());
+    assert_eq!("Not enough data", NOT_ENOUGH_DATA.to_string());
+    assert_eq!("Attribute not settable", ATTRIBUTE_NOT_SETTABLE.to_string());
+    assert_eq!("Attribute list error", ATTRIBUTE_LIST_ERROR.to_string());
+    assert_eq!("CIP error 0xfe", ErrorCode(0xfe).to_string());
+}
+
+#[test]
+fn name() {
+    assert!(SUCCESS.is_success());
+    assert_eq!("SUCCESS", SUCCESS.name());
+    assert!(!NOT_ENOUGH_DATA.is_success());
+    assert_eq!("NOT_ENOUGH_DATA", NOT_ENOUGH_DATA.name());
+    assert!(!INVALID_SESSION.is_success());
+    assert_eq!("INVALID_SESSION", INVALID_SESSION.name());
+    assert_eq!("ATTRIBUTE_LIST_ERROR", ATTRIBUTE_LIST_ERROR.name());
+    assert!(!ErrorCode(0xfe).is_success());
+    assert_eq!("UNKNOWN", ErrorCode(0xfe).name());
+}
+
+#[test]
+fn error() {
+    fn fails() -> Result<(), Box<dyn std::error::Error>> {
+        Err(NOT_ENOUGH_DATA)?;
+        Ok(())
+    }
+
+    let e = fails().unwrap_err();
+    assert_eq!("Not enough data", e.to_string());
+    assert_eq!(Some(&NOT_ENOUGH_DATA), e.downcast_ref::<ErrorCode>());
+}
+
 #[test]
 fn auto_traits() {
     use crate::eip::check_auto_traits;
 
     check_auto_traits::<ErrorCode>();
 }
+
+#[test]
+fn hash_key() {
+    use crate::eip::check_hash_key;
+
+    check_hash_key(NOT_ENOUGH_DATA);
+}
@@ -0,0 +1,124 @@
+use crate::eip::Serializing;
+use crate::encapsulation::Encapsulation;
+use bytes::BytesMut;
+
+/// Reassembles encapsulation frames from a byte stream, e.g. TCP.
+/// A frame may arrive in several reads and one read may hold several frames.
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FrameReader {
+    buf: BytesMut, // The received bytes not yet returned as a frame
+}
+
+impl FrameReader {
+    /// Create an instance
+    ///
+    /// # Returns
+    ///
+    /// * The created instance
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add received bytes
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The bytes of one read
+    ///
+    pub fn push(&mut self, data: &[u8]) {
+        self.buf.extend_from_slice(data);
+    }
+
+    /// Take the next complete frame
+    ///
+    /// # Returns
+    ///
+    /// * The frame, the header followed by the data. None if more data is needed
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use eip_protocol::frame_reader::FrameReader;
+    ///
+    /// let frame = b"\x65\0\x04\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\x01\0\0\0";
+    /// let mut reader = FrameReader::new();
+    ///
+    /// reader.push(&frame[..26]);
+    /// assert_eq!(None, reader.next_frame());
+    /// reader.push(&frame[26..]);
+    /// assert_eq!(Some(&frame[..]), reader.next_frame().as_deref());
+    /// ```
+    pub fn next_frame(&mut self) -> Option<BytesMut> {
+        let mut enc = Encapsulation::default();
+        enc.deserialize(&mut &self.buf[..]).ok()?;
+        let size = enc.serial_size() + enc.len as usize;
+        if self.buf.len() < size {
+            return None;
+        }
+        Some(self.buf.split_to(size))
+    }
+
+    /// Get the number of buffered bytes
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes of the incomplete frames.
+    ///
+    pub fn len(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// Test if there are no buffered bytes
+    ///
+    /// # Returns
+    ///
+    /// True if there are no bytes, false otherwise.
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+}
+
+#[cfg(test)]
+const REGISTER_SESSION: &[u8] = b"\x65\0\x04\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\x01\0\0\0";
+
+#[test]
+fn split_reads() {
+    let mut reader = FrameReader::new();
+    assert_eq!(None, reader.next_frame());
+
+    reader.push(&REGISTER_SESSION[..10]);
+    assert_eq!(None, reader.next_frame());
+    reader.push(&REGISTER_SESSION[10..24]); // header complete
+    assert_eq!(None, reader.next_frame());
+    reader.push(&REGISTER_SESSION[24..]);
+
+    assert_eq!(Some(REGISTER_SESSION), reader.next_frame().as_deref());
+    assert_eq!(None, reader.next_frame());
+    assert!(reader.is_empty());
+}
+
+#[test]
+fn joined_reads() {
+    let mut reader = FrameReader::new();
+    let mut data = REGISTER_SESSION.to_vec();
+    data.extend_from_slice(REGISTER_SESSION);
+    reader.push(&data[..40]);
+
+    assert_eq!(Some(REGISTER_SESSION), reader.next_frame().as_deref());
+    assert_eq!(None, reader.next_frame());
+    assert_eq!(12, reader.len());
+
+    reader.push(&data[40..]);
+    assert_eq!(Some(REGISTER_SESSION), reader.next_frame().as_deref());
+    assert!(reader.is_empty());
+}
+
+#[test]
+fn auto_traits() {
+    use crate::eip::check_auto_traits;
+
+    check_auto_traits::<FrameReader>();
+}
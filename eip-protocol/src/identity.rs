@@ -1,17 +1,131 @@
 use crate::attr::{AccessCode, Duint, ShortString, Uint, Usint};
-use crate::eip::{EipResult, Serializing};
+use crate::eip::{self, EipResult, Serializing};
 use crate::encapsulation;
-use crate::error_code::ATTRIBUTE_NOT_SUPPORTED;
+use crate::encapsulation::Encapsulation;
+use crate::error_code::{
+    ATTRIBUTE_NOT_GETTABLE, ATTRIBUTE_NOT_SUPPORTED, INVALID_PARAMETER, PATH_DESTINATION_UNKNOWN,
+    PATH_SEGMENT_ERROR, SERVICE_NOT_SUPPORTED, SUCCESS, TOO_MUCH_DATA, UNSUPPORTED_COMMAND,
+};
 use crate::item::Item;
+use crate::message_router::{Object, Request};
 use crate::socket_address::SocketAddress;
+use crate::static_attr::StaticAttr;
+use alloc::string::String;
+#[cfg(test)]
+use alloc::string::ToString;
 use bytes::{Buf, BufMut, BytesMut};
 
 /// This object provides identification of and general information about the device.
 
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Attr(u16);
 
+impl From<u16> for Attr {
+    fn from(val: u16) -> Self {
+        Self(val)
+    }
+}
+
+/// The revision of the item the Identity object represents, major followed by minor on the wire
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Revision {
+    pub major: u8,
+    pub minor: u8,
+}
+
+impl Revision {
+    /// Create an instance
+    ///
+    /// # Arguments
+    ///
+    /// * `major` - The major revision
+    /// * `minor` - The minor revision
+    ///
+    /// # Returns
+    ///
+    /// * The created instance
+    ///
+    pub const fn new(major: u8, minor: u8) -> Self {
+        Revision { major, minor }
+    }
+}
+
+impl From<u16> for Revision {
+    fn from(val: u16) -> Self {
+        Self::new(val as u8, (val >> 8) as u8)
+    }
+}
+
+impl From<Revision> for u16 {
+    fn from(val: Revision) -> Self {
+        val.major as u16 | (val.minor as u16) << 8
+    }
+}
+
+/// The state of the device as represented by the state transition diagram
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct State(u8);
+
+impl State {
+    pub const NONEXISTENT: State = State(0);
+    pub const SELF_TESTING: State = State(1);
+    pub const STANDBY: State = State(2);
+    pub const OPERATIONAL: State = State(3);
+    pub const MAJOR_RECOVERABLE_FAULT: State = State(4);
+    pub const MAJOR_UNRECOVERABLE_FAULT: State = State(5);
+
+    /// Check if the state transition diagram allows a transition.
+    /// Remaining in the same state and losing power are always allowed.
+    ///
+    /// # Arguments
+    ///
+    /// * `to` - The next state
+    ///
+    /// # Returns
+    ///
+    /// * True if the transition is allowed
+    ///
+    pub fn can_change_to(&self, to: State) -> bool {
+        if *self == to || to == Self::NONEXISTENT {
+            return true;
+        }
+        match *self {
+            Self::NONEXISTENT => to == Self::SELF_TESTING,
+            Self::SELF_TESTING | Self::STANDBY | Self::OPERATIONAL => matches!(
+                to,
+                Self::SELF_TESTING
+                    | Self::STANDBY
+                    | Self::OPERATIONAL
+                    | Self::MAJOR_RECOVERABLE_FAULT
+                    | Self::MAJOR_UNRECOVERABLE_FAULT
+            ),
+            Self::MAJOR_RECOVERABLE_FAULT => {
+                matches!(to, Self::SELF_TESTING | Self::MAJOR_UNRECOVERABLE_FAULT)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl From<u8> for State {
+    fn from(val: u8) -> Self {
+        Self(val)
+    }
+}
+
+impl From<State> for u8 {
+    fn from(val: State) -> Self {
+        val.0
+    }
+}
+
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Identity {
     pub vendor_id: Uint,      // Identification of each vendor by number
     pub device_type: Uint,    // Indication of general type of product
@@ -24,9 +138,24 @@ pub struct Identity {
     pub configuration_consistency_value: Uint, // Contents identify configuration of device
     pub heartbeat_interval: Usint, // The nominal interval between heartbeat messages in seconds
     pub socket_address: SocketAddress,
+    pub class_attr: StaticAttr, // The class level attributes, served at instance 0
 }
 
 impl Identity {
+    pub const CLASS: u32 = 0x01;
+
+    pub const RESET_POWER_CYCLE: u8 = 0;
+    pub const RESET_FACTORY_DEFAULTS: u8 = 1;
+
+    // Bits of the status attribute
+    pub const STATUS_OWNED: u16 = 0x0001;
+    pub const STATUS_CONFIGURED: u16 = 0x0004;
+    pub const STATUS_EXTENDED: u16 = 0x00f0;
+    pub const STATUS_MINOR_RECOVERABLE_FAULT: u16 = 0x0100;
+    pub const STATUS_MINOR_UNRECOVERABLE_FAULT: u16 = 0x0200;
+    pub const STATUS_MAJOR_RECOVERABLE_FAULT: u16 = 0x0400;
+    pub const STATUS_MAJOR_UNRECOVERABLE_FAULT: u16 = 0x0800;
+
     pub const VENDOR_ID: Attr = Attr(1);
     pub const DEVICE_TYPE: Attr = Attr(2);
     pub const PRODUCT_CODE: Attr = Attr(3);
@@ -59,10 +188,10 @@ impl Identity {
     ///
     /// ```rust
     /// use bytes::BytesMut;
-    /// use eip_protocol::identity::Identity;
+    /// use eip_protocol::identity::{Identity, Revision};
     ///
     /// let name: String = str::to_string("Hello");
-    /// let identity = Identity::new(1, 2, 3, 4, 5, name);
+    /// let identity = Identity::new(1, 2, 3, Revision::new(4, 0), 5, name);
     /// let mut buf = BytesMut::with_capacity(10);
     ///
     /// identity.serialize_attribute_single(&mut buf, Identity::SERIAL_NUMBER);
@@ -74,7 +203,7 @@ impl Identity {
         vendor_id: u16,
         device_type: u16,
         product_code: u16,
-        revision: u16,
+        revision: Revision,
         serial_number: u32,
         product_name: String,
     ) -> Self {
@@ -83,7 +212,7 @@ impl Identity {
             vendor_id: Uint::new(vendor_id, gettable.clone()),
             device_type: Uint::new(device_type, gettable.clone()),
             product_code: Uint::new(product_code, gettable.clone()),
-            revision: Uint::new(revision, gettable.clone()),
+            revision: Uint::new(revision.into(), gettable.clone()),
             status: Uint::default(),
             serial_number: Duint::new(serial_number, gettable.clone()),
             product_name: ShortString::with_capacity(product_name, gettable.clone(), 32),
@@ -91,6 +220,7 @@ impl Identity {
             configuration_consistency_value: Uint::default(),
             heartbeat_interval: Usint::default(),
             socket_address: SocketAddress::default(),
+            class_attr: StaticAttr::new(1, 1, 1),
         }
     }
 
@@ -124,6 +254,57 @@ impl Identity {
         Ok(())
     }
 
+    /// Get the serialized size of one specific attribute in Bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `attr` - The attribute identifier number
+    ///
+    /// # Returns
+    ///
+    /// * The number of bytes when serialized, 0 if the attribute is non existent
+    ///
+    fn attribute_size(&self, attr: Attr) -> usize {
+        match attr {
+            Self::VENDOR_ID => self.vendor_id.serial_size(),
+            Self::DEVICE_TYPE => self.device_type.serial_size(),
+            Self::PRODUCT_CODE => self.product_code.serial_size(),
+            Self::REVISION => self.revision.serial_size(),
+            Self::STATUS => self.status.serial_size(),
+            Self::SERIAL_NUMBER => self.serial_number.serial_size(),
+            Self::PRODUCT_NAME => self.product_name.serial_size(),
+            Self::STATE => self.state.serial_size(),
+            Self::CONFIGURATION_CONSISTENCY_VALUE => {
+                self.configuration_consistency_value.serial_size()
+            }
+            Self::HEARTBEAT_INTERVAL => self.heartbeat_interval.serial_size(),
+            _ => 0,
+        }
+    }
+
+    /// Serialize the reply data of the Get_Attributes_All service,
+    /// all attributes in identifier order. A non getable attribute is zero filled,
+    /// the product name as an empty string to keep the following attributes in place.
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to write to
+    ///
+    /// # Errors
+    ///
+    /// If there is not enough room an error variant will be returned.
+    ///
+    pub fn serialize_all_attributes(&self, buf: &mut BytesMut) -> EipResult {
+        for n in 1..Self::ATTRIBUTE_END.0 {
+            match self.serialize_attribute_single(buf, Attr(n)) {
+                Err(ATTRIBUTE_NOT_GETTABLE) if Attr(n) == Self::PRODUCT_NAME => buf.put_u8(0),
+                Err(ATTRIBUTE_NOT_GETTABLE) => buf.put_bytes(0, self.attribute_size(Attr(n))),
+                r => r?,
+            }
+        }
+        Ok(())
+    }
+
     /// Deserialize one specific attribute
     ///
     /// # Arguments
@@ -134,6 +315,7 @@ impl Identity {
     /// # Errors
     ///
     /// If the attribute is non existent or is not set-able, an error variant will be returned.
+    /// A state the transition diagram does not allow is rejected as by set_state().
     ///
     pub fn deserialize_attribute_single(&mut self, buf: &mut dyn Buf, attr: Attr) -> EipResult {
         match attr {
@@ -144,7 +326,14 @@ impl Identity {
             Self::STATUS => self.status.deserialize(buf)?,
             Self::SERIAL_NUMBER => self.serial_number.deserialize(buf)?,
             Self::PRODUCT_NAME => self.product_name.deserialize(buf)?,
-            Self::STATE => self.state.deserialize(buf)?,
+            Self::STATE => eip::deserialize_atomic(&mut self.state, buf, |state, buf| {
+                let from = State(state.get());
+                state.deserialize(buf)?;
+                if !from.can_change_to(State(state.get())) {
+                    return Err(INVALID_PARAMETER);
+                }
+                Ok(())
+            })?,
             Self::CONFIGURATION_CONSISTENCY_VALUE => {
                 self.configuration_consistency_value.deserialize(buf)?
             }
@@ -154,6 +343,206 @@ impl Identity {
         Ok(())
     }
 
+    /// Get the revision
+    ///
+    /// # Returns
+    ///
+    /// * The major and minor revision
+    ///
+    pub fn revision(&self) -> Revision {
+        self.revision.get().into()
+    }
+
+    /// Set or clear bits of the status attribute
+    ///
+    /// # Arguments
+    ///
+    /// * `mask` - The status bits, the values can be or-ed |
+    /// * `on` - True to set, false to clear
+    ///
+    pub fn set_status_bits(&mut self, mask: u16, on: bool) {
+        let status = self.status.get();
+        self.status
+            .set(if on { status | mask } else { status & !mask });
+    }
+
+    /// Check if the device has an owner, e.g. an open I/O connection
+    ///
+    /// # Returns
+    ///
+    /// * True if owned
+    ///
+    pub fn is_owned(&self) -> bool {
+        self.status.get() & Self::STATUS_OWNED != 0
+    }
+
+    /// Set or clear the owned bit of the status attribute
+    ///
+    /// # Arguments
+    ///
+    /// * `owned` - True if the device has an owner
+    ///
+    pub fn set_owned(&mut self, owned: bool) {
+        self.set_status_bits(Self::STATUS_OWNED, owned);
+    }
+
+    /// Check if the device is configured differently than the out-of-box default
+    ///
+    /// # Returns
+    ///
+    /// * True if configured
+    ///
+    pub fn is_configured(&self) -> bool {
+        self.status.get() & Self::STATUS_CONFIGURED != 0
+    }
+
+    /// Set or clear the configured bit of the status attribute
+    ///
+    /// # Arguments
+    ///
+    /// * `configured` - True if the configuration differs from the default
+    ///
+    pub fn set_configured(&mut self, configured: bool) {
+        self.set_status_bits(Self::STATUS_CONFIGURED, configured);
+    }
+
+    /// Get the extended device status
+    ///
+    /// # Returns
+    ///
+    /// * The 4 bit extended device status
+    ///
+    pub fn extended_status(&self) -> u8 {
+        ((self.status.get() & Self::STATUS_EXTENDED) >> 4) as u8
+    }
+
+    /// Set the extended device status
+    ///
+    /// # Arguments
+    ///
+    /// * `extended` - The 4 bit extended device status
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if the value does not fit in 4 bits.
+    ///
+    pub fn set_extended_status(&mut self, extended: u8) -> EipResult {
+        if extended > 0x0f {
+            return Err(INVALID_PARAMETER);
+        }
+        let status = self.status.get() & !Self::STATUS_EXTENDED;
+        self.status.set(status | ((extended as u16) << 4));
+        Ok(())
+    }
+
+    /// Get the present state of the device
+    ///
+    /// # Returns
+    ///
+    /// * The state
+    ///
+    pub fn state(&self) -> State {
+        State(self.state.get())
+    }
+
+    /// Change the state of the device following the state transition diagram
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - The next state
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if the transition is not allowed.
+    ///
+    pub fn set_state(&mut self, state: State) -> EipResult {
+        if !self.state().can_change_to(state) {
+            return Err(INVALID_PARAMETER);
+        }
+        self.state.set(state.into());
+        Ok(())
+    }
+
+    /// Get the nominal interval between heartbeat messages
+    ///
+    /// # Returns
+    ///
+    /// * The interval in seconds, None if the heartbeat is disabled (0)
+    ///
+    pub fn heartbeat(&self) -> Option<u8> {
+        match self.heartbeat_interval.get() {
+            0 => None,
+            seconds => Some(seconds),
+        }
+    }
+
+    /// Reset the device, emulating a power cycle or returning to the factory defaults.
+    /// The state attributes are returned to their initial values.
+    ///
+    /// # Arguments
+    ///
+    /// * `reset_type` - RESET_POWER_CYCLE or RESET_FACTORY_DEFAULTS
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if the reset type is unknown.
+    ///
+    pub fn reset(&mut self, reset_type: u8) -> EipResult {
+        match reset_type {
+            Self::RESET_POWER_CYCLE => (),
+            Self::RESET_FACTORY_DEFAULTS => {
+                self.configuration_consistency_value.set(0);
+                self.heartbeat_interval.set(0);
+            }
+            _ => return Err(INVALID_PARAMETER),
+        }
+        self.status.set(0);
+        self.state.set(0);
+        Ok(())
+    }
+
+    /// Handle a request addressed to the Identity object
+    ///
+    /// # Arguments
+    ///
+    /// * `req` - The request with the path
+    /// * `data` - The request data following the path
+    /// * `buf` - The message buffer to write the reply data to
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if the path, the service or the data is invalid.
+    ///
+    pub fn handle_request(
+        &mut self,
+        req: &Request,
+        data: &mut dyn Buf,
+        buf: &mut BytesMut,
+    ) -> EipResult {
+        if req.class != Some(Self::CLASS) {
+            return Err(PATH_DESTINATION_UNKNOWN);
+        }
+        let attr = || match req.attribute.map(u16::try_from) {
+            Some(Ok(a)) => Ok(Attr(a)),
+            Some(Err(_)) => Err(ATTRIBUTE_NOT_SUPPORTED),
+            None => Err(PATH_SEGMENT_ERROR),
+        };
+        match req.service {
+            Request::GET_ATTRIBUTE_ALL => self.serialize_all_attributes(buf),
+            Request::GET_ATTRIBUTE_SINGLE => self.serialize_attribute_single(buf, attr()?),
+            Request::SET_ATTRIBUTE_SINGLE => self.deserialize_attribute_single(data, attr()?),
+            Request::RESET => {
+                let reset_type = match data.remaining() {
+                    0 => Self::RESET_POWER_CYCLE,
+                    1 => data.get_u8(),
+                    _ => return Err(TOO_MUCH_DATA),
+                };
+                self.reset(reset_type)
+            }
+            _ => Err(SERVICE_NOT_SUPPORTED),
+        }
+    }
+
     /// List the mandatory attributes
     /// State is the last mandatory attribute.
     ///
@@ -165,10 +554,10 @@ impl Identity {
     ///
     /// ```rust
     /// use bytes::BytesMut;
-    /// use eip_protocol::identity::Identity;
+    /// use eip_protocol::identity::{Identity, Revision};
     ///
     /// let name: String = str::to_string("Hello");
-    /// let id = Identity::new(1, 2, 3, 4, 5, name);
+    /// let id = Identity::new(1, 2, 3, Revision::new(4, 0), 5, name);
     /// let mut buf = BytesMut::with_capacity(100);
     ///
     /// assert_eq!(
@@ -186,21 +575,60 @@ impl Identity {
     /// an error variant will be returned.
     ///
     pub fn list(&self, buf: &mut BytesMut) -> EipResult {
-        let mut item = Item::new(Item::IDENTITY, 0);
-        let mut rest = item.split_off(buf)?;
+        Item::new(Item::IDENTITY, 0).serialize_with_body(buf, |rest| {
+            rest.put_u16_le(encapsulation::VERSION);
 
-        rest.put_u16_le(encapsulation::VERSION);
+            self.socket_address.serialize(rest)?;
 
-        self.socket_address.serialize(&mut rest)?;
+            for n in 1..Self::STATE.0 + 1 {
+                self.serialize_attribute_single(rest, Attr(n))?;
+            }
+            Ok(())
+        })
+    }
 
-        for n in 1..Self::STATE.0 + 1 {
-            self.serialize_attribute_single(&mut rest, Attr(n))?;
+    /// Reply to a ListIdentity request, typically received as UDP broadcast.
+    /// The complete frame is written, the encapsulation header echoes the sender context.
+    ///
+    /// # Arguments
+    ///
+    /// * `req` - The encapsulation header of the request
+    /// * `buf` - The message buffer to write to
+    ///
+    /// # Errors
+    ///
+    /// If the request is not ListIdentity or there is not enough room an error variant will be
+    /// returned.
+    ///
+    pub fn reply_list_identity(&self, req: &Encapsulation, buf: &mut BytesMut) -> EipResult {
+        if req.command != Encapsulation::LIST_IDENTITY {
+            return Err(UNSUPPORTED_COMMAND);
         }
+        let mut payload = BytesMut::with_capacity(64);
+        payload.put_u16_le(1); // item_count only one item
+        let mut item = BytesMut::with_capacity(64);
+        self.list(&mut item)?;
+        payload.put_slice(&item);
 
-        item.len = rest.len();
-        item.serialize(buf)?;
-        buf.unsplit(rest);
-        Ok(())
+        req.to_reply(SUCCESS).serialize_with_payload(buf, &payload)
+    }
+}
+
+impl Object for Identity {
+    fn get_attribute(&self, buf: &mut BytesMut, attr: u16) -> EipResult {
+        self.serialize_attribute_single(buf, attr.into())
+    }
+
+    fn set_attribute(&mut self, buf: &mut dyn Buf, attr: u16) -> EipResult {
+        self.deserialize_attribute_single(buf, attr.into())
+    }
+
+    fn service(&mut self, req: &Request, data: &mut dyn Buf, buf: &mut BytesMut) -> EipResult {
+        self.handle_request(req, data, buf)
+    }
+
+    fn class_attributes(&self) -> Option<&StaticAttr> {
+        Some(&self.class_attr)
     }
 }
 
@@ -214,12 +642,15 @@ impl Serializing for Identity {
     /// # Errors
     ///
     /// If one of the attributes is non existent or is not set-able, an error variant will be returned.
+    /// All attributes keep their prior value then and no input is consumed.
     ///
     fn deserialize(&mut self, buf: &mut dyn Buf) -> EipResult {
-        for n in 1..Self::ATTRIBUTE_END.0 {
-            self.deserialize_attribute_single(buf, Attr(n))?;
-        }
-        Ok(())
+        eip::deserialize_atomic(self, buf, |received, buf| {
+            for n in 1..Self::ATTRIBUTE_END.0 {
+                received.deserialize_attribute_single(buf, Attr(n))?;
+            }
+            Ok(())
+        })
     }
 
     /// Serialize all attributes
@@ -239,6 +670,25 @@ impl Serializing for Identity {
         }
         Ok(())
     }
+
+    /// Get the serialized size in Bytes.
+    ///
+    /// # Returns
+    ///
+    /// * The number of bytes when serialized
+    ///
+    fn serial_size(&self) -> usize {
+        self.vendor_id.serial_size()
+            + self.device_type.serial_size()
+            + self.product_code.serial_size()
+            + self.revision.serial_size()
+            + self.status.serial_size()
+            + self.serial_number.serial_size()
+            + self.product_name.serial_size()
+            + self.state.serial_size()
+            + self.configuration_consistency_value.serial_size()
+            + self.heartbeat_interval.serial_size()
+    }
 }
 
 #[test]
@@ -256,6 +706,35 @@ fn deserialize() {
     assert_eq!(5, id.serial_number.get());
 }
 
+#[test]
+fn deserialize_keeps_prior_value() {
+    use crate::error_code::NOT_ENOUGH_DATA;
+
+    let mut id = Identity::default();
+    id.vendor_id.set(1);
+    id.heartbeat_interval.set(9);
+    let _ = id.vendor_id.take_dirty();
+    let prior = id.clone();
+
+    // all attributes but the last, the heartbeat interval
+    let mut buf = &b"\x0a\0\x0b\0\x0c\0\x0d\0\x0e\0\x0f\0\0\0\x05World\x01\x10\0"[..];
+    assert_eq!(Err(NOT_ENOUGH_DATA), id.deserialize(&mut buf));
+    assert_eq!(23, buf.len());
+    assert_eq!(prior, id);
+    assert!(!id.vendor_id.take_dirty());
+
+    // split over two chunks
+    let mut chain = buf.chain(&b""[..]);
+    assert_eq!(Err(NOT_ENOUGH_DATA), id.deserialize(&mut chain));
+    assert_eq!(23, chain.remaining());
+    assert_eq!(prior, id);
+
+    let mut chain = buf.chain(&b"\x11!"[..]);
+    assert_eq!(Ok(()), id.deserialize(&mut chain));
+    assert_eq!(1, chain.remaining());
+    assert_eq!(Some(0x11), id.heartbeat());
+}
+
 #[test]
 fn deserialize_single() {
     let mut id = Identity::default();
@@ -271,20 +750,29 @@ fn deserialize_single() {
 #[test]
 fn serialize() {
     let name: String = str::to_string("Hello");
-    let id = Identity::new(1, 2, 3, 4, 5, name);
+    let id = Identity::new(1, 2, 3, Revision::new(4, 0), 5, name);
     let mut buf = BytesMut::with_capacity(100);
     assert_eq!(Ok(()), id.serialize(&mut buf));
     assert_eq!(24, buf.len());
+    assert_eq!(24, id.serial_size());
     assert_eq!(
         &b"\x01\0\x02\0\x03\0\x04\0\0\0\x05\0\0\0\x05Hello\0\0\0\0"[..],
         buf
     );
 }
 
+#[test]
+fn to_bytes() {
+    let id = Identity::new(1, 2, 3, Revision::new(4, 0), 5, str::to_string("Hello"));
+    let mut buf = BytesMut::with_capacity(100);
+    assert_eq!(Ok(()), id.serialize(&mut buf));
+    assert_eq!(Ok(buf), id.to_bytes());
+}
+
 #[test]
 fn serialize_single() {
     let name: String = str::to_string("Hello");
-    let id = Identity::new(1, 2, 3, 4, 5, name);
+    let id = Identity::new(1, 2, 3, Revision::new(4, 0), 5, name);
     let mut buf = BytesMut::with_capacity(10);
     assert_eq!(
         Ok(()),
@@ -297,7 +785,7 @@ fn serialize_single() {
 #[cfg(test)]
 fn setup_test_identity() -> Identity {
     let name: String = str::to_string("Hello");
-    let mut identity = Identity::new(1, 2, 3, 4, 5, name);
+    let mut identity = Identity::new(1, 2, 3, Revision::new(4, 0), 5, name);
 
     identity.status.set(6);
     identity.state.set(7);
@@ -329,10 +817,338 @@ fn get_attribute_state() {
     assert_eq!(7, buf[0]);
 }
 
+#[test]
+fn get_attribute_list() {
+    use crate::error_code::ATTRIBUTE_LIST_ERROR;
+    use crate::message_router::{Request, Response};
+
+    let mut req = Request::default();
+    let mut buf = &b"\x03\x02\x20\x01\x24\x01\x02\0\x01\0\x20\0"[..];
+    assert_eq!(Ok(()), req.deserialize(&mut buf));
+
+    let identity = setup_test_identity();
+    let mut res = BytesMut::with_capacity(100);
+    assert_eq!(
+        Ok(ATTRIBUTE_LIST_ERROR),
+        Response::serialize_attribute_list(&mut res, &req.attribute_list, |b, id| {
+            identity.serialize_attribute_single(b, id.into())
+        })
+    );
+    assert_eq!(&b"\x02\0\x01\0\0\0\x01\0\x20\0\x14\0"[..], res);
+}
+
+#[test]
+fn set_attribute_list() {
+    use crate::error_code::ATTRIBUTE_LIST_ERROR;
+    use crate::message_router::Response;
+
+    let mut identity = setup_test_identity();
+    let mut data = &b"\x02\0\x05\0\x06\x07\x0a\0\x0b"[..];
+    let mut res = BytesMut::with_capacity(100);
+    assert_eq!(
+        Ok(SUCCESS),
+        Response::serialize_set_attribute_list(&mut res, &mut data, |b, id| {
+            identity.deserialize_attribute_single(b, id.into())
+        })
+    );
+    assert_eq!(0, data.len());
+    assert_eq!(&b"\x02\0\x05\0\0\0\x0a\0\0\0"[..], res);
+    assert_eq!(0x0706, identity.status.get());
+    assert_eq!(0x0b, identity.heartbeat_interval.get());
+
+    // vendor identification is not settable, processing stops
+    data = &b"\x02\0\x01\0\x06\x07\x0a\0\x0b"[..];
+    res = BytesMut::with_capacity(100);
+    assert_eq!(
+        Ok(ATTRIBUTE_LIST_ERROR),
+        Response::serialize_set_attribute_list(&mut res, &mut data, |b, id| {
+            identity.deserialize_attribute_single(b, id.into())
+        })
+    );
+    assert_eq!(&b"\x01\0\x01\0\x0e\0"[..], res);
+    assert_eq!(1, identity.vendor_id.get());
+}
+
+#[test]
+fn reset() {
+    let mut identity = setup_test_identity();
+    assert_eq!(Ok(()), identity.reset(Identity::RESET_POWER_CYCLE));
+    assert_eq!(0, identity.status.get());
+    assert_eq!(0, identity.state.get());
+    assert_eq!(8, identity.configuration_consistency_value.get());
+    assert_eq!(9, identity.heartbeat_interval.get());
+
+    identity = setup_test_identity();
+    assert_eq!(Ok(()), identity.reset(Identity::RESET_FACTORY_DEFAULTS));
+    assert_eq!(0, identity.status.get());
+    assert_eq!(0, identity.configuration_consistency_value.get());
+    assert_eq!(0, identity.heartbeat_interval.get());
+
+    identity = setup_test_identity();
+    assert_eq!(Err(INVALID_PARAMETER), identity.reset(2));
+    assert_eq!(7, identity.state.get());
+}
+
+#[test]
+fn revision() {
+    let rev = Revision::new(2, 7);
+    assert_eq!(0x0702, u16::from(rev));
+    assert_eq!(rev, Revision::from(0x0702));
+
+    let id = Identity::new(1, 2, 3, rev, 5, str::to_string("Hello"));
+    assert_eq!(rev, id.revision());
+    let mut buf = BytesMut::with_capacity(10);
+    assert_eq!(
+        Ok(()),
+        id.serialize_attribute_single(&mut buf, Identity::REVISION)
+    );
+    assert_eq!(&b"\x02\x07"[..], buf); // major first
+}
+
+#[test]
+fn state() {
+    let mut id = Identity::default();
+    assert_eq!(State::NONEXISTENT, id.state());
+    assert_eq!(Ok(()), id.set_state(State::SELF_TESTING));
+    assert_eq!(Ok(()), id.set_state(State::OPERATIONAL));
+    assert_eq!(3, id.state.get());
+    assert_eq!(Ok(()), id.set_state(State::MAJOR_RECOVERABLE_FAULT));
+
+    // a recoverable fault has to be tested again
+    assert_eq!(Err(INVALID_PARAMETER), id.set_state(State::OPERATIONAL));
+    assert_eq!(State::MAJOR_RECOVERABLE_FAULT, id.state());
+
+    assert_eq!(Ok(()), id.set_state(State::MAJOR_UNRECOVERABLE_FAULT));
+    assert_eq!(Err(INVALID_PARAMETER), id.set_state(State::SELF_TESTING));
+    assert_eq!(Ok(()), id.set_state(State::NONEXISTENT));
+
+    assert_eq!(Err(INVALID_PARAMETER), id.set_state(State::OPERATIONAL));
+    assert_eq!(Err(INVALID_PARAMETER), id.set_state(State::from(6)));
+    assert_eq!(5, u8::from(State::MAJOR_UNRECOVERABLE_FAULT));
+
+    // written via the eip interface, the same transitions apply
+    id.state.take_dirty();
+    let mut buf = &b"\x03"[..];
+    assert_eq!(
+        Err(INVALID_PARAMETER),
+        id.deserialize_attribute_single(&mut buf, Identity::STATE)
+    );
+    assert_eq!(1, buf.len());
+    assert_eq!(State::NONEXISTENT, id.state());
+    assert!(!id.state.take_dirty());
+    buf = &b"\x01"[..];
+    assert_eq!(
+        Ok(()),
+        id.deserialize_attribute_single(&mut buf, Identity::STATE)
+    );
+    assert_eq!(State::SELF_TESTING, id.state());
+}
+
+#[test]
+fn status() {
+    let mut id = Identity::default();
+    id.set_owned(true);
+    assert!(id.is_owned());
+    assert_eq!(0x0001, id.status.get());
+
+    id.set_configured(true);
+    assert!(id.is_configured());
+    assert_eq!(Ok(()), id.set_extended_status(0x05));
+    assert_eq!(0x05, id.extended_status());
+    assert_eq!(0x0055, id.status.get());
+
+    id.set_status_bits(Identity::STATUS_MAJOR_RECOVERABLE_FAULT, true);
+    id.set_owned(false);
+    assert!(!id.is_owned());
+    assert_eq!(0x0454, id.status.get());
+
+    assert_eq!(Err(INVALID_PARAMETER), id.set_extended_status(0x10));
+    assert_eq!(Ok(()), id.set_extended_status(0));
+    assert_eq!(0x0404, id.status.get());
+
+    let mut buf = BytesMut::with_capacity(10);
+    assert_eq!(
+        Ok(()),
+        id.serialize_attribute_single(&mut buf, Identity::STATUS)
+    );
+    assert_eq!(&b"\x04\x04"[..], buf);
+}
+
+#[test]
+fn handle_request_reset() {
+    let mut identity = setup_test_identity();
+    let mut req = Request::default();
+    assert_eq!(
+        Ok(()),
+        req.deserialize(&mut &b"\x05\x02\x20\x01\x24\x01"[..])
+    );
+
+    let mut buf = BytesMut::with_capacity(10);
+    assert_eq!(
+        Ok(()),
+        identity.handle_request(&req, &mut &b"\x01"[..], &mut buf)
+    );
+    assert_eq!(0, identity.heartbeat_interval.get());
+
+    identity = setup_test_identity();
+    assert_eq!(
+        Ok(()),
+        identity.handle_request(&req, &mut &b""[..], &mut buf)
+    );
+    assert_eq!(0, identity.state.get());
+    assert_eq!(9, identity.heartbeat_interval.get());
+
+    assert_eq!(
+        Err(INVALID_PARAMETER),
+        identity.handle_request(&req, &mut &b"\x03"[..], &mut buf)
+    );
+
+    req.class = Some(2);
+    assert_eq!(
+        Err(PATH_DESTINATION_UNKNOWN),
+        identity.handle_request(&req, &mut &b""[..], &mut buf)
+    );
+}
+
+#[test]
+fn handle_request_attribute() {
+    let mut identity = setup_test_identity();
+    let mut buf = BytesMut::with_capacity(10);
+    let mut req = Request::get_attribute_single(Identity::CLASS, 1, 1);
+    assert_eq!(
+        Ok(()),
+        identity.handle_request(&req, &mut &b""[..], &mut buf)
+    );
+    assert_eq!(&b"\x01\0"[..], buf);
+
+    // beyond 16 bit, not the vendor id
+    buf.clear();
+    req.attribute = Some(0x10001);
+    assert_eq!(
+        Err(ATTRIBUTE_NOT_SUPPORTED),
+        identity.handle_request(&req, &mut &b""[..], &mut buf)
+    );
+    req = Request::set_attribute_single(Identity::CLASS, 1, 0x1000a);
+    assert_eq!(
+        Err(ATTRIBUTE_NOT_SUPPORTED),
+        identity.handle_request(&req, &mut &b"\x05"[..], &mut buf)
+    );
+    assert_eq!(9, identity.heartbeat_interval.get());
+
+    // no attribute segment
+    req.attribute = None;
+    assert_eq!(
+        Err(PATH_SEGMENT_ERROR),
+        identity.handle_request(&req, &mut &b"\x05"[..], &mut buf)
+    );
+    assert!(buf.is_empty());
+}
+
+#[test]
+fn reply_list_identity() {
+    let name: String = str::to_string("Hello");
+    let id = Identity::new(1, 2, 3, Revision::new(4, 0), 5, name);
+
+    let mut req = Encapsulation::default();
+    let mut frame = &b"\x63\0\0\0\0\0\0\0\0\0\0\0\x01\x02\x03\x04\x05\x06\x07\x08\0\0\0\0"[..];
+    assert_eq!(Ok(()), req.deserialize(&mut frame));
+
+    let mut buf = BytesMut::with_capacity(100);
+    assert_eq!(Ok(()), id.reply_list_identity(&req, &mut buf));
+    assert_eq!(&b"\x63\0\x2d\0\0\0\0\0\0\0\0\0\x01\x02\x03\x04\x05\x06\x07\x08\0\0\0\0\x01\0\x0c\0'\0\x01\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\x01\0\x02\0\x03\0\x04\0\0\0\x05\0\0\0\x05Hello\0"[..], buf);
+
+    let mut reply = Encapsulation::default();
+    assert_eq!(Ok(()), reply.deserialize(&mut &buf[..]));
+    assert_eq!(req.context, reply.context);
+    assert_eq!(buf.len() - reply.serial_size(), reply.len as usize);
+
+    req.command = Encapsulation::LIST_SERVICES;
+    assert_eq!(
+        Err(UNSUPPORTED_COMMAND),
+        id.reply_list_identity(&req, &mut buf)
+    );
+}
+
+#[test]
+fn serialize_all_attributes() {
+    let mut id = Identity::new(1, 2, 3, Revision::new(4, 1), 5, str::to_string("Hello"));
+    id.state.set(3);
+    id.heartbeat_interval = Usint::new(7, AccessCode::set_only());
+
+    let mut buf = BytesMut::with_capacity(100);
+    assert_eq!(Ok(()), id.serialize_all_attributes(&mut buf));
+    // vendor, device type, product code, revision, status, serial number, product name,
+    // state, configuration consistency value and the zero filled heartbeat interval
+    assert_eq!(
+        &b"\x01\0\x02\0\x03\0\x04\x01\0\0\x05\0\0\0\x05Hello\x03\0\0\0"[..],
+        buf
+    );
+
+    // via the router
+    let req = Request::get_attribute_all(Identity::CLASS, 1);
+    let mut reply = BytesMut::with_capacity(100);
+    assert_eq!(Ok(()), id.handle_request(&req, &mut &b""[..], &mut reply));
+    assert_eq!(buf, reply);
+
+    // a non getable product name is an empty string, the state stays in place
+    id.product_name =
+        ShortString::with_capacity(str::to_string("Hello"), AccessCode::set_only(), 32);
+    buf.clear();
+    assert_eq!(Ok(()), id.serialize_all_attributes(&mut buf));
+    assert_eq!(
+        &b"\x01\0\x02\0\x03\0\x04\x01\0\0\x05\0\0\0\0\x03\0\0\0"[..],
+        buf
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_round_trip() {
+    let id = Identity::new(1, 2, 3, Revision::new(4, 1), 5, str::to_string("Hello"));
+    let json = serde_json::to_string(&id).unwrap();
+    assert!(json.contains("\"vendor_id\""));
+
+    let received: Identity = serde_json::from_str(&json).unwrap();
+    assert_eq!(id, received);
+
+    let state = serde_json::to_string(&State::OPERATIONAL).unwrap();
+    assert_eq!(
+        state,
+        serde_json::to_string(&u8::from(State::OPERATIONAL)).unwrap()
+    );
+}
+
+#[test]
+fn heartbeat() {
+    use crate::error_code::ATTRIBUTE_NOT_SETTABLE;
+
+    let mut identity = setup_test_identity();
+    assert_eq!(Some(9), identity.heartbeat());
+
+    // disabled by a write of 0
+    assert_eq!(
+        Ok(()),
+        identity.deserialize_attribute_single(&mut &b"\0"[..], Identity::HEARTBEAT_INTERVAL)
+    );
+    assert_eq!(None, identity.heartbeat());
+
+    identity.heartbeat_interval.set(u8::MAX);
+    assert_eq!(Some(255), identity.heartbeat());
+
+    identity.heartbeat_interval = Usint::new(30, AccessCode::get_only());
+    assert_eq!(
+        Err(ATTRIBUTE_NOT_SETTABLE),
+        identity.deserialize_attribute_single(&mut &b"\0"[..], Identity::HEARTBEAT_INTERVAL)
+    );
+    assert_eq!(Some(30), identity.heartbeat());
+}
+
 #[test]
 fn auto_traits() {
     use crate::eip::check_auto_traits;
 
     check_auto_traits::<Attr>();
     check_auto_traits::<Identity>();
+    check_auto_traits::<Revision>();
+    check_auto_traits::<State>();
 }
@@ -0,0 +1,127 @@
+use crate::eip::{EipResult, Serializing};
+use crate::error_code::{NOT_ENOUGH_DATA, REPLY_DATA_TOO_LARGE};
+use crate::item::Item;
+use bytes::{Buf, BufMut, BytesMut};
+use core::mem::size_of;
+
+/// This is the ListInterfaces reply, no non-CIP communication interfaces are supported
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ListInterfaces {
+    pub item_count: u16, // Number of interface items
+}
+
+impl ListInterfaces {
+    /// Create a new server, listing no interfaces
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytes::BytesMut;
+    /// use eip_protocol::interfaces::ListInterfaces;
+    /// use eip_protocol::eip::Serializing;
+    ///
+    /// let li = ListInterfaces::server();
+    /// let mut buf = BytesMut::with_capacity(100);
+    ///
+    /// assert_eq!(
+    ///    Ok(()),
+    ///    li.serialize(&mut buf)
+    /// );
+    ///
+    /// assert_eq!(&b"\0\0"[..], buf);
+    /// ```
+    ///
+    pub fn server() -> Self {
+        ListInterfaces::default()
+    }
+}
+
+impl Serializing for ListInterfaces {
+    /// Deserialize all fields
+    /// Note: the bodies of the interface items are skipped
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to read from
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if there is not enough data.
+    ///
+    fn deserialize(&mut self, buf: &mut dyn Buf) -> EipResult {
+        if buf.remaining() < self.serial_size() {
+            return Err(NOT_ENOUGH_DATA);
+        }
+        self.item_count = buf.get_u16_le();
+        for _ in 0..self.item_count {
+            let mut item = Item::default();
+            item.deserialize_checked(buf)?;
+            buf.advance(item.len);
+        }
+        Ok(())
+    }
+
+    /// Serialize all fields
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to write to
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if there is not enough room.
+    ///
+    fn serialize(&self, buf: &mut BytesMut) -> EipResult {
+        if buf.remaining_mut() < self.serial_size() {
+            return Err(REPLY_DATA_TOO_LARGE);
+        }
+        buf.put_u16_le(self.item_count);
+        Ok(())
+    }
+
+    /// Get the serialized size in Bytes.
+    ///
+    /// # Returns
+    ///
+    /// * The number of bytes when serialized
+    ///
+    fn serial_size(&self) -> usize {
+        size_of::<u16>() // item_count
+    }
+}
+
+#[test]
+fn deserialize() {
+    let mut li = ListInterfaces::default();
+    let mut buf = &b"\0\0"[..];
+    assert_eq!(Ok(()), li.deserialize(&mut buf));
+    assert_eq!(0, buf.len());
+    assert_eq!(0, li.item_count);
+
+    buf = &b"\x01\0\x01\x01\x02\0\x01\x02"[..];
+    assert_eq!(Ok(()), li.deserialize(&mut buf));
+    assert_eq!(0, buf.len());
+    assert_eq!(1, li.item_count);
+
+    buf = &b"\x01\0\x01\x01\x02\0\x01"[..];
+    assert_eq!(Err(NOT_ENOUGH_DATA), li.deserialize(&mut buf));
+}
+
+#[test]
+fn serialize() {
+    let li = ListInterfaces::server();
+    let mut buf = BytesMut::with_capacity(100);
+
+    assert_eq!(Ok(()), li.serialize(&mut buf));
+    assert_eq!(2, buf.len());
+    assert_eq!(&b"\0\0"[..], buf);
+}
+
+#[test]
+fn auto_traits() {
+    use crate::eip::check_auto_traits;
+
+    check_auto_traits::<ListInterfaces>();
+}
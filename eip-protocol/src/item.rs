@@ -1,13 +1,17 @@
 use crate::eip;
 use crate::eip::{EipResult, Serializing};
 use crate::error_code::{ErrorCode, NOT_ENOUGH_DATA, REPLY_DATA_TOO_LARGE};
+#[cfg(test)]
+use alloc::string::String;
 use bytes::{Buf, BufMut, BytesMut};
 use core::mem::size_of;
 
-#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Id(u16);
 
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Item {
     pub type_id: Id,
     pub len: usize,
@@ -40,17 +44,6 @@ impl Item {
         Item { type_id, len }
     }
 
-    /// Get the serialized size in Bytes.
-    ///
-    /// # Returns
-    ///
-    /// * The number of bytes when serialized
-    ///
-    pub const fn serial_size(&self) -> usize {
-        size_of::<u16>() // type_id
-        + size_of::<u16>() // len
-    }
-
     /// Reserve room in a buffer to serialize this later.
     ///
     /// # Returns
@@ -68,6 +61,54 @@ impl Item {
     pub fn split_off(&self, buf: &mut BytesMut) -> Result<BytesMut, ErrorCode> {
         eip::split_off(buf, self.serial_size())
     }
+
+    /// Serialize this header followed by a body of which the length is not known up front.
+    /// The header is reserved after the data already in the buffer, the body written after it
+    /// and the measured length filled in.
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to write to
+    /// * `body` - Writes the body to the buffer after the header
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if there is not enough room or the body fails.
+    ///
+    pub fn serialize_with_body<F>(&mut self, buf: &mut BytesMut, body: F) -> EipResult
+    where
+        F: FnOnce(&mut BytesMut) -> EipResult,
+    {
+        let header_end = buf.len() + self.serial_size();
+        if eip::remaining_needed(buf, header_end) > 0 {
+            return Err(REPLY_DATA_TOO_LARGE);
+        }
+        let mut rest = buf.split_off(header_end);
+        body(&mut rest)?;
+        self.len = rest.len();
+        self.serialize(buf)?;
+        buf.unsplit(rest);
+        Ok(())
+    }
+
+    /// Deserialize all fields and verify the body is available
+    /// Note: the body itself is not consumed
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to read from
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if there is not enough data for the header or the body.
+    ///
+    pub fn deserialize_checked(&mut self, buf: &mut dyn Buf) -> EipResult {
+        self.deserialize(buf)?;
+        if buf.remaining() < self.len {
+            return Err(NOT_ENOUGH_DATA);
+        }
+        Ok(())
+    }
 }
 
 impl Serializing for Item {
@@ -108,6 +149,17 @@ impl Serializing for Item {
         buf.put_u16_le(self.len as u16);
         Ok(())
     }
+
+    /// Get the serialized size in Bytes.
+    ///
+    /// # Returns
+    ///
+    /// * The number of bytes when serialized
+    ///
+    fn serial_size(&self) -> usize {
+        size_of::<u16>() // type_id
+        + size_of::<u16>() // len
+    }
 }
 
 #[test]
@@ -122,6 +174,18 @@ fn deserialize() {
     assert_eq!(Err(NOT_ENOUGH_DATA), item.deserialize(&mut buf));
 }
 
+#[test]
+fn deserialize_checked() {
+    let mut item = Item::default();
+    let mut buf = &b"\xb2\0\x04\0\x01\x02\x03\x04"[..];
+    assert_eq!(Ok(()), item.deserialize_checked(&mut buf));
+    assert_eq!(Item::UNCONNECTED_DATA, item.type_id);
+    assert_eq!(4, buf.len());
+
+    buf = &b"\xb2\0\x0a\0\x01\x02\x03\x04"[..];
+    assert_eq!(Err(NOT_ENOUGH_DATA), item.deserialize_checked(&mut buf));
+}
+
 #[test]
 fn serialize() {
     let mut item = Item::new(Item::SEQUENCED_ADDRESS, 0);
@@ -132,6 +196,52 @@ fn serialize() {
     assert_eq!(&b"\x02\x80\x03\0"[..], buf);
 }
 
+#[test]
+fn serialize_with_body() {
+    use crate::identity::{Identity, Revision};
+
+    let identity = Identity::new(1, 2, 3, Revision::new(4, 0), 5, String::from("Hello"));
+    let mut expected = BytesMut::with_capacity(100);
+    assert_eq!(Ok(()), identity.list(&mut expected));
+
+    let mut item = Item::new(Item::IDENTITY, 0);
+    let mut buf = BytesMut::with_capacity(100);
+    let body = |b: &mut BytesMut| {
+        b.put_u16_le(crate::encapsulation::VERSION);
+        identity.socket_address.serialize(b)?;
+        for attr in 1..=8 {
+            identity.serialize_attribute_single(b, attr.into())?;
+        }
+        Ok(())
+    };
+    assert_eq!(Ok(()), item.serialize_with_body(&mut buf, body));
+    assert_eq!(39, item.len);
+    assert_eq!(expected, buf);
+
+    // a failing body
+    buf.clear();
+    let res = item.serialize_with_body(&mut buf, |_| Err(REPLY_DATA_TOO_LARGE));
+    assert_eq!(Err(REPLY_DATA_TOO_LARGE), res);
+
+    // after data already in the buffer, e.g. the item count
+    buf = BytesMut::with_capacity(100);
+    buf.put_slice(b"\x01\0\xcd\xab\x55\x66");
+    let res = item.serialize_with_body(&mut buf, |b| {
+        b.put_u16_le(0x1234);
+        Ok(())
+    });
+    assert_eq!(Ok(()), res);
+    assert_eq!(2, item.len);
+    assert_eq!(&b"\x01\0\xcd\xab\x55\x66\x0c\0\x02\0\x34\x12"[..], buf);
+
+    // no room for the header after the data
+    buf = BytesMut::with_capacity(4);
+    buf.put_slice(b"\x01\0");
+    let res = item.serialize_with_body(&mut buf, |_| Ok(()));
+    assert_eq!(Err(REPLY_DATA_TOO_LARGE), res);
+    assert_eq!(&b"\x01\0"[..], buf);
+}
+
 #[test]
 fn auto_traits() {
     use crate::eip::check_auto_traits;
@@ -139,3 +249,10 @@ fn auto_traits() {
     check_auto_traits::<Id>();
     check_auto_traits::<Item>();
 }
+
+#[test]
+fn hash_key() {
+    use crate::eip::check_hash_key;
+
+    check_hash_key(Item::UNCONNECTED_DATA);
+}
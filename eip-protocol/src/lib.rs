@@ -1,13 +1,38 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+#[cfg(test)]
+extern crate std;
+
+pub mod assembly;
 mod attr;
-mod data_type;
+#[cfg(feature = "std")]
+pub mod client;
+pub mod connected_address;
+pub mod connection;
+pub mod connection_manager;
+#[cfg(feature = "std")]
+pub mod connection_timer;
+pub mod cpf;
+pub mod data_type;
 pub mod eip;
 pub mod encapsulation;
 pub mod error_code;
+pub mod frame_reader;
 pub mod identity;
+pub mod interfaces;
 pub mod item;
+pub mod message;
 pub mod message_router;
+pub mod multiple_service;
+pub mod parameter;
+pub mod port;
 pub mod send_data;
+pub mod sequenced_address;
 pub mod services;
+#[cfg(feature = "std")]
 pub mod session;
 pub mod socket_address;
 pub mod static_attr;
+#[cfg(test)]
+mod test_vectors;
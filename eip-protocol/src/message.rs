@@ -0,0 +1,277 @@
+use crate::cpf::CommonPacketFormat;
+use crate::eip::{EipResult, Serializing};
+use crate::encapsulation::Encapsulation;
+use crate::error_code::{NOT_ENOUGH_DATA, UNSUPPORTED_COMMAND};
+use crate::item::Item;
+use crate::message_router::Response;
+use crate::send_data::SendData;
+#[cfg(test)]
+use alloc::vec;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use core::mem::size_of;
+
+/// A complete SendRRData or SendUnitData frame, the encapsulation header followed by
+/// the SendData fields and the Common Packet Format items.
+/// The length and the item count are computed when serialized.
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EipMessage {
+    pub encapsulation: Encapsulation,
+    pub send_data: SendData,
+    pub cpf: CommonPacketFormat,
+}
+
+impl EipMessage {
+    /// Get the serialized size of the SendData fields preceding the item count in Bytes.
+    ///
+    /// # Returns
+    ///
+    /// * The number of bytes when serialized
+    ///
+    const fn send_data_size() -> usize {
+        size_of::<u32>() // interface_handle
+        + size_of::<u16>() // time_out
+    }
+
+    /// Serialize a complete SendRRData reply to an unconnected request, e.g. Get_Attribute_Single.
+    /// The Message Router response is followed by the payload in the unconnected data item,
+    /// all lengths and the item count are filled in.
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to write to
+    /// * `session` - The session handle of the request
+    /// * `context` - The sender context of the request, echoed
+    /// * `response` - The Message Router response
+    /// * `payload` - The response data following the response, e.g. the attribute value
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if there is not enough room.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytes::BytesMut;
+    /// use eip_protocol::message::EipMessage;
+    /// use eip_protocol::message_router::{Request, Response};
+    ///
+    /// let res = Response::success(Request::GET_ATTRIBUTE_SINGLE);
+    /// let mut buf = BytesMut::with_capacity(100);
+    ///
+    /// assert_eq!(
+    ///    Ok(()),
+    ///    EipMessage::serialize_rr_reply(&mut buf, 1, [0; 8], &res, b"\x01\0")
+    /// );
+    /// assert_eq!(46, buf.len());
+    /// ```
+    pub fn serialize_rr_reply(
+        buf: &mut BytesMut,
+        session: u32,
+        context: [u8; 8],
+        response: &Response,
+        payload: &[u8],
+    ) -> EipResult {
+        let mut data = BytesMut::with_capacity(response.serial_size() + payload.len());
+        response.serialize(&mut data)?;
+        data.put_slice(payload);
+
+        let mut msg = EipMessage {
+            encapsulation: Encapsulation {
+                command: Encapsulation::SEND_RR_DATA,
+                session,
+                context,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        msg.cpf.push(Item::NULL_ADDRESS, Bytes::new());
+        msg.cpf.push(Item::UNCONNECTED_DATA, data.freeze());
+        msg.serialize(buf)
+    }
+}
+
+impl Serializing for EipMessage {
+    /// Deserialize one frame
+    /// Note: only the bytes of this frame are consumed
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to read from
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if there is not enough data, the command is not
+    /// SendRRData or SendUnitData or the length does not match the items.
+    ///
+    fn deserialize(&mut self, buf: &mut dyn Buf) -> EipResult {
+        self.encapsulation.deserialize(buf)?;
+        if self.encapsulation.command != Encapsulation::SEND_RR_DATA
+            && self.encapsulation.command != Encapsulation::SEND_UNIT_DATA
+        {
+            return Err(UNSUPPORTED_COMMAND);
+        }
+        if buf.remaining() < self.encapsulation.len as usize {
+            return Err(NOT_ENOUGH_DATA);
+        }
+        let payload = buf.copy_to_bytes(self.encapsulation.len as usize);
+
+        self.send_data.deserialize(&mut &payload[..])?;
+        self.send_data.command = self.encapsulation.command.clone();
+
+        let mut items = &payload[Self::send_data_size()..];
+        self.cpf.deserialize(&mut items)?;
+        self.encapsulation
+            .validate_len(payload.len() - items.remaining())
+    }
+
+    /// Serialize one frame
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to write to
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if there is not enough room or too many items.
+    ///
+    fn serialize(&self, buf: &mut BytesMut) -> EipResult {
+        let mut payload = BytesMut::with_capacity(self.serial_size());
+        payload.put_u32_le(self.send_data.interface_handle());
+        payload.put_u16_le(self.send_data.time_out());
+        self.cpf.serialize(&mut payload)?; // including the item count
+        self.encapsulation.serialize_with_payload(buf, &payload)
+    }
+
+    /// Get the serialized size in Bytes.
+    ///
+    /// # Returns
+    ///
+    /// * The number of bytes when serialized
+    ///
+    fn serial_size(&self) -> usize {
+        self.encapsulation.serial_size() + Self::send_data_size() + self.cpf.serial_size()
+    }
+}
+
+#[cfg(test)]
+const SEND_RR_DATA: &[u8] = b"\x6f\0\x18\0\x78\x56\x34\x12\0\0\0\0_pycomm_\0\0\0\0\0\0\0\0\x0a\0\x02\0\0\0\0\0\xb2\0\x08\0\x0e\x03\x20\x01\x24\x01\x30\x07";
+
+#[test]
+fn deserialize() {
+    use crate::message_router::Request;
+
+    let mut msg = EipMessage::default();
+    let mut buf = SEND_RR_DATA;
+    assert_eq!(Ok(()), msg.deserialize(&mut buf));
+    assert_eq!(0, buf.len());
+    assert_eq!(0x12345678, msg.encapsulation.session);
+    assert_eq!(&b"_pycomm_"[..], &msg.encapsulation.context);
+    assert_eq!(10, msg.send_data.time_out());
+    assert!(!msg.send_data.is_connected());
+    assert_eq!(2, msg.send_data.item_count);
+
+    let data = msg.cpf.find(&Item::UNCONNECTED_DATA).unwrap();
+    let mut req = Request::default();
+    assert_eq!(Ok(()), req.deserialize(&mut &data[..]));
+    assert_eq!(Request::get_attribute_single(0x01, 1, 7), req);
+
+    let mut out = BytesMut::with_capacity(100);
+    assert_eq!(Ok(()), msg.serialize(&mut out));
+    assert_eq!(msg.serial_size(), out.len());
+    assert_eq!(SEND_RR_DATA, out);
+}
+
+#[test]
+fn deserialize_bounds() {
+    use crate::error_code::MESSAGE_FORMAT_ERROR;
+
+    let mut msg = EipMessage::default();
+
+    // truncated frame
+    let mut buf = &SEND_RR_DATA[..SEND_RR_DATA.len() - 1];
+    assert_eq!(Err(NOT_ENOUGH_DATA), msg.deserialize(&mut buf));
+
+    // length claims more than the items
+    let mut frame = BytesMut::from(SEND_RR_DATA);
+    frame[2] += 1;
+    frame.put_u8(0);
+    assert_eq!(Err(MESSAGE_FORMAT_ERROR), msg.deserialize(&mut &frame[..]));
+
+    // not SendRRData or SendUnitData
+    frame = BytesMut::from(SEND_RR_DATA);
+    frame[0] = 0x65;
+    assert_eq!(Err(UNSUPPORTED_COMMAND), msg.deserialize(&mut &frame[..]));
+}
+
+#[test]
+fn serialize() {
+    use bytes::Bytes;
+
+    let mut msg = EipMessage {
+        encapsulation: Encapsulation {
+            command: Encapsulation::SEND_UNIT_DATA,
+            session: 1,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    msg.cpf.push(
+        Item::CONNECTED_ADDRESS,
+        Bytes::from_static(b"\x04\x03\x02\x01"),
+    );
+    msg.cpf.push_connected_data(5, b"\x01\x02");
+
+    let mut buf = BytesMut::with_capacity(100);
+    assert_eq!(Ok(()), msg.serialize(&mut buf));
+    assert_eq!(
+        &b"\x70\0\x18\0\x01\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\x02\0\xa1\0\x04\0\x04\x03\x02\x01\xb1\0\x04\0\x05\0\x01\x02"[..],
+        buf
+    );
+
+    let mut received = EipMessage::default();
+    assert_eq!(Ok(()), received.deserialize(&mut &buf[..]));
+    assert!(received.send_data.is_connected());
+    assert_eq!(msg.cpf, received.cpf);
+}
+
+#[test]
+fn serialize_rr_reply() {
+    use crate::message_router::Request;
+
+    // reply to SEND_RR_DATA, the product name of the Identity
+    let reply = b"\x6f\0\x21\0\x78\x56\x34\x12\0\0\0\0_pycomm_\0\0\0\0\0\0\0\0\0\0\x02\0\0\0\0\0\xb2\0\x11\0\x8e\0\0\0\x0cEIP-PROTOCOL";
+    let res = Response::success(Request::GET_ATTRIBUTE_SINGLE);
+    let mut buf = BytesMut::with_capacity(100);
+    assert_eq!(
+        Ok(()),
+        EipMessage::serialize_rr_reply(
+            &mut buf,
+            0x12345678,
+            *b"_pycomm_",
+            &res,
+            b"\x0cEIP-PROTOCOL"
+        )
+    );
+    assert_eq!(&reply[..], buf);
+
+    let mut received = EipMessage::default();
+    assert_eq!(Ok(()), received.deserialize(&mut &buf[..]));
+    let mut data = &received.cpf.find(&Item::UNCONNECTED_DATA).unwrap()[..];
+    let mut received_res = Response::default();
+    assert_eq!(Ok(()), received_res.deserialize(&mut data));
+    assert_eq!(&b"\x0cEIP-PROTOCOL"[..], received_res.data);
+
+    // payload too large for one item
+    buf.clear();
+    let payload = vec![0; u16::MAX as usize];
+    assert!(EipMessage::serialize_rr_reply(&mut buf, 1, [0; 8], &res, &payload).is_err());
+}
+
+#[test]
+fn auto_traits() {
+    use crate::eip::check_auto_traits;
+
+    check_auto_traits::<EipMessage>();
+}
@@ -1,15 +1,61 @@
 use crate::eip;
 use crate::eip::{EipResult, Serializing};
-use crate::error_code::{ErrorCode, NOT_ENOUGH_DATA, PATH_SEGMENT_ERROR, REPLY_DATA_TOO_LARGE};
+use crate::error_code::{
+    ErrorCode, ATTRIBUTE_LIST_ERROR, ATTRIBUTE_NOT_SUPPORTED, INSUFFICIENT_MEMORY, NOT_ENOUGH_DATA,
+    PATH_DESTINATION_UNKNOWN, PATH_SEGMENT_ERROR, REPLY_DATA_TOO_LARGE, SERVICE_NOT_SUPPORTED,
+    SUCCESS,
+};
+use crate::identity::{Identity, Revision};
+use crate::static_attr::StaticAttr;
+use alloc::boxed::Box;
+use alloc::collections::btree_map::Entry;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+#[cfg(test)]
+use alloc::string::ToString;
+#[cfg(test)]
+use alloc::vec;
+use alloc::vec::Vec;
 use bytes::{Buf, BufMut, BytesMut};
+use core::fmt::Debug;
 use core::mem::size_of;
-use log::warn;
 
-const ADDITIONAL_STATUS_MAX: u8 = 2;
-
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Service(u8);
 
+impl Service {
+    /// Get the service with the response bit set
+    ///
+    /// # Returns
+    ///
+    /// * The service code of the response
+    ///
+    pub const fn as_response(self) -> Service {
+        Service(self.0 | Request::RESPONSE.0)
+    }
+
+    /// Get the service with the response bit cleared
+    ///
+    /// # Returns
+    ///
+    /// * The service code of the request
+    ///
+    pub const fn as_request(self) -> Service {
+        Service(self.0 & !Request::RESPONSE.0)
+    }
+
+    /// Check if the response bit is set
+    ///
+    /// # Returns
+    ///
+    /// * True if this is the service code of a response
+    ///
+    pub const fn is_response(&self) -> bool {
+        self.0 & Request::RESPONSE.0 != 0
+    }
+}
+
 impl From<u8> for Service {
     fn from(val: u8) -> Self {
         Self(val)
@@ -23,30 +69,156 @@ impl From<Service> for u8 {
 }
 
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Request {
     pub service: Service,
-    pub class: Option<u16>,
-    pub instance: Option<u16>,
-    pub attribute: Option<u16>,
+    pub class: Option<u32>,
+    pub instance: Option<u32>,
+    pub attribute: Option<u32>,
+    pub member: Option<u16>,      // Element within a structured attribute
+    pub symbol: Option<String>,   // ANSI extended symbol, e.g. a tag name
+    pub attribute_list: Vec<u16>, // Requested attributes of Get_Attribute_List
+    pub data_segment: Option<BytesMut>, // Simple data segment, e.g. configuration data
+    pub key: Option<ElectronicKey>, // Electronic key segment, checked against the Identity
 }
 
 impl Request {
     pub const GET_ATTRIBUTE_SINGLE: Service = Service(0x0e);
     pub const SET_ATTRIBUTE_SINGLE: Service = Service(0x0f);
     pub const GET_ATTRIBUTE_ALL: Service = Service(0x01);
+    pub const GET_ATTRIBUTE_LIST: Service = Service(0x03);
+    pub const SET_ATTRIBUTE_LIST: Service = Service(0x04);
+    pub const RESET: Service = Service(0x05);
+    pub const CREATE: Service = Service(0x08);
+    pub const DELETE: Service = Service(0x09);
+    pub const MULTIPLE_SERVICE_PACKET: Service = Service(0x0a);
     pub const SET_ATTRIBUTE_ALL: Service = Service(0x10);
     pub const NO_OPERATION: Service = Service(0x17);
+    pub const FORWARD_CLOSE: Service = Service(0x4e);
+    pub const UNCONNECTED_SEND: Service = Service(0x52);
+    pub const FORWARD_OPEN: Service = Service(0x54);
     pub const RESPONSE: Service = Service(0x80);
 
     const TYPE_MASK: u8 = 0xe0;
     const TYPE_LOGICAL: u8 = 0x20;
+    const LOGICAL_KEY: u8 = 0x34;
+    const TYPE_DATA: u8 = 0x80;
+    const DATA_SIMPLE: u8 = 0x80;
+    const DATA_ANSI_EXTENDED_SYMBOL: u8 = 0x91;
     const LEVEL_MASK: u8 = 0x1c;
     const LEVEL_CLASS: u8 = 0x00;
     const LEVEL_INSTANCE: u8 = 0x04;
+    const LEVEL_MEMBER: u8 = 0x08;
     const LEVEL_ATTRIBUTE: u8 = 0x10;
     const FORMAT_MASK: u8 = 0x03;
     const FORMAT_8: u8 = 0x00;
     const FORMAT_16: u8 = 0x01;
+    const FORMAT_32: u8 = 0x02;
+    const MAX_SEGMENTS: u8 = 8; // symbol, class, instance, attribute and member fit easily
+    const MAX_PATH_SIZE: usize = 510; // 255 words, the largest path the 8 bit size can express
+
+    /// Create a Get_Attribute_Single request
+    ///
+    /// # Arguments
+    ///
+    /// * `class` - The class identifier
+    /// * `instance` - The instance number
+    /// * `attribute` - The attribute identifier
+    ///
+    /// # Returns
+    ///
+    /// * The created instance
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytes::BytesMut;
+    /// use eip_protocol::eip::Serializing;
+    /// use eip_protocol::message_router::Request;
+    ///
+    /// let req = Request::get_attribute_single(0x01, 1, 7);
+    /// let mut buf = BytesMut::with_capacity(10);
+    ///
+    /// assert_eq!(Ok(()), req.serialize(&mut buf));
+    /// assert_eq!(&b"\x0e\x03\x20\x01\x24\x01\x30\x07"[..], buf);
+    /// ```
+    ///
+    pub fn get_attribute_single(class: u32, instance: u32, attribute: u32) -> Self {
+        Request {
+            service: Self::GET_ATTRIBUTE_SINGLE,
+            class: Some(class),
+            instance: Some(instance),
+            attribute: Some(attribute),
+            ..Default::default()
+        }
+    }
+
+    /// Create a Set_Attribute_Single request
+    /// Note: the attribute value is the request data following the path
+    ///
+    /// # Arguments
+    ///
+    /// * `class` - The class identifier
+    /// * `instance` - The instance number
+    /// * `attribute` - The attribute identifier
+    ///
+    /// # Returns
+    ///
+    /// * The created instance
+    ///
+    pub fn set_attribute_single(class: u32, instance: u32, attribute: u32) -> Self {
+        Request {
+            service: Self::SET_ATTRIBUTE_SINGLE,
+            ..Self::get_attribute_single(class, instance, attribute)
+        }
+    }
+
+    /// Create a Get_Attribute_All request
+    ///
+    /// # Arguments
+    ///
+    /// * `class` - The class identifier
+    /// * `instance` - The instance number
+    ///
+    /// # Returns
+    ///
+    /// * The created instance
+    ///
+    pub fn get_attribute_all(class: u32, instance: u32) -> Self {
+        Request {
+            service: Self::GET_ATTRIBUTE_ALL,
+            class: Some(class),
+            instance: Some(instance),
+            ..Default::default()
+        }
+    }
+
+    /// Deserialize one path segment
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to read from
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if there is not enough data or the segment is invalid.
+    ///
+    pub(crate) fn deserialize_segment(&mut self, buf: &mut dyn Buf) -> EipResult {
+        if buf.remaining() < 1 {
+            return Err(PATH_SEGMENT_ERROR);
+        }
+        let seg = buf.get_u8();
+        if seg == Self::LOGICAL_KEY {
+            self.key = Some(ElectronicKey::deserialize_body(buf)?);
+            return Ok(());
+        }
+
+        match seg & Self::TYPE_MASK {
+            Self::TYPE_LOGICAL => self.deserialize_logical(buf, seg),
+            Self::TYPE_DATA => self.deserialize_data(buf, seg),
+            _ => Err(PATH_SEGMENT_ERROR),
+        }
+    }
 
     /// Deserialize a logical segment
     ///
@@ -65,14 +237,21 @@ impl Request {
                 if buf.remaining() < 1 {
                     return Err(PATH_SEGMENT_ERROR);
                 }
-                Some(buf.get_u8() as u16)
+                Some(buf.get_u8() as u32)
             }
             Self::FORMAT_16 => {
                 if buf.remaining() < 3 {
                     return Err(PATH_SEGMENT_ERROR);
                 }
                 buf.get_u8();
-                Some(buf.get_u16_le())
+                Some(buf.get_u16_le() as u32)
+            }
+            Self::FORMAT_32 => {
+                if buf.remaining() < 5 {
+                    return Err(PATH_SEGMENT_ERROR);
+                }
+                buf.get_u8();
+                Some(buf.get_u32_le())
             }
             _ => return Err(PATH_SEGMENT_ERROR),
         };
@@ -81,184 +260,223 @@ impl Request {
             Self::LEVEL_CLASS => self.class = val,
             Self::LEVEL_INSTANCE => self.instance = val,
             Self::LEVEL_ATTRIBUTE => self.attribute = val,
+            Self::LEVEL_MEMBER => match val.map(u16::try_from) {
+                Some(Ok(m)) => self.member = Some(m),
+                _ => return Err(PATH_SEGMENT_ERROR),
+            },
             _ => return Err(PATH_SEGMENT_ERROR),
         }
         Ok(())
     }
 
-    /// Serialize one logical segment
+    /// Deserialize a data segment
     ///
     /// # Arguments
     ///
-    /// * `buf` - The message buffer to write to
-    /// * `val` - The value to write
-    /// * `tag` - The type tag
+    /// * `buf` - The message buffer to read from
+    /// * `seg` - Segment type value
     ///
     /// # Errors
     ///
-    /// An error variant will be returned if there is not enough room.
+    /// An error variant will be returned if there is not enough data or the data is invalid.
     ///
-    fn serialize_logical(buf: &mut BytesMut, val: u16, tag: u8) -> EipResult {
-        if val <= u8::MAX as u16 {
-            if buf.remaining_mut() < 2 {
-                // 8 bit tag + 8 bit value
-                return Err(REPLY_DATA_TOO_LARGE);
+    fn deserialize_data(&mut self, buf: &mut dyn Buf, seg: u8) -> EipResult {
+        match seg {
+            Self::DATA_SIMPLE => {
+                if buf.remaining() < 1 {
+                    return Err(PATH_SEGMENT_ERROR);
+                }
+                let len = buf.get_u8() as usize * 2; // word count
+                if buf.remaining() < len {
+                    return Err(PATH_SEGMENT_ERROR);
+                }
+                self.data_segment = Some(BytesMut::from(&buf.copy_to_bytes(len)[..]));
             }
-            buf.put_u8(Self::TYPE_LOGICAL | Self::FORMAT_8 | tag);
-            buf.put_u8(val as u8);
-        } else {
-            if buf.remaining_mut() < 4 {
-                // 8 bit tag + 8bit dummy + 16 bit value
-                return Err(REPLY_DATA_TOO_LARGE);
+            Self::DATA_ANSI_EXTENDED_SYMBOL => {
+                if buf.remaining() < 1 {
+                    return Err(PATH_SEGMENT_ERROR);
+                }
+                let len = buf.get_u8() as usize;
+                let pad = len % 2;
+                if buf.remaining() < len + pad {
+                    return Err(PATH_SEGMENT_ERROR);
+                }
+                let symbol = match String::from_utf8(buf.copy_to_bytes(len).to_vec()) {
+                    Ok(v) => v,
+                    Err(_) => return Err(PATH_SEGMENT_ERROR),
+                };
+                buf.advance(pad);
+                self.symbol = Some(symbol);
             }
-            buf.put_u8(Self::TYPE_LOGICAL | Self::FORMAT_16 | tag);
-            buf.put_u8(0);
-            buf.put_u16_le(val);
+            _ => return Err(PATH_SEGMENT_ERROR),
         }
         Ok(())
     }
 
-    /// Get the minumum serialized size in Bytes.
+    /// Serialize an ANSI extended symbolic segment
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to write to
+    /// * `symbol` - The symbol to write
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if there is not enough room or the symbol is too long.
+    ///
+    fn serialize_symbol(buf: &mut BytesMut, symbol: &str) -> EipResult {
+        if symbol.len() > u8::MAX as usize {
+            return Err(PATH_SEGMENT_ERROR);
+        }
+        if buf.remaining_mut() < Self::symbol_size(symbol) {
+            return Err(REPLY_DATA_TOO_LARGE);
+        }
+        buf.put_u8(Self::DATA_ANSI_EXTENDED_SYMBOL);
+        buf.put_u8(symbol.len() as u8);
+        buf.put(symbol.as_bytes());
+        buf.put_bytes(0, symbol.len() % 2); // pad to an even number of bytes
+        Ok(())
+    }
+
+    /// Get the serialized size of an ANSI extended symbolic segment in Bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `symbol` - The symbol of the segment
     ///
     /// # Returns
     ///
-    /// * The minimum number of bytes when serialized
+    /// * The number of bytes when serialized
     ///
-    pub const fn serial_size(&self) -> usize {
-        size_of::<u8>() // service
-        + size_of::<u8>() // segment count
+    fn symbol_size(symbol: &str) -> usize {
+        2 + symbol.len() + symbol.len() % 2 // 8 bit tag + 8 bit length + symbol + pad
     }
-}
 
-impl Serializing for Request {
-    /// Deserialize all fields
+    /// Serialize a simple data segment
     ///
     /// # Arguments
     ///
-    /// * `buf` - The message buffer to read from
+    /// * `buf` - The message buffer to write to
+    /// * `data` - The data to write, an even number of bytes
     ///
     /// # Errors
     ///
-    /// An error variant will be returned if there is not enough data.
+    /// An error variant will be returned if there is not enough room or the data is
+    /// not a whole number of words or too long.
     ///
-    fn deserialize(&mut self, buf: &mut dyn Buf) -> EipResult {
-        if buf.remaining() < self.serial_size() {
+    fn serialize_data_segment(buf: &mut BytesMut, data: &[u8]) -> EipResult {
+        if !data.len().is_multiple_of(2) || data.len() > u8::MAX as usize * 2 {
             return Err(PATH_SEGMENT_ERROR);
         }
-
-        self.service = Service(buf.get_u8());
-        for _ in 0..buf.get_u8() {
-            // number of path segments
-            if buf.remaining() < 1 {
-                return Err(PATH_SEGMENT_ERROR);
-            }
-            let seg = buf.get_u8();
-
-            match seg & Self::TYPE_MASK {
-                Self::TYPE_LOGICAL => self.deserialize_logical(buf, seg)?,
-                _ => return Err(PATH_SEGMENT_ERROR),
-            };
+        if buf.remaining_mut() < Self::data_segment_size(data) {
+            return Err(REPLY_DATA_TOO_LARGE);
         }
-
+        buf.put_u8(Self::DATA_SIMPLE);
+        buf.put_u8((data.len() / 2) as u8);
+        buf.put(data);
         Ok(())
     }
 
-    /// Serialize all fields
+    /// Get the serialized size of a simple data segment in Bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The data of the segment
+    ///
+    /// # Returns
+    ///
+    /// * The number of bytes when serialized
+    ///
+    fn data_segment_size(data: &[u8]) -> usize {
+        2 + data.len() // 8 bit tag + 8 bit word count + data
+    }
+
+    /// Serialize one logical segment
     ///
     /// # Arguments
     ///
     /// * `buf` - The message buffer to write to
+    /// * `val` - The value to write
+    /// * `tag` - The type tag
     ///
     /// # Errors
     ///
     /// An error variant will be returned if there is not enough room.
     ///
-    fn serialize(&self, buf: &mut BytesMut) -> EipResult {
-        let mut n: u8 = 0; // Segment counter
-
-        if buf.remaining_mut() < self.serial_size() {
+    fn serialize_logical(buf: &mut BytesMut, val: u32, tag: u8) -> EipResult {
+        if buf.remaining_mut() < Self::logical_size(val) {
             return Err(REPLY_DATA_TOO_LARGE);
         }
-        let mut b = buf.split_off(self.serial_size()); // room for n
-
-        if let Some(c) = self.class {
-            Self::serialize_logical(&mut b, c, Self::LEVEL_CLASS)?;
-            n += 1;
-            if let Some(i) = self.instance {
-                Self::serialize_logical(&mut b, i, Self::LEVEL_INSTANCE)?;
-                n += 1;
-                if let Some(a) = self.attribute {
-                    Self::serialize_logical(&mut b, a, Self::LEVEL_ATTRIBUTE)?;
-                    n += 1;
-                }
-            }
+        if val <= u8::MAX as u32 {
+            buf.put_u8(Self::TYPE_LOGICAL | Self::FORMAT_8 | tag);
+            buf.put_u8(val as u8);
+        } else if val <= u16::MAX as u32 {
+            buf.put_u8(Self::TYPE_LOGICAL | Self::FORMAT_16 | tag);
+            buf.put_u8(0);
+            buf.put_u16_le(val as u16);
+        } else {
+            buf.put_u8(Self::TYPE_LOGICAL | Self::FORMAT_32 | tag);
+            buf.put_u8(0);
+            buf.put_u32_le(val);
         }
-
-        buf.put_u8(self.service.0);
-        buf.put_u8(n);
-        buf.unsplit(b);
         Ok(())
     }
-}
-
-#[derive(Clone, Debug, Default, PartialEq)]
-pub struct Response {
-    pub service: Service,
-    pub general_status: ErrorCode,
-    pub additional_status_size: u8, // number of 16 bit segments
-    pub additional_status: [u16; ADDITIONAL_STATUS_MAX as usize],
-}
 
-impl Response {
-    /// Get the serialized size in Bytes.
-    /// Note: additional_status_size must be set correctly
+    /// Get the serialized size of one logical segment in Bytes.
     ///
-    /// # Returns
-    ///
-    /// * The minimum number of bytes when serialized
+    /// # Arguments
     ///
-    pub fn serial_size(&self) -> usize {
-        size_of::<u8>() // service
-        + size_of::<u8>() // reserved
-        + size_of::<u8>() // general_status
-        + size_of::<u8>() // additional_status_size
-        + (self.additional_status_size as usize * 2) // 2 because of 16 bit segments
-    }
-
-    /// Get the serialized maximum size in Bytes.
+    /// * `val` - The value of the segment
     ///
     /// # Returns
     ///
-    /// * The maximum number of bytes when serialized
+    /// * The number of bytes when serialized
     ///
-    const fn serial_size_max(&self) -> usize {
-        size_of::<u8>() // service
-        + size_of::<u8>() // reserved
-        + size_of::<u8>() // general_status
-        + size_of::<u8>() // additional_status_size
-        + (ADDITIONAL_STATUS_MAX as usize * 2) // 2 because of 16 bit segments
+    const fn logical_size(val: u32) -> usize {
+        if val <= u8::MAX as u32 {
+            2 // 8 bit tag + 8 bit value
+        } else if val <= u16::MAX as u32 {
+            4 // 8 bit tag + 8bit dummy + 16 bit value
+        } else {
+            6 // 8 bit tag + 8bit dummy + 32 bit value
+        }
     }
 
-    /// Reserve room in a buffer to serialize this later.
-    ///
-    /// # Returns
-    ///
-    /// * The rest of the buffer after the reservation
+    /// Deserialize the request data of the Get_Attribute_List service
     ///
     /// # Arguments
     ///
-    /// * `buf` - The message buffer to write to
+    /// * `buf` - The message buffer to read from
     ///
     /// # Errors
     ///
-    /// An error variant will be returned if there is not enough room for the reservation.
+    /// An error variant will be returned if there is not enough data.
     ///
-    pub fn split_off(&self, buf: &mut BytesMut) -> Result<BytesMut, ErrorCode> {
-        eip::split_off(buf, self.serial_size_max())
+    fn deserialize_attribute_list(&mut self, buf: &mut dyn Buf) -> EipResult {
+        if buf.remaining() < size_of::<u16>() {
+            return Err(NOT_ENOUGH_DATA);
+        }
+        let count = buf.get_u16_le() as usize;
+        if buf.remaining() < count * size_of::<u16>() {
+            return Err(NOT_ENOUGH_DATA);
+        }
+        self.attribute_list = (0..count).map(|_| buf.get_u16_le()).collect();
+        Ok(())
+    }
+
+    /// Get the minumum serialized size in Bytes.
+    ///
+    /// # Returns
+    ///
+    /// * The minimum number of bytes when serialized
+    ///
+    const fn header_size() -> usize {
+        size_of::<u8>() // service
+        + size_of::<u8>() // path size
     }
 }
 
-impl Serializing for Response {
+impl Serializing for Request {
     /// Deserialize all fields
     ///
     /// # Arguments
@@ -267,34 +485,35 @@ impl Serializing for Response {
     ///
     /// # Errors
     ///
-    /// An error variant will be returned if there is not enough data.
+    /// An error variant will be returned if there is not enough data, a segment does not fit
+    /// in the path size or the path exceeds the maximum number of segments.
     ///
     fn deserialize(&mut self, buf: &mut dyn Buf) -> EipResult {
-        self.additional_status_size = 0;
-        if buf.remaining() < self.serial_size() {
-            return Err(NOT_ENOUGH_DATA);
-        }
-
-        self.service = buf.get_u8().into();
-        buf.get_u8();
-        self.general_status = buf.get_u8().into();
-        let size = buf.get_u8();
-        if buf.remaining() < (size as usize * 2) {
-            return Err(NOT_ENOUGH_DATA);
+        if buf.remaining() < Self::header_size() {
+            return Err(PATH_SEGMENT_ERROR);
         }
-        self.additional_status_size = size;
 
-        for i in 0..size {
-            if i < ADDITIONAL_STATUS_MAX {
-                self.additional_status[i as usize] = buf.get_u16_le();
-            } else {
-                self.additional_status_size = ADDITIONAL_STATUS_MAX;
-                warn!("message_router Response::deserialize() Too much additional status. Discarded: {:#04x}",
-                    buf.get_u16_le());
+        eip::read_atomic(buf, |buf| {
+            self.service = Service(buf.get_u8());
+            let size = buf.get_u8() as usize * 2; // path size in 16 bit words
+            if buf.remaining() < size {
+                return Err(PATH_SEGMENT_ERROR);
+            }
+            let mut path = buf.copy_to_bytes(size);
+            let mut count = 0;
+            while path.has_remaining() {
+                count += 1;
+                if count > Self::MAX_SEGMENTS {
+                    return Err(PATH_SEGMENT_ERROR);
+                }
+                self.deserialize_segment(&mut path)?;
             }
-        }
 
-        Ok(())
+            if self.service == Self::GET_ATTRIBUTE_LIST {
+                self.deserialize_attribute_list(buf)?;
+            }
+            Ok(())
+        })
     }
 
     /// Serialize all fields
@@ -308,33 +527,720 @@ impl Serializing for Response {
     /// An error variant will be returned if there is not enough room.
     ///
     fn serialize(&self, buf: &mut BytesMut) -> EipResult {
-        if buf.remaining_mut() < self.serial_size() {
+        let header_end = buf.len() + Self::header_size();
+        if eip::remaining_needed(buf, header_end) > 0 {
             return Err(REPLY_DATA_TOO_LARGE);
         }
+        let mut b = buf.split_off(header_end); // room for n
 
-        buf.put_u8(self.service.into());
-        buf.put_u8(0);
-        buf.put_u8(self.general_status.into());
-
-        let mut size = self.additional_status_size;
-
-        if size > ADDITIONAL_STATUS_MAX {
-            size = ADDITIONAL_STATUS_MAX;
-            warn!("message_router Response::serialize() Too much additional status, truncated. size: {}", size);
+        if let Some(k) = &self.key {
+            k.serialize(&mut b)?;
         }
-
-        buf.put_u8(size);
-        for i in 0..size {
-            buf.put_u16_le(self.additional_status[i as usize]);
+        if let Some(s) = &self.symbol {
+            Self::serialize_symbol(&mut b, s)?;
         }
-
-        Ok(())
-    }
-}
-
-#[test]
-fn request_deserialize_logic_8() {
-    // 8 bit 3 segments
+        if let Some(c) = self.class {
+            Self::serialize_logical(&mut b, c, Self::LEVEL_CLASS)?;
+            if let Some(i) = self.instance {
+                Self::serialize_logical(&mut b, i, Self::LEVEL_INSTANCE)?;
+                if let Some(a) = self.attribute {
+                    Self::serialize_logical(&mut b, a, Self::LEVEL_ATTRIBUTE)?;
+                    if let Some(m) = self.member {
+                        Self::serialize_logical(&mut b, m as u32, Self::LEVEL_MEMBER)?;
+                    }
+                }
+            }
+        }
+        if let Some(d) = &self.data_segment {
+            Self::serialize_data_segment(&mut b, d)?;
+        }
+
+        let size = b.len();
+        if size > Self::MAX_PATH_SIZE {
+            return Err(PATH_SEGMENT_ERROR);
+        }
+
+        if self.service == Self::GET_ATTRIBUTE_LIST {
+            if b.remaining_mut() < size_of::<u16>() * (1 + self.attribute_list.len()) {
+                return Err(REPLY_DATA_TOO_LARGE);
+            }
+            b.put_u16_le(self.attribute_list.len() as u16);
+            for a in &self.attribute_list {
+                b.put_u16_le(*a);
+            }
+        }
+
+        buf.put_u8(self.service.0);
+        buf.put_u8((size / 2) as u8); // path size in 16 bit words
+        buf.unsplit(b);
+        Ok(())
+    }
+
+    /// Get the serialized size in Bytes.
+    ///
+    /// # Returns
+    ///
+    /// * The number of bytes when serialized
+    ///
+    fn serial_size(&self) -> usize {
+        let mut size = Self::header_size();
+        if let Some(k) = &self.key {
+            size += k.serial_size();
+        }
+        if let Some(s) = &self.symbol {
+            size += Self::symbol_size(s);
+        }
+        if let Some(c) = self.class {
+            size += Self::logical_size(c);
+            if let Some(i) = self.instance {
+                size += Self::logical_size(i);
+                if let Some(a) = self.attribute {
+                    size += Self::logical_size(a);
+                    if let Some(m) = self.member {
+                        size += Self::logical_size(m as u32);
+                    }
+                }
+            }
+        }
+        if let Some(d) = &self.data_segment {
+            size += Self::data_segment_size(d);
+        }
+        if self.service == Self::GET_ATTRIBUTE_LIST {
+            size += size_of::<u16>() * (1 + self.attribute_list.len()); // count + identifiers
+        }
+        size
+    }
+}
+
+/// Electronic key segment, identifies the expected device of e.g. a Forward_Open.
+/// A zero vendor, device type, product code or major revision matches any device.
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ElectronicKey {
+    pub vendor_id: u16,
+    pub device_type: u16,
+    pub product_code: u16,
+    pub compatibility: bool, // Accept a device that can emulate the revision
+    pub revision: Revision,
+}
+
+impl ElectronicKey {
+    const FORMAT: u8 = 4;
+    const COMPATIBILITY: u8 = 0x80;
+
+    /// Deserialize the key format and the key, following the segment type
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to read from
+    ///
+    /// # Returns
+    ///
+    /// * The key
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if there is not enough data or the key format is not 4.
+    ///
+    fn deserialize_body(buf: &mut dyn Buf) -> Result<Self, ErrorCode> {
+        if buf.remaining() < 9 {
+            return Err(PATH_SEGMENT_ERROR);
+        }
+        if buf.get_u8() != Self::FORMAT {
+            return Err(PATH_SEGMENT_ERROR);
+        }
+        let vendor_id = buf.get_u16_le();
+        let device_type = buf.get_u16_le();
+        let product_code = buf.get_u16_le();
+        let major = buf.get_u8();
+        let minor = buf.get_u8();
+        Ok(ElectronicKey {
+            vendor_id,
+            device_type,
+            product_code,
+            compatibility: major & Self::COMPATIBILITY != 0,
+            revision: Revision::new(major & !Self::COMPATIBILITY, minor),
+        })
+    }
+
+    /// Test if the key accepts a device.
+    /// Without the compatibility bit the revision must be equal, with it the device
+    /// must have the same major and at least the minor revision.
+    ///
+    /// # Arguments
+    ///
+    /// * `identity` - The identity of the device
+    ///
+    /// # Returns
+    ///
+    /// True if the device matches the key, false otherwise.
+    ///
+    pub fn matches(&self, identity: &Identity) -> bool {
+        let wildcard = |key: u16, val: u16| key == 0 || key == val;
+        let revision = Revision::from(identity.revision.get());
+        wildcard(self.vendor_id, identity.vendor_id.get())
+            && wildcard(self.device_type, identity.device_type.get())
+            && wildcard(self.product_code, identity.product_code.get())
+            && (self.revision.major == 0
+                || (self.revision.major == revision.major
+                    && (self.revision.minor == revision.minor
+                        || (self.compatibility && self.revision.minor <= revision.minor))))
+    }
+}
+
+impl Serializing for ElectronicKey {
+    /// Deserialize the segment
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to read from
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if there is not enough data or this is not
+    /// an electronic key segment of key format 4.
+    ///
+    fn deserialize(&mut self, buf: &mut dyn Buf) -> EipResult {
+        if buf.remaining() < self.serial_size() {
+            return Err(PATH_SEGMENT_ERROR);
+        }
+        if buf.get_u8() != Request::LOGICAL_KEY {
+            return Err(PATH_SEGMENT_ERROR);
+        }
+        *self = Self::deserialize_body(buf)?;
+        Ok(())
+    }
+
+    /// Serialize the segment
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to write to
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if there is not enough room.
+    ///
+    fn serialize(&self, buf: &mut BytesMut) -> EipResult {
+        if buf.remaining_mut() < self.serial_size() {
+            return Err(REPLY_DATA_TOO_LARGE);
+        }
+        let mut major = self.revision.major & !Self::COMPATIBILITY;
+        if self.compatibility {
+            major |= Self::COMPATIBILITY;
+        }
+        buf.put_u8(Request::LOGICAL_KEY);
+        buf.put_u8(Self::FORMAT);
+        buf.put_u16_le(self.vendor_id);
+        buf.put_u16_le(self.device_type);
+        buf.put_u16_le(self.product_code);
+        buf.put_u8(major);
+        buf.put_u8(self.revision.minor);
+        Ok(())
+    }
+
+    /// Get the serialized size in Bytes.
+    ///
+    /// # Returns
+    ///
+    /// * The number of bytes when serialized
+    ///
+    fn serial_size(&self) -> usize {
+        size_of::<u8>() // segment type
+        + size_of::<u8>() // key format
+        + size_of::<u16>() // vendor_id
+        + size_of::<u16>() // device_type
+        + size_of::<u16>() // product_code
+        + size_of::<u8>() // major revision and compatibility
+        + size_of::<u8>() // minor revision
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Response {
+    pub service: Service,
+    pub general_status: ErrorCode,
+    pub additional_status: Vec<u16>,
+    pub data: BytesMut, // Response data, e.g. the attribute value
+}
+
+impl Response {
+    /// Create a successful response
+    ///
+    /// # Arguments
+    ///
+    /// * `service` - The service of the request, the response bit is set
+    ///
+    /// # Returns
+    ///
+    /// * The created instance
+    ///
+    pub fn success(service: Service) -> Self {
+        Response {
+            service: service.as_response(),
+            general_status: SUCCESS,
+            ..Default::default()
+        }
+    }
+
+    /// Create an error response
+    ///
+    /// # Arguments
+    ///
+    /// * `service` - The service of the request, the response bit is set
+    /// * `code` - The general status
+    ///
+    /// # Returns
+    ///
+    /// * The created instance
+    ///
+    pub fn error(service: Service, code: ErrorCode) -> Self {
+        Response {
+            general_status: code,
+            ..Self::success(service)
+        }
+    }
+
+    /// Create the response to a request
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The request, its service is echoed with the response bit set
+    /// * `code` - The general status
+    ///
+    /// # Returns
+    ///
+    /// * The created instance
+    ///
+    pub fn from_request(request: &Request, code: ErrorCode) -> Self {
+        Self::error(request.service, code)
+    }
+
+    /// Get the serialized size without the response data in Bytes.
+    ///
+    /// # Returns
+    ///
+    /// * The number of bytes of the status header when serialized
+    ///
+    fn header_size(&self) -> usize {
+        size_of::<u8>() // service
+        + size_of::<u8>() // reserved
+        + size_of::<u8>() // general_status
+        + size_of::<u8>() // additional_status_size
+        + (self.additional_status.len() * 2) // 2 because of 16 bit segments
+    }
+
+    /// Reserve room in a buffer to serialize this later.
+    ///
+    /// # Returns
+    ///
+    /// * The rest of the buffer after the reservation
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to write to
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if there is not enough room for the reservation.
+    ///
+    pub fn split_off(&self, buf: &mut BytesMut) -> Result<BytesMut, ErrorCode> {
+        eip::split_off(buf, self.header_size())
+    }
+
+    /// Serialize the reply data of the Get_Attribute_List service.
+    /// For each attribute the identifier, the status and, on success, the data is written.
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to write to
+    /// * `list` - The requested attribute identifiers
+    /// * `get` - Serializes one attribute of the target object
+    ///
+    /// # Returns
+    ///
+    /// * The general status of the reply, ATTRIBUTE_LIST_ERROR if any attribute failed
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if there is not enough room or the list is too long.
+    ///
+    pub fn serialize_attribute_list<F>(
+        buf: &mut BytesMut,
+        list: &[u16],
+        mut get: F,
+    ) -> Result<ErrorCode, ErrorCode>
+    where
+        F: FnMut(&mut BytesMut, u16) -> EipResult,
+    {
+        let mut general_status = SUCCESS;
+        if list.len() > u16::MAX as usize {
+            return Err(REPLY_DATA_TOO_LARGE);
+        }
+        if buf.remaining_mut() < size_of::<u16>() {
+            return Err(REPLY_DATA_TOO_LARGE);
+        }
+        buf.put_u16_le(list.len() as u16);
+
+        for id in list {
+            if buf.remaining_mut() < size_of::<u16>() + size_of::<u16>() {
+                return Err(REPLY_DATA_TOO_LARGE);
+            }
+            let start = buf.len();
+            buf.put_u16_le(*id);
+            buf.put_u16_le(u8::from(SUCCESS) as u16);
+            if let Err(e) = get(buf, *id) {
+                // discard partial data and patch the status
+                buf.truncate(start + size_of::<u16>() + size_of::<u16>());
+                let status = u8::from(e) as u16;
+                buf[start + 2..start + 4].copy_from_slice(&status.to_le_bytes());
+                general_status = ATTRIBUTE_LIST_ERROR;
+            }
+        }
+        Ok(general_status)
+    }
+
+    /// Handle the request data of the Set_Attribute_List service and serialize the reply data.
+    /// For each attribute the identifier and the status is written.
+    /// The attribute data is variable length, so the object must consume exactly its own data.
+    /// Processing stops at the first failing attribute, the reply only lists the processed ones.
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to write to
+    /// * `data` - The request data, the attribute count followed by identifier and data pairs
+    /// * `set` - Deserializes one attribute of the target object
+    ///
+    /// # Returns
+    ///
+    /// * The general status of the reply, ATTRIBUTE_LIST_ERROR if an attribute failed
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if the request data is incomplete or there is not enough room.
+    ///
+    pub fn serialize_set_attribute_list<F>(
+        buf: &mut BytesMut,
+        data: &mut dyn Buf,
+        mut set: F,
+    ) -> Result<ErrorCode, ErrorCode>
+    where
+        F: FnMut(&mut dyn Buf, u16) -> EipResult,
+    {
+        if data.remaining() < size_of::<u16>() {
+            return Err(NOT_ENOUGH_DATA);
+        }
+        let count = data.get_u16_le();
+
+        if buf.remaining_mut() < size_of::<u16>() {
+            return Err(REPLY_DATA_TOO_LARGE);
+        }
+        let start = buf.len();
+        buf.put_u16_le(0);
+
+        let mut processed: u16 = 0;
+        while processed < count {
+            if data.remaining() < size_of::<u16>() {
+                return Err(NOT_ENOUGH_DATA);
+            }
+            if buf.remaining_mut() < size_of::<u16>() + size_of::<u16>() {
+                return Err(REPLY_DATA_TOO_LARGE);
+            }
+            let id = data.get_u16_le();
+            let status = match set(data, id) {
+                Ok(()) => SUCCESS,
+                Err(e) => e,
+            };
+            buf.put_u16_le(id);
+            buf.put_u16_le(u8::from(status) as u16);
+            processed += 1;
+            if status != SUCCESS {
+                buf[start..start + 2].copy_from_slice(&processed.to_le_bytes());
+                return Ok(ATTRIBUTE_LIST_ERROR); // the length of the remaining data is unknown
+            }
+        }
+        buf[start..start + 2].copy_from_slice(&processed.to_le_bytes());
+        Ok(SUCCESS)
+    }
+}
+
+impl Serializing for Response {
+    /// Deserialize all fields
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to read from
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if there is not enough data.
+    ///
+    fn deserialize(&mut self, buf: &mut dyn Buf) -> EipResult {
+        self.additional_status.clear();
+        self.data.clear();
+        if buf.remaining() < self.serial_size() {
+            return Err(NOT_ENOUGH_DATA);
+        }
+
+        self.service = buf.get_u8().into();
+        buf.get_u8();
+        self.general_status = buf.get_u8().into();
+        let size = buf.get_u8();
+        if buf.remaining() < (size as usize * 2) {
+            return Err(NOT_ENOUGH_DATA);
+        }
+        self.additional_status = (0..size).map(|_| buf.get_u16_le()).collect();
+
+        self.data = BytesMut::from(&buf.copy_to_bytes(buf.remaining())[..]);
+        Ok(())
+    }
+
+    /// Serialize all fields
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to write to
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if there is not enough room.
+    ///
+    fn serialize(&self, buf: &mut BytesMut) -> EipResult {
+        if buf.remaining_mut() < self.serial_size()
+            || self.additional_status.len() > u8::MAX as usize
+        {
+            return Err(REPLY_DATA_TOO_LARGE);
+        }
+
+        buf.put_u8(self.service.into());
+        buf.put_u8(0);
+        buf.put_u8(self.general_status.into());
+        buf.put_u8(self.additional_status.len() as u8);
+        for s in &self.additional_status {
+            buf.put_u16_le(*s);
+        }
+        buf.put_slice(&self.data);
+        Ok(())
+    }
+
+    /// Get the serialized size in Bytes.
+    ///
+    /// # Returns
+    ///
+    /// * The number of bytes when serialized
+    ///
+    fn serial_size(&self) -> usize {
+        self.header_size() + self.data.len()
+    }
+}
+
+/// An object instance that can be addressed through the Router
+pub trait Object: Debug + Send + Sync {
+    /// Serialize one specific attribute
+    fn get_attribute(&self, buf: &mut BytesMut, attr: u16) -> EipResult;
+
+    /// Deserialize one specific attribute
+    fn set_attribute(&mut self, buf: &mut dyn Buf, attr: u16) -> EipResult;
+
+    /// Handle an object specific service
+    fn service(&mut self, _req: &Request, _data: &mut dyn Buf, _buf: &mut BytesMut) -> EipResult {
+        Err(SERVICE_NOT_SUPPORTED)
+    }
+
+    /// Create a dynamic instance, called on the class level object (instance 0)
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - The request data, the initial attribute values
+    ///
+    /// # Returns
+    ///
+    /// * The instance number and the instance to register
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if the class does not support dynamic instances.
+    ///
+    fn create_instance(
+        &mut self,
+        _data: &mut dyn Buf,
+    ) -> Result<(u16, Box<dyn Object>), ErrorCode> {
+        Err(ATTRIBUTE_NOT_SUPPORTED)
+    }
+
+    /// Get the class level attributes, served by the Router at instance 0
+    /// when no class level object is registered.
+    fn class_attributes(&self) -> Option<&StaticAttr> {
+        None
+    }
+
+    /// Prepare the removal of a dynamic instance, called on the instance itself
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if the instance can not be deleted, it stays registered.
+    ///
+    fn delete_instance(&mut self) -> EipResult {
+        Err(ATTRIBUTE_NOT_SUPPORTED)
+    }
+}
+
+/// The Message Router object, routes requests to the registered objects
+
+#[derive(Debug, Default)]
+pub struct Router {
+    objects: BTreeMap<(u32, u32), Box<dyn Object>>, // keyed by class and instance
+}
+
+impl Router {
+    pub const CLASS: u32 = 0x02;
+
+    /// Register an object instance
+    ///
+    /// # Arguments
+    ///
+    /// * `class` - The class identifier
+    /// * `instance` - The instance identifier, 0 for the class itself
+    /// * `object` - The object to route the requests to
+    ///
+    /// # Returns
+    ///
+    /// * The previously registered object with the same class and instance
+    ///
+    pub fn register(
+        &mut self,
+        class: u32,
+        instance: u32,
+        object: Box<dyn Object>,
+    ) -> Option<Box<dyn Object>> {
+        self.objects.insert((class, instance), object)
+    }
+
+    /// Route a request to the addressed object and build the response.
+    /// A No_Operation request always succeeds without data, it is used as keepalive.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The request with the path
+    /// * `payload` - The request data following the path
+    ///
+    /// # Returns
+    ///
+    /// * The response with the status and the response data
+    ///
+    pub fn dispatch(&mut self, request: &Request, payload: &mut dyn Buf) -> Response {
+        let mut res = Response::from_request(request, SUCCESS);
+        if request.service == Request::NO_OPERATION {
+            return res;
+        }
+        match self.route(request, payload, &mut res) {
+            Ok(()) => res,
+            Err(e) => Response::from_request(request, e),
+        }
+    }
+
+    /// Route a request to the addressed object
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The request with the path
+    /// * `payload` - The request data following the path
+    /// * `res` - The response to write the data and, for the attribute lists, the status to
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if the object or attribute does not exist or the
+    /// service fails.
+    ///
+    fn route(&mut self, request: &Request, payload: &mut dyn Buf, res: &mut Response) -> EipResult {
+        let Response {
+            general_status,
+            data: buf,
+            ..
+        } = res;
+        let class = request.class.ok_or(PATH_DESTINATION_UNKNOWN)?;
+        let key = (class, request.instance.unwrap_or(0));
+        let attr = || match request.attribute.map(u16::try_from) {
+            Some(Ok(a)) => Ok(a),
+            Some(Err(_)) => Err(ATTRIBUTE_NOT_SUPPORTED),
+            None => Err(PATH_SEGMENT_ERROR),
+        };
+
+        if key.1 == 0 && !self.objects.contains_key(&key) {
+            let class_attr = self
+                .objects
+                .range((class, 1)..=(class, u32::MAX))
+                .find_map(|(_, o)| o.class_attributes())
+                .ok_or(PATH_DESTINATION_UNKNOWN)?;
+            return match request.service {
+                Request::GET_ATTRIBUTE_SINGLE => {
+                    class_attr.serialize_attribute_single(buf, attr()?.into())
+                }
+                Request::GET_ATTRIBUTE_ALL => class_attr.serialize_all_attributes(buf),
+                _ => Err(SERVICE_NOT_SUPPORTED),
+            };
+        }
+
+        let object = self.objects.get_mut(&key).ok_or(PATH_DESTINATION_UNKNOWN)?;
+
+        match request.service {
+            Request::GET_ATTRIBUTE_SINGLE => object.get_attribute(buf, attr()?),
+            Request::SET_ATTRIBUTE_SINGLE => object.set_attribute(payload, attr()?),
+            Request::GET_ATTRIBUTE_LIST => {
+                *general_status =
+                    Response::serialize_attribute_list(buf, &request.attribute_list, |b, a| {
+                        object.get_attribute(b, a)
+                    })?;
+                Ok(())
+            }
+            Request::SET_ATTRIBUTE_LIST => {
+                *general_status = Response::serialize_set_attribute_list(buf, payload, |b, a| {
+                    object.set_attribute(b, a)
+                })?;
+                Ok(())
+            }
+            Request::CREATE => {
+                if buf.remaining_mut() < size_of::<u16>() {
+                    return Err(REPLY_DATA_TOO_LARGE);
+                }
+                let (instance, mut created) = object.create_instance(payload)?;
+                match self.objects.entry((class, instance as u32)) {
+                    Entry::Vacant(e) => e.insert(created),
+                    Entry::Occupied(_) => {
+                        // instance in use, undo the creation, it is dropped regardless
+                        let _ = created.delete_instance();
+                        return Err(INSUFFICIENT_MEMORY);
+                    }
+                };
+                buf.put_u16_le(instance);
+                Ok(())
+            }
+            Request::DELETE => {
+                object.delete_instance()?;
+                self.objects.remove(&key);
+                Ok(())
+            }
+            _ => object.service(request, payload, buf),
+        }
+    }
+}
+
+#[test]
+fn service_response_bit() {
+    let service = Request::GET_ATTRIBUTE_SINGLE;
+    assert!(!service.is_response());
+    assert_eq!(Service(0x8e), service.as_response());
+    assert!(service.as_response().is_response());
+    assert_eq!(service, service.as_response().as_request());
+    assert_eq!(service, service.as_request());
+    assert_eq!(service.as_response(), service.as_response().as_response());
+}
+
+#[test]
+fn request_deserialize_logic_8() {
+    // 8 bit 3 segments
     let mut request = Request::default();
     let mut buf = &b"\x0e\x03\x20\x12\x24\x34\x30\x56"[..];
     assert_eq!(Ok(()), request.deserialize(&mut buf));
@@ -356,7 +1262,7 @@ fn request_deserialize_logic_8() {
 #[test]
 fn request_deserialize_logic_16() {
     // 16 bit 3 segments
-    let mut buf = &b"\x0e\x03\x21\0\x34\x12\x25\0\x78\x56\x31\0\x12\x90"[..];
+    let mut buf = &b"\x0e\x06\x21\0\x34\x12\x25\0\x78\x56\x31\0\x12\x90"[..];
     let mut request = Request::default();
     assert_eq!(Ok(()), request.deserialize(&mut buf));
     assert_eq!(0, buf.len());
@@ -365,6 +1271,23 @@ fn request_deserialize_logic_16() {
     assert_eq!(Some(0x9012), request.attribute);
 }
 
+#[test]
+fn request_deserialize_logic_32() {
+    // 32 bit 3 segments
+    let mut buf = &b"\x0e\x09\x22\0\x78\x56\x34\x12\x26\0\x01\0\x01\0\x32\0\xff\xff\xff\xff"[..];
+    let mut request = Request::default();
+    assert_eq!(Ok(()), request.deserialize(&mut buf));
+    assert_eq!(0, buf.len());
+    assert_eq!(Some(0x12345678), request.class);
+    assert_eq!(Some(0x00010001), request.instance);
+    assert_eq!(Some(0xffffffff), request.attribute);
+
+    // 32 bit segment, one missing byte
+    buf = &b"\x0e\x03\x22\0\x78\x56\x34"[..];
+    request = Request::default();
+    assert_eq!(Err(PATH_SEGMENT_ERROR), request.deserialize(&mut buf));
+}
+
 #[test]
 fn request_deserialize_logic_bounds() {
     // no data, empty buffer
@@ -388,14 +1311,55 @@ fn request_deserialize_logic_bounds() {
     assert_eq!(Err(PATH_SEGMENT_ERROR), request.deserialize(&mut buf));
 }
 
+#[test]
+fn request_deserialize_segment_count() {
+    // pathological count of repeated minimal segments, rejected without consuming any
+    let mut data = BytesMut::from(&b"\x0e\xff"[..]);
+    for _ in 0..255 {
+        data.put_slice(b"\x20\x01");
+    }
+    let mut buf = &data[..];
+    let mut request = Request::default();
+    assert_eq!(Err(PATH_SEGMENT_ERROR), request.deserialize(&mut buf));
+    assert_eq!(512, buf.len());
+
+    // maximum number of segments
+    data = BytesMut::from(&b"\x0e\x08"[..]);
+    for _ in 0..8 {
+        data.put_slice(b"\x20\x01");
+    }
+    request = Request::default();
+    assert_eq!(Ok(()), request.deserialize(&mut &data[..]));
+    assert_eq!(Some(1), request.class);
+
+    // a segment exceeding the path size
+    request = Request::default();
+    assert_eq!(
+        Err(PATH_SEGMENT_ERROR),
+        request.deserialize(&mut &b"\x0e\x02\x91\x04Tag1"[..])
+    );
+
+    // symbol and data exceeding the maximum path size
+    request = Request {
+        service: Service(0x0e),
+        symbol: Some("a".repeat(200)),
+        data_segment: Some(BytesMut::from(&[0; 400][..])),
+        ..Default::default()
+    };
+    let mut out = BytesMut::with_capacity(1000);
+    assert_eq!(Err(PATH_SEGMENT_ERROR), request.serialize(&mut out));
+}
+
 #[test]
 fn request_serialize_logic_8() {
     // 8 bit 3 segments
-    let mut request = Request::default();
-    request.service = Service(0x0e);
-    request.class = Some(0x12);
-    request.instance = Some(0x34);
-    request.attribute = Some(0x56);
+    let mut request = Request {
+        service: Service(0x0e),
+        class: Some(0x12),
+        instance: Some(0x34),
+        attribute: Some(0x56),
+        ..Default::default()
+    };
 
     let mut buf = BytesMut::with_capacity(100);
 
@@ -410,33 +1374,274 @@ fn request_serialize_logic_8() {
 
     assert_eq!(Ok(()), request.serialize(&mut buf));
     assert_eq!(6, buf.len());
+    assert_eq!(6, request.serial_size());
     assert_eq!(&b"\x0e\x02\x20\x12\x24\x34"[..], buf);
 }
 
+#[test]
+fn request_serialize_small_buffer() {
+    let request = Request::get_attribute_single(1, 1, 7);
+
+    // no room for the service and the path size
+    let mut buf = BytesMut::with_capacity(1);
+    assert_eq!(Err(REPLY_DATA_TOO_LARGE), request.serialize(&mut buf));
+    assert!(buf.is_empty());
+
+    // after data already in the buffer
+    buf = BytesMut::with_capacity(3);
+    buf.put_u16_le(0xabcd);
+    assert_eq!(Err(REPLY_DATA_TOO_LARGE), request.serialize(&mut buf));
+
+    // the segments grow the buffer
+    buf = BytesMut::with_capacity(2);
+    assert_eq!(Ok(()), request.serialize(&mut buf));
+    assert_eq!(&b"\x0e\x03\x20\x01\x24\x01\x30\x07"[..], buf);
+}
+
 #[test]
 fn request_serialize_logic_16() {
     // 16 bit 3 segments
-    let mut request = Request::default();
-    request.service = Service(0x0e);
-    request.class = Some(0x1234);
-    request.instance = Some(0x5678);
-    request.attribute = Some(0x9012);
+    let request = Request {
+        service: Service(0x0e),
+        class: Some(0x1234),
+        instance: Some(0x5678),
+        attribute: Some(0x9012),
+        ..Default::default()
+    };
 
     let mut buf = BytesMut::with_capacity(100);
 
     assert_eq!(Ok(()), request.serialize(&mut buf));
     assert_eq!(14, buf.len());
+    assert_eq!(14, request.serial_size());
+    assert_eq!(
+        &b"\x0e\x06\x21\0\x34\x12\x25\0\x78\x56\x31\0\x12\x90"[..],
+        buf
+    );
+}
+
+#[test]
+fn request_serialize_logic_32() {
+    // 32 bit 3 segments
+    let request = Request {
+        service: Service(0x0e),
+        class: Some(0x12345678),
+        instance: Some(0x00010001),
+        attribute: Some(0xffffffff),
+        ..Default::default()
+    };
+
+    let mut buf = BytesMut::with_capacity(100);
+
+    assert_eq!(Ok(()), request.serialize(&mut buf));
+    assert_eq!(20, buf.len());
+    assert_eq!(20, request.serial_size());
     assert_eq!(
-        &b"\x0e\x03\x21\0\x34\x12\x25\0\x78\x56\x31\0\x12\x90"[..],
+        &b"\x0e\x09\x22\0\x78\x56\x34\x12\x26\0\x01\0\x01\0\x32\0\xff\xff\xff\xff"[..],
         buf
     );
 }
 
+#[test]
+fn request_symbolic() {
+    // even length, no pad byte
+    let mut buf = &b"\x4c\x03\x91\x04Tag1"[..];
+    let mut request = Request::default();
+    assert_eq!(Ok(()), request.deserialize(&mut buf));
+    assert_eq!(0, buf.len());
+    assert_eq!(Some("Tag1".to_string()), request.symbol);
+
+    let mut out = BytesMut::with_capacity(100);
+    assert_eq!(Ok(()), request.serialize(&mut out));
+    assert_eq!(8, request.serial_size());
+    assert_eq!(&b"\x4c\x03\x91\x04Tag1"[..], out);
+
+    // odd length, with pad byte
+    buf = &b"\x4c\x03\x91\x03Tag\0"[..];
+    request = Request::default();
+    assert_eq!(Ok(()), request.deserialize(&mut buf));
+    assert_eq!(0, buf.len());
+    assert_eq!(Some("Tag".to_string()), request.symbol);
+
+    out.clear();
+    assert_eq!(Ok(()), request.serialize(&mut out));
+    assert_eq!(8, request.serial_size());
+    assert_eq!(&b"\x4c\x03\x91\x03Tag\0"[..], out);
+
+    // missing pad byte
+    buf = &b"\x4c\x03\x91\x03Tag"[..];
+    request = Request::default();
+    assert_eq!(Err(PATH_SEGMENT_ERROR), request.deserialize(&mut buf));
+}
+
+#[test]
+fn request_member() {
+    let data = &b"\x0e\x05\x20\x04\x24\x01\x30\x03\x29\0\x34\x12"[..];
+    let mut buf = data;
+    let mut request = Request::default();
+    assert_eq!(Ok(()), request.deserialize(&mut buf));
+    assert_eq!(0, buf.len());
+    assert_eq!(Some(4), request.class);
+    assert_eq!(Some(1), request.instance);
+    assert_eq!(Some(3), request.attribute);
+    assert_eq!(Some(0x1234), request.member);
+
+    let mut out = BytesMut::with_capacity(100);
+    assert_eq!(Ok(()), request.serialize(&mut out));
+    assert_eq!(request.serial_size(), out.len());
+    assert_eq!(data, out);
+
+    // member out of range
+    buf = &b"\x0e\x03\x2a\0\0\0\x01\0"[..];
+    request = Request::default();
+    assert_eq!(Err(PATH_SEGMENT_ERROR), request.deserialize(&mut buf));
+}
+
+#[test]
+fn request_attribute_list() {
+    let mut buf = &b"\x03\x02\x20\x01\x24\x01\x02\0\x01\0\x07\0"[..];
+    let mut request = Request::default();
+    assert_eq!(Ok(()), request.deserialize(&mut buf));
+    assert_eq!(0, buf.len());
+    assert_eq!(Request::GET_ATTRIBUTE_LIST, request.service);
+    assert_eq!(vec![1, 7], request.attribute_list);
+
+    let mut out = BytesMut::with_capacity(100);
+    assert_eq!(Ok(()), request.serialize(&mut out));
+    assert_eq!(12, request.serial_size());
+    assert_eq!(&b"\x03\x02\x20\x01\x24\x01\x02\0\x01\0\x07\0"[..], out);
+
+    // one identifier missing
+    buf = &b"\x03\x02\x20\x01\x24\x01\x02\0\x01\0"[..];
+    request = Request::default();
+    assert_eq!(Err(NOT_ENOUGH_DATA), request.deserialize(&mut buf));
+}
+
+#[test]
+fn request_builder() {
+    let mut buf = BytesMut::with_capacity(100);
+    let request = Request::get_attribute_single(0x01, 1, 7);
+    assert_eq!(Ok(()), request.serialize(&mut buf));
+    assert_eq!(request.serial_size(), buf.len());
+    assert_eq!(&b"\x0e\x03\x20\x01\x24\x01\x30\x07"[..], buf);
+
+    buf.clear();
+    let request = Request::set_attribute_single(0x04, 0x64, 3);
+    assert_eq!(Ok(()), request.serialize(&mut buf));
+    assert_eq!(&b"\x0f\x03\x20\x04\x24\x64\x30\x03"[..], buf);
+
+    buf.clear();
+    let request = Request::get_attribute_all(0x01, 1);
+    assert_eq!(Ok(()), request.serialize(&mut buf));
+    assert_eq!(&b"\x01\x02\x20\x01\x24\x01"[..], buf);
+
+    let mut received = Request::default();
+    assert_eq!(Ok(()), received.deserialize(&mut &buf[..]));
+    assert_eq!(request, received);
+}
+
+#[test]
+fn request_data_segment() {
+    let mut request = Request::default();
+    let mut buf = &b"\x54\x05\x20\x04\x24\x97\x80\x02\x01\x02\x03\x04"[..];
+    assert_eq!(Ok(()), request.deserialize(&mut buf));
+    assert_eq!(0, buf.len());
+    assert_eq!(Some(0x04), request.class);
+    assert_eq!(Some(0x97), request.instance);
+    assert_eq!(
+        Some(BytesMut::from(&b"\x01\x02\x03\x04"[..])),
+        request.data_segment
+    );
+
+    let mut out = BytesMut::with_capacity(20);
+    assert_eq!(Ok(()), request.serialize(&mut out));
+    assert_eq!(12, request.serial_size());
+    assert_eq!(
+        &b"\x54\x05\x20\x04\x24\x97\x80\x02\x01\x02\x03\x04"[..],
+        out
+    );
+
+    // truncated
+    request = Request::default();
+    buf = &b"\x54\x03\x80\x02\x01\x02\x03"[..];
+    assert_eq!(Err(PATH_SEGMENT_ERROR), request.deserialize(&mut buf));
+    buf = &b"\x54\x01\x80"[..];
+    assert_eq!(Err(PATH_SEGMENT_ERROR), request.deserialize(&mut buf));
+
+    // not a whole number of words
+    request.data_segment = Some(BytesMut::from(&b"\x01"[..]));
+    assert_eq!(Err(PATH_SEGMENT_ERROR), request.serialize(&mut out));
+}
+
+#[test]
+fn request_electronic_key() {
+    let mut request = Request::default();
+    let path = b"\x54\x07\x34\x04\x01\0\x02\0\x03\0\x84\x01\x20\x04\x24\x97";
+    let mut buf = &path[..];
+    assert_eq!(Ok(()), request.deserialize(&mut buf));
+    assert_eq!(0, buf.len());
+    assert_eq!(Some(0x04), request.class);
+    assert_eq!(
+        Some(ElectronicKey {
+            vendor_id: 1,
+            device_type: 2,
+            product_code: 3,
+            compatibility: true,
+            revision: Revision::new(4, 1),
+        }),
+        request.key
+    );
+
+    let mut out = BytesMut::with_capacity(20);
+    assert_eq!(Ok(()), request.serialize(&mut out));
+    assert_eq!(path.len(), request.serial_size());
+    assert_eq!(&path[..], out);
+
+    // key format 5 and truncated
+    buf = &b"\x54\x05\x34\x05\x01\0\x02\0\x03\0\x84\x01"[..];
+    assert_eq!(Err(PATH_SEGMENT_ERROR), request.deserialize(&mut buf));
+    buf = &path[..11];
+    assert_eq!(Err(PATH_SEGMENT_ERROR), request.deserialize(&mut buf));
+}
+
+#[test]
+fn electronic_key_matches() {
+    let identity = Identity::new(1, 2, 3, Revision::new(4, 2), 5, str::to_string("Hello"));
+    let mut key = ElectronicKey {
+        vendor_id: 1,
+        device_type: 2,
+        product_code: 3,
+        compatibility: false,
+        revision: Revision::new(4, 2),
+    };
+    assert!(key.matches(&identity));
+    assert!(ElectronicKey::default().matches(&identity)); // all wildcards
+
+    // an older minor revision only with the compatibility bit
+    key.revision.minor = 1;
+    assert!(!key.matches(&identity));
+    key.compatibility = true;
+    assert!(key.matches(&identity));
+
+    key.revision.minor = 3;
+    assert!(!key.matches(&identity));
+    key.revision = Revision::new(5, 0);
+    assert!(!key.matches(&identity));
+    key.revision = Revision::new(4, 2);
+    key.product_code = 4;
+    assert!(!key.matches(&identity));
+    key.product_code = 0;
+    key.vendor_id = 9;
+    assert!(!key.matches(&identity));
+}
+
 #[test]
 fn response_serialize() {
-    let mut res = Response::default();
-    res.service = Request::GET_ATTRIBUTE_SINGLE;
-    res.general_status = REPLY_DATA_TOO_LARGE;
+    let mut res = Response {
+        service: Request::GET_ATTRIBUTE_SINGLE,
+        general_status: REPLY_DATA_TOO_LARGE,
+        ..Default::default()
+    };
 
     let mut buf = BytesMut::with_capacity(100);
 
@@ -445,23 +1650,89 @@ fn response_serialize() {
     assert_eq!(&b"\x0e\0\x11\0"[..], buf);
 
     buf = BytesMut::with_capacity(100);
-    res.additional_status_size = 2;
-    res.additional_status[0] = 0x1234;
-    res.additional_status[1] = 0x5678;
+    res.additional_status = vec![0x1234, 0x5678];
 
     assert_eq!(Ok(()), res.serialize(&mut buf));
     assert_eq!(8, buf.len());
     assert_eq!(&b"\x0e\0\x11\x02\x34\x12\x78\x56"[..], buf);
 }
 
+#[test]
+fn response_builder() {
+    let mut buf = BytesMut::with_capacity(100);
+    let res = Response::success(Request::GET_ATTRIBUTE_SINGLE);
+    assert_eq!(Ok(()), res.serialize(&mut buf));
+    assert_eq!(&b"\x8e\0\0\0"[..], buf);
+
+    buf.clear();
+    let res = Response::error(Request::SET_ATTRIBUTE_SINGLE, ATTRIBUTE_NOT_SUPPORTED);
+    assert_eq!(Ok(()), res.serialize(&mut buf));
+    assert_eq!(&b"\x8f\0\x14\0"[..], buf);
+    assert!(res.additional_status.is_empty());
+    assert!(res.data.is_empty());
+}
+
 #[test]
 fn response_deserialize() {
     let mut res = Response::default();
     let mut buf = &b"\x0e\0\x11\0"[..];
     assert_eq!(Ok(()), res.deserialize(&mut buf));
     assert_eq!(0, buf.len());
-    assert_eq!(Request::GET_ATTRIBUTE_SINGLE, res.service.into());
-    assert_eq!(REPLY_DATA_TOO_LARGE, res.general_status.into());
+    assert_eq!(Request::GET_ATTRIBUTE_SINGLE, res.service);
+    assert_eq!(REPLY_DATA_TOO_LARGE, res.general_status);
+}
+
+#[test]
+fn response_data() {
+    let mut res = Response {
+        service: Request::GET_ATTRIBUTE_SINGLE,
+        ..Default::default()
+    };
+    res.data.put_u16_le(0x1234);
+
+    let mut buf = BytesMut::with_capacity(100);
+    assert_eq!(Ok(()), res.serialize(&mut buf));
+    assert_eq!(6, res.serial_size());
+    assert_eq!(&b"\x0e\0\0\0\x34\x12"[..], buf);
+
+    let mut received = Response::default();
+    assert_eq!(Ok(()), received.deserialize(&mut &buf[..]));
+    assert_eq!(res, received);
+}
+
+#[test]
+fn response_additional_status() {
+    let res = Response {
+        service: Request::GET_ATTRIBUTE_SINGLE,
+        general_status: REPLY_DATA_TOO_LARGE,
+        additional_status: vec![0x1234, 0x5678, 0x9abc],
+        ..Default::default()
+    };
+
+    let mut buf = BytesMut::with_capacity(100);
+    assert_eq!(Ok(()), res.serialize(&mut buf));
+    assert_eq!(10, res.serial_size());
+    assert_eq!(&b"\x0e\0\x11\x03\x34\x12\x78\x56\xbc\x9a"[..], buf);
+
+    let mut received = Response::default();
+    assert_eq!(Ok(()), received.deserialize(&mut &buf[..]));
+    assert_eq!(res, received);
+}
+
+#[test]
+fn response_from_request() {
+    let req = Request::get_attribute_single(0x01, 1, 7);
+    let res = Response::from_request(&req, SUCCESS);
+    assert_eq!(Service(0x8e), res.service);
+    assert_eq!(SUCCESS, res.general_status);
+
+    let mut buf = BytesMut::with_capacity(10);
+    assert_eq!(Ok(()), res.serialize(&mut buf));
+    assert_eq!(&b"\x8e\0\0\0"[..], buf);
+
+    let res = Response::from_request(&req, ATTRIBUTE_NOT_SUPPORTED);
+    assert_eq!(Service(0x8e), res.service);
+    assert_eq!(ATTRIBUTE_NOT_SUPPORTED, res.general_status);
 }
 
 #[test]
@@ -474,11 +1745,212 @@ fn response_deserialize_bounds() {
     assert_eq!(Err(NOT_ENOUGH_DATA), res.deserialize(&mut buf));
 }
 
+#[test]
+fn router_dispatch() {
+    let mut router = Router::default();
+    let identity = Identity::new(1, 2, 3, Revision::new(4, 0), 5, str::to_string("Hello"));
+    assert!(router
+        .register(Identity::CLASS, 1, Box::new(identity))
+        .is_none());
+
+    let mut req = Request {
+        service: Request::GET_ATTRIBUTE_SINGLE,
+        class: Some(Identity::CLASS),
+        instance: Some(1),
+        attribute: Some(1),
+        ..Default::default()
+    };
+    let res = router.dispatch(&req, &mut &b""[..]);
+    assert_eq!(Service(0x8e), res.service);
+    assert_eq!(SUCCESS, res.general_status);
+    assert_eq!(&b"\x01\0"[..], res.data);
+
+    req.attribute = Some(0x20);
+    let res = router.dispatch(&req, &mut &b""[..]);
+    assert_eq!(ATTRIBUTE_NOT_SUPPORTED, res.general_status);
+    assert_eq!(0, res.data.len());
+
+    req.class = Some(0x99);
+    let res = router.dispatch(&req, &mut &b""[..]);
+    assert_eq!(PATH_DESTINATION_UNKNOWN, res.general_status);
+
+    req.class = Some(Identity::CLASS);
+    req.service = Request::FORWARD_OPEN;
+    let res = router.dispatch(&req, &mut &b""[..]);
+    assert_eq!(SERVICE_NOT_SUPPORTED, res.general_status);
+    // one attribute of the list fails, the data is kept with the list error status
+    req.service = Request::GET_ATTRIBUTE_LIST;
+    req.attribute = None;
+    req.attribute_list = vec![1, 0x20];
+    let res = router.dispatch(&req, &mut &b""[..]);
+    assert_eq!(ATTRIBUTE_LIST_ERROR, res.general_status);
+    assert_eq!(&b"\x02\0\x01\0\0\0\x01\0\x20\0\x14\0"[..], res.data);
+
+    req.attribute_list = vec![1];
+    let res = router.dispatch(&req, &mut &b""[..]);
+    assert_eq!(SUCCESS, res.general_status);
+}
+
+#[test]
+fn router_dispatch_no_operation() {
+    let mut router = Router::default();
+    let req = Request {
+        service: Request::NO_OPERATION,
+        class: Some(Router::CLASS),
+        instance: Some(1),
+        ..Default::default()
+    };
+    let res = router.dispatch(&req, &mut &b"\x01\x02"[..]);
+    assert_eq!(Service(0x97), res.service);
+    assert_eq!(SUCCESS, res.general_status);
+    assert_eq!(0, res.data.len());
+
+    let mut buf = BytesMut::with_capacity(10);
+    assert_eq!(Ok(()), res.serialize(&mut buf));
+    assert_eq!(&b"\x97\0\0\0"[..], buf);
+}
+
+#[test]
+fn router_create_delete() {
+    use crate::attr::{AccessCode, Uint};
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Debug, Default)]
+    struct Dynamic {
+        next: u16,
+        value: Uint,
+    }
+
+    impl Object for Dynamic {
+        fn get_attribute(&self, buf: &mut BytesMut, _attr: u16) -> EipResult {
+            self.value.serialize(buf)
+        }
+
+        fn set_attribute(&mut self, _buf: &mut dyn Buf, _attr: u16) -> EipResult {
+            Err(crate::error_code::ATTRIBUTE_NOT_SETTABLE)
+        }
+
+        fn create_instance(
+            &mut self,
+            data: &mut dyn Buf,
+        ) -> Result<(u16, Box<dyn Object>), ErrorCode> {
+            let mut value = Uint::new(0, AccessCode::get_set());
+            value.deserialize(data)?;
+            self.next += 1;
+            Ok((self.next, Box::new(Dynamic { next: 0, value })))
+        }
+
+        fn delete_instance(&mut self) -> EipResult {
+            DELETED.fetch_add(1, Ordering::Relaxed);
+            Ok(())
+        }
+    }
+
+    static DELETED: AtomicUsize = AtomicUsize::new(0);
+
+    let mut router = Router::default();
+    router.register(0x64, 0, Box::<Dynamic>::default());
+
+    let mut req = Request {
+        service: Request::CREATE,
+        class: Some(0x64),
+        instance: Some(0),
+        ..Default::default()
+    };
+    let res = router.dispatch(&req, &mut &b"\x34\x12"[..]);
+    assert_eq!(Service(0x88), res.service);
+    assert_eq!(SUCCESS, res.general_status);
+    assert_eq!(&b"\x01\0"[..], res.data);
+
+    req.service = Request::GET_ATTRIBUTE_SINGLE;
+    req.instance = Some(1);
+    req.attribute = Some(1);
+    let res = router.dispatch(&req, &mut &b""[..]);
+    assert_eq!(&b"\x34\x12"[..], res.data);
+
+    req.service = Request::DELETE;
+    req.attribute = None;
+    let res = router.dispatch(&req, &mut &b""[..]);
+    assert_eq!(Service(0x89), res.service);
+    assert_eq!(SUCCESS, res.general_status);
+    assert_eq!(1, DELETED.load(Ordering::Relaxed));
+    let res = router.dispatch(&req, &mut &b""[..]);
+    assert_eq!(PATH_DESTINATION_UNKNOWN, res.general_status);
+
+    // the next instance number is in use, the created instance is undone
+    router.register(0x64, 2, Box::new(Dynamic::default()));
+    req.service = Request::CREATE;
+    req.instance = Some(0);
+    let res = router.dispatch(&req, &mut &b"\x78\x56"[..]);
+    assert_eq!(INSUFFICIENT_MEMORY, res.general_status);
+    assert_eq!(2, DELETED.load(Ordering::Relaxed));
+    req.service = Request::GET_ATTRIBUTE_SINGLE;
+    req.instance = Some(2);
+    req.attribute = Some(1);
+    let res = router.dispatch(&req, &mut &b""[..]);
+    assert_eq!(&b"\0\0"[..], res.data);
+
+    req.service = Request::DELETE;
+    req.instance = Some(1);
+    req.attribute = None;
+
+    // not supported
+    let identity = crate::identity::Identity::default();
+    router.register(crate::identity::Identity::CLASS, 1, Box::new(identity));
+    req.class = Some(crate::identity::Identity::CLASS);
+    let res = router.dispatch(&req, &mut &b""[..]);
+    assert_eq!(ATTRIBUTE_NOT_SUPPORTED, res.general_status);
+    req.service = Request::CREATE;
+    let res = router.dispatch(&req, &mut &b""[..]);
+    assert_eq!(ATTRIBUTE_NOT_SUPPORTED, res.general_status);
+}
+
+#[test]
+fn router_class_attributes() {
+    let mut router = Router::default();
+    let mut req = Request {
+        service: Request::GET_ATTRIBUTE_SINGLE,
+        class: Some(Identity::CLASS),
+        instance: Some(0),
+        attribute: Some(1),
+        ..Default::default()
+    };
+    let res = router.dispatch(&req, &mut &b""[..]);
+    assert_eq!(PATH_DESTINATION_UNKNOWN, res.general_status);
+
+    let identity = Identity::new(1, 2, 3, Revision::new(4, 0), 5, str::to_string("Hello"));
+    router.register(Identity::CLASS, 1, Box::new(identity));
+    let res = router.dispatch(&req, &mut &b""[..]);
+    assert_eq!(SUCCESS, res.general_status);
+    assert_eq!(&b"\x01\0"[..], res.data); // revision
+
+    req.attribute = Some(4);
+    let res = router.dispatch(&req, &mut &b""[..]);
+    assert_eq!(ATTRIBUTE_NOT_SUPPORTED, res.general_status);
+
+    req.service = Request::GET_ATTRIBUTE_ALL;
+    let res = router.dispatch(&req, &mut &b""[..]);
+    assert_eq!(&b"\x01\0\x01\0\x01\0"[..], res.data);
+
+    req.service = Request::SET_ATTRIBUTE_SINGLE;
+    let res = router.dispatch(&req, &mut &b"\x02\0"[..]);
+    assert_eq!(SERVICE_NOT_SUPPORTED, res.general_status);
+}
+
 #[test]
 fn auto_traits() {
     use crate::eip::check_auto_traits;
 
+    check_auto_traits::<ElectronicKey>();
     check_auto_traits::<Request>();
     check_auto_traits::<Response>();
+    check_auto_traits::<Router>();
     check_auto_traits::<Service>();
 }
+
+#[test]
+fn hash_key() {
+    use crate::eip::check_hash_key;
+
+    check_hash_key(Request::GET_ATTRIBUTE_SINGLE);
+}
@@ -0,0 +1,215 @@
+use crate::eip::{EipResult, Serializing};
+use crate::error_code::{MESSAGE_FORMAT_ERROR, NOT_ENOUGH_DATA, REPLY_DATA_TOO_LARGE};
+use crate::message_router::{Request, Response};
+#[cfg(test)]
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use core::mem::size_of;
+
+/// The Multiple Service Packet bundles several requests or responses in one message.
+/// The data starts with the number of services followed by a table of offsets,
+/// each offset is relative to the start of the number of services.
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MultipleServicePacket {
+    pub services: Vec<Bytes>, // The embedded request or response messages
+}
+
+impl MultipleServicePacket {
+    /// Add a request
+    ///
+    /// # Arguments
+    ///
+    /// * `req` - The request to embed
+    /// * `data` - The request data following the path
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if the request can not be serialized.
+    ///
+    pub fn push_request(&mut self, req: &Request, data: &[u8]) -> EipResult {
+        let mut buf = BytesMut::with_capacity(req.serial_size() + data.len());
+        req.serialize(&mut buf)?;
+        buf.put_slice(data);
+        self.services.push(buf.freeze());
+        Ok(())
+    }
+
+    /// Add a response
+    ///
+    /// # Arguments
+    ///
+    /// * `res` - The response to embed, including the response data
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if the response can not be serialized.
+    ///
+    pub fn push_response(&mut self, res: &Response) -> EipResult {
+        self.services.push(res.to_bytes()?.freeze());
+        Ok(())
+    }
+
+    /// Get the size of the number of services and the offset table in Bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The number of services
+    ///
+    const fn header_size(count: usize) -> usize {
+        size_of::<u16>() // number of services
+        + (count * size_of::<u16>()) // offsets
+    }
+}
+
+impl Serializing for MultipleServicePacket {
+    /// Deserialize all services
+    /// Note: consumes all remaining data
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to read from
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if there is not enough data or an offset is invalid.
+    ///
+    fn deserialize(&mut self, buf: &mut dyn Buf) -> EipResult {
+        self.services.clear();
+        if buf.remaining() < size_of::<u16>() {
+            return Err(NOT_ENOUGH_DATA);
+        }
+        let count = buf.get_u16_le() as usize;
+        if buf.remaining() < count * size_of::<u16>() {
+            return Err(NOT_ENOUGH_DATA);
+        }
+        let offsets: Vec<usize> = (0..count).map(|_| buf.get_u16_le() as usize).collect();
+
+        let header = Self::header_size(count);
+        let end = header + buf.remaining();
+        let data = buf.copy_to_bytes(buf.remaining());
+
+        for (n, start) in offsets.iter().enumerate() {
+            let stop = offsets.get(n + 1).copied().unwrap_or(end);
+            if *start < header || *start > stop || stop > end {
+                return Err(MESSAGE_FORMAT_ERROR);
+            }
+            self.services
+                .push(data.slice(start - header..stop - header));
+        }
+        Ok(())
+    }
+
+    /// Serialize all services with the offset table
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to write to
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if there is not enough room or too many services.
+    ///
+    fn serialize(&self, buf: &mut BytesMut) -> EipResult {
+        if self.serial_size() > u16::MAX as usize || buf.remaining_mut() < self.serial_size() {
+            return Err(REPLY_DATA_TOO_LARGE);
+        }
+
+        buf.put_u16_le(self.services.len() as u16);
+        let mut offset = Self::header_size(self.services.len());
+        for s in &self.services {
+            buf.put_u16_le(offset as u16);
+            offset += s.len();
+        }
+        for s in &self.services {
+            buf.put_slice(s);
+        }
+        Ok(())
+    }
+
+    /// Get the serialized size in Bytes.
+    ///
+    /// # Returns
+    ///
+    /// * The number of bytes when serialized
+    ///
+    fn serial_size(&self) -> usize {
+        Self::header_size(self.services.len())
+            + self.services.iter().map(|s| s.len()).sum::<usize>()
+    }
+}
+
+#[test]
+fn deserialize() {
+    let mut msp = MultipleServicePacket::default();
+    let mut buf =
+        &b"\x02\0\x06\0\x0e\0\x0e\x03\x20\x01\x24\x01\x30\x01\x0e\x03\x20\x01\x24\x01\x30\x07"[..];
+
+    assert_eq!(Ok(()), msp.deserialize(&mut buf));
+    assert_eq!(0, buf.len());
+    assert_eq!(2, msp.services.len());
+
+    let mut req = Request::default();
+    assert_eq!(Ok(()), req.deserialize(&mut &msp.services[1][..]));
+    assert_eq!(Request::GET_ATTRIBUTE_SINGLE, req.service);
+    assert_eq!(Some(7), req.attribute);
+}
+
+#[test]
+fn deserialize_bounds() {
+    let mut msp = MultipleServicePacket::default();
+
+    // offset past the buffer
+    let mut buf = &b"\x02\0\x06\0\x20\0\x0e\x03\x20\x01\x24\x01\x30\x01"[..];
+    assert_eq!(Err(MESSAGE_FORMAT_ERROR), msp.deserialize(&mut buf));
+
+    // offset into the offset table
+    buf = &b"\x01\0\x02\0\x0e\x03\x20\x01\x24\x01\x30\x01"[..];
+    assert_eq!(Err(MESSAGE_FORMAT_ERROR), msp.deserialize(&mut buf));
+
+    // incomplete offset table
+    buf = &b"\x02\0\x06\0"[..];
+    assert_eq!(Err(NOT_ENOUGH_DATA), msp.deserialize(&mut buf));
+}
+
+#[test]
+fn get_attribute_single() {
+    use crate::identity::{Identity, Revision};
+
+    let mut buf =
+        &b"\x02\0\x06\0\x0e\0\x0e\x03\x20\x01\x24\x01\x30\x01\x0e\x03\x20\x01\x24\x01\x30\x06"[..];
+    let mut msp = MultipleServicePacket::default();
+    assert_eq!(Ok(()), msp.deserialize(&mut buf));
+
+    let identity = Identity::new(1, 2, 3, Revision::new(4, 0), 5, str::to_string("Hello"));
+    let mut replies = MultipleServicePacket::default();
+    for s in &msp.services {
+        let mut req = Request::default();
+        assert_eq!(Ok(()), req.deserialize(&mut &s[..]));
+
+        let mut res = Response::success(req.service);
+        let attr = req.attribute.unwrap() as u16;
+        assert_eq!(
+            Ok(()),
+            identity.serialize_attribute_single(&mut res.data, attr.into())
+        );
+        assert_eq!(Ok(()), replies.push_response(&res));
+    }
+
+    let mut out = BytesMut::with_capacity(100);
+    assert_eq!(Ok(()), replies.serialize(&mut out));
+    assert_eq!(replies.serial_size(), out.len());
+    assert_eq!(
+        &b"\x02\0\x06\0\x0c\0\x8e\0\0\0\x01\0\x8e\0\0\0\x05\0\0\0"[..],
+        out
+    );
+}
+
+#[test]
+fn auto_traits() {
+    use crate::eip::check_auto_traits;
+
+    check_auto_traits::<MultipleServicePacket>();
+}
@@ -0,0 +1,353 @@
+use crate::attr::{AccessCode, AttrPrimitive, Attribute, ShortString, Usint, Word};
+use crate::eip::{EipResult, Serializing};
+use crate::error_code::{
+    ErrorCode, ATTRIBUTE_NOT_SETTABLE, ATTRIBUTE_NOT_SUPPORTED, TOO_MUCH_DATA,
+};
+use crate::message_router::Object;
+use alloc::string::String;
+use bytes::{Buf, BytesMut};
+use core::fmt::Debug;
+
+/// The Parameter object exposes one configurable value of the device,
+/// together with its limits and descriptive strings.
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Attr(u16);
+
+impl From<u16> for Attr {
+    fn from(val: u16) -> Self {
+        Self(val)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Parameter<T: AttrPrimitive> {
+    pub value: Attribute<T>, // Actual value, limited to the minimum and maximum
+    pub descriptor: Word,    // Properties of the parameter
+    pub name: ShortString,   // Human readable name of the parameter
+    pub units: ShortString,  // Engineering units of the value
+    pub help: ShortString,   // Description of the parameter
+    min: T,                  // Minimum valid value
+    max: T,                  // Maximum valid value
+    default: T,              // Value after a reset to the factory defaults
+}
+
+impl<T: AttrPrimitive> Parameter<T> {
+    pub const CLASS: u32 = 0x0f;
+
+    pub const VALUE: Attr = Attr(1);
+    pub const LINK_PATH_SIZE: Attr = Attr(2);
+    pub const LINK_PATH: Attr = Attr(3);
+    pub const DESCRIPTOR: Attr = Attr(4);
+    pub const DATA_TYPE: Attr = Attr(5);
+    pub const DATA_SIZE: Attr = Attr(6);
+    pub const NAME: Attr = Attr(7);
+    pub const UNITS: Attr = Attr(8);
+    pub const HELP: Attr = Attr(9);
+    pub const MINIMUM: Attr = Attr(10);
+    pub const MAXIMUM: Attr = Attr(11);
+    pub const DEFAULT: Attr = Attr(12);
+
+    pub const DESCRIPTOR_READ_ONLY: u16 = 0x0010;
+    pub const DESCRIPTOR_MONITOR: u16 = 0x0020;
+
+    const NAME_CAPACITY: u8 = 16;
+    const UNITS_CAPACITY: u8 = 4;
+    const HELP_CAPACITY: u8 = 64;
+
+    /// Create an instance, the value starts at the default.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The human readable name
+    /// * `units` - The engineering units
+    /// * `acc` - The accessibility of the value
+    /// * `min` - The minimum valid value, inclusive
+    /// * `max` - The maximum valid value, inclusive
+    /// * `default` - The default value, clamped to the range
+    ///
+    /// # Returns
+    ///
+    /// * The created instance
+    ///
+    /// # Errors
+    ///
+    /// If the minimum exceeds the maximum or a string exceeds its capacity, 16 bytes for the
+    /// name and 4 for the units, an error variant will be returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytes::BytesMut;
+    /// use eip_protocol::parameter::Parameter;
+    ///
+    /// let p = Parameter::<u16>::try_new(
+    ///     String::from("Speed"),
+    ///     String::from("rpm"),
+    ///     Default::default(),
+    ///     0,
+    ///     1500,
+    ///     750,
+    /// )
+    /// .unwrap();
+    /// let mut buf = BytesMut::with_capacity(10);
+    ///
+    /// assert_eq!(
+    ///    Ok(()),
+    ///    p.serialize_attribute_single(&mut buf, Parameter::<u16>::MAXIMUM)
+    /// );
+    /// assert_eq!(&b"\xdc\x05"[..], buf);
+    /// ```
+    pub fn try_new(
+        name: String,
+        units: String,
+        acc: AccessCode,
+        min: T,
+        max: T,
+        default: T,
+    ) -> Result<Self, ErrorCode> {
+        if name.len() > Self::NAME_CAPACITY as usize || units.len() > Self::UNITS_CAPACITY as usize
+        {
+            return Err(TOO_MUCH_DATA);
+        }
+        let gettable = AccessCode::get_only();
+        let mut descriptor = Word::new(0, gettable.clone());
+        if !acc.settable() {
+            descriptor.set(Self::DESCRIPTOR_READ_ONLY);
+        }
+        let value = Attribute::try_with_bounds(default, acc, min, max)?;
+        Ok(Parameter {
+            default: value.get(),
+            value,
+            descriptor,
+            name: ShortString::with_capacity(name, gettable.clone(), Self::NAME_CAPACITY),
+            units: ShortString::with_capacity(units, gettable.clone(), Self::UNITS_CAPACITY),
+            help: ShortString::with_capacity(String::new(), gettable, Self::HELP_CAPACITY),
+            min,
+            max,
+        })
+    }
+
+    /// Get the minimum valid value
+    ///
+    /// # Returns
+    ///
+    /// * The minimum, inclusive
+    ///
+    pub fn min(&self) -> T {
+        self.min
+    }
+
+    /// Get the maximum valid value
+    ///
+    /// # Returns
+    ///
+    /// * The maximum, inclusive
+    ///
+    pub fn max(&self) -> T {
+        self.max
+    }
+
+    /// Get the default value
+    ///
+    /// # Returns
+    ///
+    /// * The factory default
+    ///
+    pub fn default_value(&self) -> T {
+        self.default
+    }
+
+    /// Change the value back to the default
+    pub fn reset(&mut self) {
+        self.value.set(self.default);
+    }
+
+    /// Serialize one specific attribute
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to write to
+    /// * `attr` - The attribute identifier number
+    ///
+    /// # Errors
+    ///
+    /// If the attribute is non existent or is not getable, an error variant will be returned.
+    ///
+    pub fn serialize_attribute_single(&self, buf: &mut BytesMut, attr: Attr) -> EipResult {
+        let gettable = AccessCode::get_only();
+        match attr {
+            Self::VALUE => self.value.serialize(buf)?,
+            Self::LINK_PATH_SIZE => Usint::new(0, gettable).serialize(buf)?, // no link
+            Self::LINK_PATH => {}
+            Self::DESCRIPTOR => self.descriptor.serialize(buf)?,
+            Self::DATA_TYPE => Usint::new(T::DATA_TYPE.into(), gettable).serialize(buf)?,
+            Self::DATA_SIZE => Usint::new(T::SIZE as u8, gettable).serialize(buf)?,
+            Self::NAME => self.name.serialize(buf)?,
+            Self::UNITS => self.units.serialize(buf)?,
+            Self::HELP => self.help.serialize(buf)?,
+            Self::MINIMUM => Attribute::new(self.min, gettable).serialize(buf)?,
+            Self::MAXIMUM => Attribute::new(self.max, gettable).serialize(buf)?,
+            Self::DEFAULT => Attribute::new(self.default, gettable).serialize(buf)?,
+            _ => return Err(ATTRIBUTE_NOT_SUPPORTED),
+        };
+        Ok(())
+    }
+
+    /// Deserialize one specific attribute
+    /// Note: only the value can be set, it is rejected when outside the limits
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to read from
+    /// * `attr` - The attribute identifier number
+    ///
+    /// # Errors
+    ///
+    /// If the attribute is non existent or is not set-able, an error variant will be returned.
+    ///
+    pub fn deserialize_attribute_single(&mut self, buf: &mut dyn Buf, attr: Attr) -> EipResult {
+        match attr {
+            Self::VALUE => self.value.deserialize(buf),
+            Attr(n) if (Self::LINK_PATH_SIZE.0..=Self::DEFAULT.0).contains(&n) => {
+                Err(ATTRIBUTE_NOT_SETTABLE)
+            }
+            _ => Err(ATTRIBUTE_NOT_SUPPORTED),
+        }
+    }
+}
+
+impl<T: AttrPrimitive + Debug + Send + Sync> Object for Parameter<T> {
+    fn get_attribute(&self, buf: &mut BytesMut, attr: u16) -> EipResult {
+        self.serialize_attribute_single(buf, attr.into())
+    }
+
+    fn set_attribute(&mut self, buf: &mut dyn Buf, attr: u16) -> EipResult {
+        self.deserialize_attribute_single(buf, attr.into())
+    }
+}
+
+#[test]
+fn get_attribute_single() {
+    let p = Parameter::<u16>::try_new(
+        String::from("Speed"),
+        String::from("rpm"),
+        AccessCode::get_set(),
+        100,
+        1500,
+        750,
+    )
+    .unwrap();
+    let mut buf = BytesMut::with_capacity(100);
+    for attr in [
+        Parameter::<u16>::VALUE,
+        Parameter::<u16>::MINIMUM,
+        Parameter::<u16>::MAXIMUM,
+        Parameter::<u16>::DEFAULT,
+    ] {
+        assert_eq!(Ok(()), p.serialize_attribute_single(&mut buf, attr));
+    }
+    assert_eq!(&b"\xee\x02\x64\0\xdc\x05\xee\x02"[..], buf);
+
+    buf.clear();
+    for attr in [
+        Parameter::<u16>::LINK_PATH_SIZE,
+        Parameter::<u16>::LINK_PATH,
+        Parameter::<u16>::DESCRIPTOR,
+        Parameter::<u16>::DATA_TYPE,
+        Parameter::<u16>::DATA_SIZE,
+        Parameter::<u16>::NAME,
+        Parameter::<u16>::UNITS,
+        Parameter::<u16>::HELP,
+    ] {
+        assert_eq!(Ok(()), p.serialize_attribute_single(&mut buf, attr));
+    }
+    assert_eq!(&b"\0\0\0\xc7\x02\x05Speed\x03rpm\0"[..], buf);
+
+    assert_eq!(
+        Err(ATTRIBUTE_NOT_SUPPORTED),
+        p.serialize_attribute_single(&mut buf, Attr(13))
+    );
+}
+
+#[test]
+fn set_attribute_single() {
+    use crate::error_code::INVALID_PARAMETER;
+
+    let mut p = Parameter::<i8>::try_new(
+        String::from("Offset"),
+        String::new(),
+        AccessCode::get_set(),
+        -10,
+        10,
+        20,
+    )
+    .unwrap();
+    assert_eq!(10, p.value.get()); // clamped
+    assert_eq!(10, p.default_value());
+
+    assert_eq!(
+        Ok(()),
+        p.deserialize_attribute_single(&mut &b"\xfb"[..], Parameter::<i8>::VALUE)
+    );
+    assert_eq!(-5, p.value.get());
+    assert_eq!(
+        Err(INVALID_PARAMETER),
+        p.deserialize_attribute_single(&mut &b"\xf5"[..], Parameter::<i8>::VALUE)
+    );
+    assert_eq!(-5, p.value.get());
+    assert_eq!(
+        Err(ATTRIBUTE_NOT_SETTABLE),
+        p.deserialize_attribute_single(&mut &b"\0"[..], Parameter::<i8>::MAXIMUM)
+    );
+    assert_eq!(
+        Err(ATTRIBUTE_NOT_SUPPORTED),
+        p.deserialize_attribute_single(&mut &b"\0"[..], Attr(13))
+    );
+
+    p.reset();
+    assert_eq!(10, p.value.get());
+
+    // read only
+    let p = Parameter::<u8>::try_new(
+        String::new(),
+        String::new(),
+        AccessCode::get_only(),
+        0,
+        1,
+        0,
+    )
+    .unwrap();
+    assert_eq!(Parameter::<u8>::DESCRIPTOR_READ_ONLY, p.descriptor.get());
+
+    // an empty range
+    assert_eq!(
+        Err(INVALID_PARAMETER),
+        Parameter::<u8>::try_new(String::new(), String::new(), AccessCode::get_set(), 2, 1, 0)
+            .map(|p| p.max())
+    );
+
+    // strings exceeding the capacity
+    let name = String::from("Acceleration ramp"); // 17 bytes
+    assert_eq!(
+        Err(TOO_MUCH_DATA),
+        Parameter::<u8>::try_new(name, String::new(), AccessCode::get_set(), 0, 1, 0)
+            .map(|p| p.max())
+    );
+    let units = String::from("rad/s");
+    assert_eq!(
+        Err(TOO_MUCH_DATA),
+        Parameter::<u8>::try_new(String::new(), units, AccessCode::get_set(), 0, 1, 0)
+            .map(|p| p.max())
+    );
+}
+
+#[test]
+fn auto_traits() {
+    use crate::eip::check_auto_traits;
+
+    check_auto_traits::<Attr>();
+    check_auto_traits::<Parameter<u16>>();
+}
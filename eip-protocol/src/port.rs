@@ -0,0 +1,236 @@
+use crate::attr::{AccessCode, ShortString, Uint};
+use crate::eip::{EipResult, Serializing};
+use crate::error_code::{ATTRIBUTE_NOT_SETTABLE, ATTRIBUTE_NOT_SUPPORTED, REPLY_DATA_TOO_LARGE};
+use crate::message_router::Object;
+use alloc::string::String;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use core::mem::size_of;
+
+/// The Port object describes the CIP ports of the device, used to build routing paths.
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Attr(u16);
+
+impl From<u16> for Attr {
+    fn from(val: u16) -> Self {
+        Self(val)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Port {
+    pub port_type: Uint,        // Type of the communication network
+    pub port_number: Uint,      // CIP port number, used in routing paths
+    link_path: Bytes,           // Padded EPATH to the link object of the port
+    pub port_name: ShortString, // Human readable name of the port
+}
+
+impl Port {
+    pub const CLASS: u32 = 0xf4;
+
+    pub const PORT_TYPE: Attr = Attr(1);
+    pub const PORT_NUMBER: Attr = Attr(2);
+    pub const LINK_OBJECT: Attr = Attr(3);
+    pub const PORT_NAME: Attr = Attr(4);
+
+    pub const TYPE_TCP_IP: u16 = 4;
+    const TCP_IP_INTERFACE_PATH: &'static [u8] = b"\x20\xf5\x24\x01"; // class 0xf5, instance 1
+
+    /// Create an instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `port_type` - The type of the communication network
+    /// * `port_number` - The CIP port number
+    /// * `link_path` - The EPATH to the link object, padded to an even number of bytes
+    /// * `port_name` - The human readable name
+    ///
+    /// # Returns
+    ///
+    /// * The created instance
+    ///
+    pub fn new(port_type: u16, port_number: u16, link_path: Bytes, port_name: String) -> Self {
+        let gettable = AccessCode::get_only();
+        let link_path = if link_path.len().is_multiple_of(2) {
+            link_path
+        } else {
+            // the size is in words, pad to keep the last byte
+            let mut padded = BytesMut::with_capacity(link_path.len() + 1);
+            padded.put_slice(&link_path);
+            padded.put_u8(0);
+            padded.freeze()
+        };
+        Port {
+            port_type: Uint::new(port_type, gettable.clone()),
+            port_number: Uint::new(port_number, gettable.clone()),
+            link_path,
+            port_name: ShortString::with_capacity(port_name, gettable, 64),
+        }
+    }
+
+    /// Create the EtherNet/IP port, linked to the first TCP/IP Interface object.
+    ///
+    /// # Arguments
+    ///
+    /// * `port_number` - The CIP port number, typically 2
+    ///
+    /// # Returns
+    ///
+    /// * The created instance
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use bytes::BytesMut;
+    /// use eip_protocol::port::Port;
+    ///
+    /// let port = Port::tcp_ip(2);
+    /// let mut buf = BytesMut::with_capacity(10);
+    ///
+    /// assert_eq!(
+    ///    Ok(()),
+    ///    port.serialize_attribute_single(&mut buf, Port::LINK_OBJECT)
+    /// );
+    /// assert_eq!(&b"\x02\0\x20\xf5\x24\x01"[..], buf);
+    /// ```
+    pub fn tcp_ip(port_number: u16) -> Self {
+        Self::new(
+            Self::TYPE_TCP_IP,
+            port_number,
+            Bytes::from_static(Self::TCP_IP_INTERFACE_PATH),
+            String::from("EtherNet/IP"),
+        )
+    }
+
+    /// Get the path to the link object
+    ///
+    /// # Returns
+    ///
+    /// * The padded EPATH
+    ///
+    pub fn link_path(&self) -> &[u8] {
+        &self.link_path
+    }
+
+    /// Serialize one specific attribute
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to write to
+    /// * `attr` - The attribute identifier number
+    ///
+    /// # Errors
+    ///
+    /// If the attribute is non existent or is not getable, an error variant will be returned.
+    ///
+    pub fn serialize_attribute_single(&self, buf: &mut BytesMut, attr: Attr) -> EipResult {
+        match attr {
+            Self::PORT_TYPE => self.port_type.serialize(buf)?,
+            Self::PORT_NUMBER => self.port_number.serialize(buf)?,
+            Self::LINK_OBJECT => {
+                if buf.remaining_mut() < size_of::<u16>() + self.link_path.len() {
+                    return Err(REPLY_DATA_TOO_LARGE);
+                }
+                let words =
+                    u16::try_from(self.link_path.len() / 2).map_err(|_| REPLY_DATA_TOO_LARGE)?;
+                buf.put_u16_le(words); // size in words
+                buf.put_slice(&self.link_path);
+            }
+            Self::PORT_NAME => self.port_name.serialize(buf)?,
+            _ => return Err(ATTRIBUTE_NOT_SUPPORTED),
+        };
+        Ok(())
+    }
+
+    /// Deserialize one specific attribute
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to read from
+    /// * `attr` - The attribute identifier number
+    ///
+    /// # Errors
+    ///
+    /// If the attribute is non existent or is not set-able, an error variant will be returned.
+    ///
+    pub fn deserialize_attribute_single(&mut self, buf: &mut dyn Buf, attr: Attr) -> EipResult {
+        match attr {
+            Self::PORT_TYPE => self.port_type.deserialize(buf)?,
+            Self::PORT_NUMBER => self.port_number.deserialize(buf)?,
+            Self::LINK_OBJECT => return Err(ATTRIBUTE_NOT_SETTABLE),
+            Self::PORT_NAME => self.port_name.deserialize(buf)?,
+            _ => return Err(ATTRIBUTE_NOT_SUPPORTED),
+        };
+        Ok(())
+    }
+}
+
+impl Object for Port {
+    fn get_attribute(&self, buf: &mut BytesMut, attr: u16) -> EipResult {
+        self.serialize_attribute_single(buf, attr.into())
+    }
+
+    fn set_attribute(&mut self, buf: &mut dyn Buf, attr: u16) -> EipResult {
+        self.deserialize_attribute_single(buf, attr.into())
+    }
+}
+
+#[test]
+fn get_attribute_single() {
+    let port = Port::tcp_ip(2);
+    let mut buf = BytesMut::with_capacity(10);
+    assert_eq!(
+        Ok(()),
+        port.serialize_attribute_single(&mut buf, Port::PORT_TYPE)
+    );
+    assert_eq!(
+        Ok(()),
+        port.serialize_attribute_single(&mut buf, Port::PORT_NUMBER)
+    );
+    assert_eq!(&b"\x04\0\x02\0"[..], buf);
+
+    buf.clear();
+    assert_eq!(
+        Ok(()),
+        port.serialize_attribute_single(&mut buf, Port::PORT_NAME)
+    );
+    assert_eq!(&b"\x0bEtherNet/IP"[..], buf);
+
+    // an odd path is padded, the word count covers the last byte
+    let port = Port::new(1, 3, Bytes::from_static(b"\x20\xf5\x24"), String::new());
+    buf.clear();
+    assert_eq!(
+        Ok(()),
+        port.serialize_attribute_single(&mut buf, Port::LINK_OBJECT)
+    );
+    assert_eq!(&b"\x02\0\x20\xf5\x24\0"[..], buf);
+
+    assert_eq!(
+        Err(ATTRIBUTE_NOT_SUPPORTED),
+        port.serialize_attribute_single(&mut buf, Attr(5))
+    );
+}
+
+#[test]
+fn set_attribute_single() {
+    let mut port = Port::tcp_ip(2);
+    assert_eq!(
+        Err(ATTRIBUTE_NOT_SETTABLE),
+        port.deserialize_attribute_single(&mut &b"\x03\0"[..], Port::PORT_NUMBER)
+    );
+    assert_eq!(
+        Err(ATTRIBUTE_NOT_SETTABLE),
+        port.deserialize_attribute_single(&mut &b"\x01\0\x20\x02"[..], Port::LINK_OBJECT)
+    );
+    assert_eq!(&b"\x20\xf5\x24\x01"[..], port.link_path());
+}
+
+#[test]
+fn auto_traits() {
+    use crate::eip::check_auto_traits;
+
+    check_auto_traits::<Attr>();
+    check_auto_traits::<Port>();
+}
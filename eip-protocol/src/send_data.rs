@@ -1,23 +1,165 @@
 use crate::eip;
 use crate::eip::{EipResult, Serializing};
-use crate::error_code::{ErrorCode, NOT_ENOUGH_DATA, REPLY_DATA_TOO_LARGE};
+use crate::encapsulation::{Command, Encapsulation};
+use crate::error_code::{ErrorCode, NOT_ENOUGH_DATA, REPLY_DATA_TOO_LARGE, UNSUPPORTED_COMMAND};
 use bytes::{Buf, BufMut, BytesMut};
 use core::mem::size_of;
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::Instant;
 
 /// This is the SendRRData and SendUnitData implementation
 
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SendData {
     interface_handle: u32,
     time_out: u16,
     pub item_count: u16,
+    pub command: Command, // SendRRData or SendUnitData, not serialized
 }
 
 impl SendData {
-    const fn serial_size(&self) -> usize {
-        size_of::<u32>() // interface_handle
-        + size_of::<u16>() // time_out
-        + size_of::<u16>() // item_count
+    const TICK_MASK: u8 = 0x0f; // The time per tick of the low byte is 2^n ms
+
+    /// Deserialize the encapsulation header followed by all fields.
+    /// The command is recorded to distinguish connected from unconnected data.
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to read from
+    ///
+    /// # Returns
+    ///
+    /// * The encapsulation header
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if there is not enough data or the command is not
+    /// SendRRData or SendUnitData.
+    ///
+    pub fn deserialize_frame(&mut self, buf: &mut dyn Buf) -> Result<Encapsulation, ErrorCode> {
+        let mut enc = Encapsulation::default();
+        enc.deserialize(buf)?;
+        if enc.command != Encapsulation::SEND_RR_DATA
+            && enc.command != Encapsulation::SEND_UNIT_DATA
+        {
+            return Err(UNSUPPORTED_COMMAND);
+        }
+        self.deserialize(buf)?;
+        self.command = enc.command.clone();
+        Ok(enc)
+    }
+
+    /// Test if this is connected data, received with SendUnitData
+    ///
+    /// # Returns
+    ///
+    /// True if connected, false otherwise.
+    ///
+    pub fn is_connected(&self) -> bool {
+        self.command == Encapsulation::SEND_UNIT_DATA
+    }
+
+    /// Get the interface handle, shall be 0 for CIP
+    ///
+    /// # Returns
+    ///
+    /// * The interface handle
+    ///
+    pub fn interface_handle(&self) -> u32 {
+        self.interface_handle
+    }
+
+    /// Set the interface handle
+    ///
+    /// # Arguments
+    ///
+    /// * `handle` - The interface handle, shall be 0 for CIP
+    ///
+    pub fn set_interface_handle(&mut self, handle: u32) {
+        self.interface_handle = handle;
+    }
+
+    /// Get the raw operation timeout
+    ///
+    /// # Returns
+    ///
+    /// * The timeout, the priority and time per tick in the low byte and the ticks in the high byte
+    ///
+    pub fn time_out(&self) -> u16 {
+        self.time_out
+    }
+
+    /// Set the raw operation timeout
+    ///
+    /// # Arguments
+    ///
+    /// * `time_out` - The timeout, the priority and time per tick in the low byte and the ticks
+    ///   in the high byte
+    ///
+    pub fn set_time_out(&mut self, time_out: u16) {
+        self.time_out = time_out;
+    }
+
+    /// Get the operation timeout as a duration
+    ///
+    /// # Returns
+    ///
+    /// * The number of ticks times the time per tick
+    ///
+    pub fn time_out_duration(&self) -> Duration {
+        let [tick, ticks] = self.time_out.to_le_bytes();
+        Duration::from_millis((ticks as u64) << (tick & Self::TICK_MASK))
+    }
+
+    /// Set the operation timeout from a duration, the priority bit is kept.
+    /// The finest time per tick is used, rounded up to a whole number of ticks.
+    ///
+    /// # Arguments
+    ///
+    /// * `time_out` - The timeout, limited to 255 ticks of 32768 ms
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use core::time::Duration;
+    /// use eip_protocol::send_data::SendData;
+    ///
+    /// let mut send_data = SendData::default();
+    /// send_data.set_time_out_duration(Duration::from_millis(1000));
+    ///
+    /// assert_eq!(0xfa02, send_data.time_out()); // 250 ticks of 4 ms
+    /// ```
+    pub fn set_time_out_duration(&mut self, time_out: Duration) {
+        let max = (u8::MAX as u128) << Self::TICK_MASK;
+        let ms = time_out.as_millis().min(max);
+        let tick = (0..Self::TICK_MASK)
+            .find(|n| ms.div_ceil(1 << n) <= u8::MAX as u128)
+            .unwrap_or(Self::TICK_MASK);
+        let ticks = ms.div_ceil(1 << tick) as u8;
+        let priority = self.time_out as u8 & !Self::TICK_MASK;
+        self.time_out = u16::from_le_bytes([priority | tick, ticks]);
+    }
+
+    /// Get the moment a server handler must have produced the reply.
+    /// The crate does not enforce it, a handler that overruns it should reply with an error
+    /// status instead of the data.
+    ///
+    /// # Arguments
+    ///
+    /// * `received` - The moment the request is received
+    ///
+    /// # Returns
+    ///
+    /// * The deadline, None if the timeout is zero and left to the CIP layer
+    ///
+    #[cfg(feature = "std")]
+    pub fn deadline(&self, received: Instant) -> Option<Instant> {
+        match self.time_out_duration() {
+            Duration::ZERO => None,
+            d => Some(received + d),
+        }
     }
 
     /// Reserve room in a buffer to serialize this later.
@@ -79,6 +221,18 @@ impl Serializing for SendData {
         buf.put_u16_le(self.item_count);
         Ok(())
     }
+
+    /// Get the serialized size in Bytes.
+    ///
+    /// # Returns
+    ///
+    /// * The number of bytes when serialized
+    ///
+    fn serial_size(&self) -> usize {
+        size_of::<u32>() // interface_handle
+        + size_of::<u16>() // time_out
+        + size_of::<u16>() // item_count
+    }
 }
 
 #[test]
@@ -94,15 +248,94 @@ fn deserialize() {
 }
 
 #[test]
-fn serialize() {
+fn deserialize_frame() {
     let mut send_data = SendData::default();
-    send_data.item_count = 0x1234;
+    let mut buf = &b"\x70\0\x08\0\x01\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\x02\0"[..];
+    let enc = send_data.deserialize_frame(&mut buf).unwrap();
+    assert_eq!(0, buf.len());
+    assert_eq!(1, enc.session);
+    assert_eq!(Encapsulation::SEND_UNIT_DATA, send_data.command);
+    assert!(send_data.is_connected());
+    assert_eq!(2, send_data.item_count);
+
+    buf = &b"\x6f\0\x08\0\x01\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\x02\0"[..];
+    assert!(send_data.deserialize_frame(&mut buf).is_ok());
+    assert!(!send_data.is_connected());
+
+    buf = &b"\x65\0\x08\0\x01\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\x02\0"[..];
+    assert_eq!(
+        Err(UNSUPPORTED_COMMAND),
+        send_data.deserialize_frame(&mut buf)
+    );
+}
+
+#[test]
+fn serialize() {
+    let send_data = SendData {
+        item_count: 0x1234,
+        ..Default::default()
+    };
     let mut buf = BytesMut::with_capacity(100);
     assert_eq!(Ok(()), send_data.serialize(&mut buf));
     assert_eq!(8, buf.len());
     assert_eq!(&b"\0\0\0\0\0\0\x34\x12"[..], buf);
 }
 
+#[test]
+fn accessors() {
+    let mut send_data = SendData::default();
+    send_data.set_interface_handle(0x12345678);
+    send_data.set_time_out(10);
+    assert_eq!(0x12345678, send_data.interface_handle());
+    assert_eq!(10, send_data.time_out());
+
+    let mut buf = BytesMut::with_capacity(100);
+    assert_eq!(Ok(()), send_data.serialize(&mut buf));
+    assert_eq!(&b"\x78\x56\x34\x12\x0a\0\0\0"[..], buf);
+
+    let mut received = SendData::default();
+    assert_eq!(Ok(()), received.deserialize(&mut &buf[..]));
+    assert_eq!(0x12345678, received.interface_handle());
+    assert_eq!(10, received.time_out());
+}
+
+#[test]
+fn time_out_duration() {
+    let ms = Duration::from_millis;
+    let mut send_data = SendData::default();
+    send_data.set_time_out_duration(ms(250));
+    assert_eq!(0xfa00, send_data.time_out());
+    assert_eq!(ms(250), send_data.time_out_duration());
+
+    send_data.set_time_out_duration(ms(1021)); // 128 ticks of 8 ms
+    assert_eq!(0x8003, send_data.time_out());
+    assert_eq!(ms(1024), send_data.time_out_duration());
+
+    send_data.set_time_out_duration(Duration::from_secs(86400));
+    assert_eq!(0xff0f, send_data.time_out());
+    assert_eq!(ms(255 << 15), send_data.time_out_duration());
+
+    // the priority bit is kept and ignored
+    send_data.set_time_out(0x0a10);
+    assert_eq!(ms(10), send_data.time_out_duration());
+    send_data.set_time_out_duration(ms(0));
+    assert_eq!(0x0010, send_data.time_out());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn deadline() {
+    let received = Instant::now();
+    let mut send_data = SendData::default();
+    assert_eq!(None, send_data.deadline(received));
+
+    send_data.set_time_out(0x7d03); // 125 ticks of 8 ms
+    assert_eq!(
+        Some(received + Duration::from_millis(1000)),
+        send_data.deadline(received)
+    );
+}
+
 #[test]
 fn auto_traits() {
     use crate::eip::check_auto_traits;
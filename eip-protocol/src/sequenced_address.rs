@@ -0,0 +1,113 @@
+use crate::eip::{EipResult, Serializing};
+use crate::error_code::{NOT_ENOUGH_DATA, REPLY_DATA_TOO_LARGE};
+use bytes::{Buf, BufMut, BytesMut};
+use core::mem::size_of;
+
+/// This is the body of the sequenced address item, used by class 1 implicit messaging
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SequencedAddress {
+    pub connection_id: u32,   // Identification of the connection
+    pub sequence_number: u32, // Encapsulation sequence number of the packet
+}
+
+impl SequencedAddress {
+    /// Create an instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `connection_id` - The identification of the connection
+    /// * `sequence_number` - The sequence number
+    ///
+    /// # Returns
+    ///
+    /// * The created instance
+    ///
+    pub fn new(connection_id: u32, sequence_number: u32) -> Self {
+        SequencedAddress {
+            connection_id,
+            sequence_number,
+        }
+    }
+}
+
+impl Serializing for SequencedAddress {
+    /// Deserialize all fields
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to read from
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if there is not enough data.
+    ///
+    fn deserialize(&mut self, buf: &mut dyn Buf) -> EipResult {
+        if buf.remaining() < self.serial_size() {
+            return Err(NOT_ENOUGH_DATA);
+        }
+        self.connection_id = buf.get_u32_le();
+        self.sequence_number = buf.get_u32_le();
+        Ok(())
+    }
+
+    /// Serialize all fields
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to write to
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if there is not enough room.
+    ///
+    fn serialize(&self, buf: &mut BytesMut) -> EipResult {
+        if buf.remaining_mut() < self.serial_size() {
+            return Err(REPLY_DATA_TOO_LARGE);
+        }
+        buf.put_u32_le(self.connection_id);
+        buf.put_u32_le(self.sequence_number);
+        Ok(())
+    }
+
+    /// Get the serialized size in Bytes.
+    ///
+    /// # Returns
+    ///
+    /// * The number of bytes when serialized
+    ///
+    fn serial_size(&self) -> usize {
+        size_of::<u32>() // connection_id
+        + size_of::<u32>() // sequence_number
+    }
+}
+
+#[test]
+fn deserialize() {
+    let mut sa = SequencedAddress::default();
+    let mut buf = &b"\x78\x56\x34\x12\x04\x03\x02\x01"[..];
+    assert_eq!(Ok(()), sa.deserialize(&mut buf));
+    assert_eq!(0, buf.len());
+    assert_eq!(0x12345678, sa.connection_id);
+    assert_eq!(0x01020304, sa.sequence_number);
+
+    buf = &b"\x78\x56\x34\x12\x04\x03\x02"[..];
+    assert_eq!(Err(NOT_ENOUGH_DATA), sa.deserialize(&mut buf));
+}
+
+#[test]
+fn serialize() {
+    let sa = SequencedAddress::new(0x12345678, 0x01020304);
+    let mut buf = BytesMut::with_capacity(100);
+    assert_eq!(Ok(()), sa.serialize(&mut buf));
+    assert_eq!(8, buf.len());
+    assert_eq!(&b"\x78\x56\x34\x12\x04\x03\x02\x01"[..], buf);
+}
+
+#[test]
+fn auto_traits() {
+    use crate::eip::check_auto_traits;
+
+    check_auto_traits::<SequencedAddress>();
+}
@@ -1,7 +1,11 @@
+use crate::eip;
 use crate::eip::{EipResult, Serializing};
 use crate::encapsulation::VERSION;
-use crate::error_code::{NOT_ENOUGH_DATA, REPLY_DATA_TOO_LARGE};
+use crate::error_code::{ErrorCode, NOT_ENOUGH_DATA, REPLY_DATA_TOO_LARGE, TOO_MUCH_DATA};
 use crate::item::Item;
+#[cfg(test)]
+use alloc::vec;
+use alloc::vec::Vec;
 use bytes::{Buf, BufMut, BytesMut};
 use core::mem::size_of;
 use core::ops::BitOr;
@@ -10,8 +14,25 @@ const NAME_LEN: usize = 16;
 const NAME: &[u8; NAME_LEN] = b"Communications\0\0";
 
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Capability(u16);
 
+impl Capability {
+    /// Check if the flags are set
+    ///
+    /// # Arguments
+    ///
+    /// * `flag` - The flag or or-ed flags to test
+    ///
+    /// # Returns
+    ///
+    /// * True if all flags are set
+    ///
+    pub fn contains(&self, flag: Capability) -> bool {
+        self.0 & flag.0 == flag.0
+    }
+}
+
 impl BitOr for Capability {
     type Output = Self;
 
@@ -21,6 +42,7 @@ impl BitOr for Capability {
 }
 
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Services {
     pub item: Item,                 // TypeId
     pub encapsulation_version: u16, // Version of the encapsulation
@@ -29,8 +51,9 @@ pub struct Services {
 }
 
 impl Services {
-    pub const EIP_ENCAPSULATION: Capability = Capability(0x0020);
-    pub const SUPPORT_CLASS_01: Capability = Capability(0x0100);
+    // Only bit 5 and bit 8 are defined, all other bits are reserved
+    pub const EIP_ENCAPSULATION: Capability = Capability(0x0020); // CIP over TCP
+    pub const SUPPORT_CLASS_01: Capability = Capability(0x0100); // CIP class 0/1 over UDP
 
     /// Create a new server
     ///
@@ -59,19 +82,44 @@ impl Services {
     ///
     pub fn server() -> Self {
         Services {
-            item: Item::new(
-                Item::SERVICES,
-                size_of::<u16>() // encapsulation_version
-            + size_of::<u16>() // capability
-            + NAME_LEN,
-            ),
+            item: Item::new(Item::SERVICES, Self::body_size()),
             encapsulation_version: VERSION,
             capability: Self::EIP_ENCAPSULATION | Self::SUPPORT_CLASS_01,
             name: *NAME,
         }
     }
 
-    const fn serial_size(&self) -> usize {
+    /// Create a new server with a vendor specific service name
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The service name, shorter names are padded with zeros
+    ///
+    /// # Returns
+    ///
+    /// * The created instance
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if the name is longer than 16 bytes.
+    ///
+    pub fn with_name(name: &str) -> Result<Self, ErrorCode> {
+        if name.len() > NAME_LEN {
+            return Err(TOO_MUCH_DATA);
+        }
+        let mut srv = Self::server();
+        srv.name = [0; NAME_LEN];
+        srv.name[..name.len()].copy_from_slice(name.as_bytes());
+        Ok(srv)
+    }
+
+    /// Get the serialized size of the body, the item header excluded.
+    ///
+    /// # Returns
+    ///
+    /// * The number of bytes when serialized
+    ///
+    const fn body_size() -> usize {
         size_of::<u16>() // encapsulation_version
         + size_of::<u16>() // capability
         + NAME_LEN // name
@@ -88,14 +136,91 @@ impl Services {
     /// An error variant will be returned if there is not enough room.
     ///
     pub fn list(&self, buf: &mut BytesMut) -> EipResult {
-        if buf.remaining_mut() < size_of::<u16>() {
-            // room for item_count
-            return Err(REPLY_DATA_TOO_LARGE);
+        serialize_list(buf, core::slice::from_ref(self))
+    }
+}
+
+/// Serialize the item count followed by the service items
+///
+/// # Arguments
+///
+/// * `buf` - The message buffer to write to
+/// * `services` - The service items
+///
+/// # Errors
+///
+/// An error variant will be returned if there is not enough room or too many items.
+///
+fn serialize_list(buf: &mut BytesMut, services: &[Services]) -> EipResult {
+    if buf.remaining_mut() < size_of::<u16>() || services.len() > u16::MAX as usize {
+        // room for item_count
+        return Err(REPLY_DATA_TOO_LARGE);
+    }
+    buf.put_u16_le(services.len() as u16);
+    for srv in services {
+        srv.serialize(buf)?;
+    }
+    Ok(())
+}
+
+/// The ListServices reply data, all communication services of the device
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ServiceList {
+    pub services: Vec<Services>,
+}
+
+impl Serializing for ServiceList {
+    /// Deserialize the item count and the service items.
+    /// The count is checked against the received data before allocating, on an error
+    /// no input is consumed.
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to read from
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if there is not enough data.
+    ///
+    fn deserialize(&mut self, buf: &mut dyn Buf) -> EipResult {
+        if buf.remaining() < size_of::<u16>() {
+            return Err(NOT_ENOUGH_DATA);
         }
-        buf.put_u16_le(1); // item_count only one item
-        self.serialize(buf)?;
+        self.services = eip::read_atomic(buf, |buf| {
+            let count = buf.get_u16_le() as usize;
+            if count * Services::default().serial_size() > buf.remaining() {
+                return Err(NOT_ENOUGH_DATA);
+            }
+            eip::deserialize_count(buf, count)
+        })?;
         Ok(())
     }
+
+    /// Serialize the item count and the service items
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to write to
+    ///
+    /// # Errors
+    ///
+    /// An error variant will be returned if there is not enough room or too many items.
+    ///
+    fn serialize(&self, buf: &mut BytesMut) -> EipResult {
+        serialize_list(buf, &self.services)
+    }
+
+    /// Get the serialized size in Bytes.
+    ///
+    /// # Returns
+    ///
+    /// * The number of bytes when serialized
+    ///
+    fn serial_size(&self) -> usize {
+        size_of::<u16>() + self.services.serial_size()
+    }
 }
 
 impl Serializing for Services {
@@ -108,13 +233,13 @@ impl Serializing for Services {
     /// # Errors
     ///
     /// An error variant will be returned if there is not enough data.
+    /// All fields keep their prior value then and no input is consumed.
     ///
     fn deserialize(&mut self, buf: &mut dyn Buf) -> EipResult {
-        self.item.deserialize(buf)?;
-
-        if buf.remaining() < self.serial_size() {
+        if buf.remaining() < self.item.serial_size() + Self::body_size() {
             return Err(NOT_ENOUGH_DATA);
         }
+        self.item.deserialize(buf)?;
         self.encapsulation_version = buf.get_u16_le();
         self.capability = Capability(buf.get_u16_le());
         for n in 0..NAME_LEN {
@@ -136,7 +261,7 @@ impl Serializing for Services {
     fn serialize(&self, buf: &mut BytesMut) -> EipResult {
         self.item.serialize(buf)?;
 
-        if buf.remaining_mut() < self.serial_size() {
+        if buf.remaining_mut() < Self::body_size() {
             return Err(REPLY_DATA_TOO_LARGE);
         }
         buf.put_u16_le(self.encapsulation_version);
@@ -144,6 +269,16 @@ impl Serializing for Services {
         buf.put(&self.name[..]);
         Ok(())
     }
+
+    /// Get the serialized size in Bytes.
+    ///
+    /// # Returns
+    ///
+    /// * The number of bytes when serialized
+    ///
+    fn serial_size(&self) -> usize {
+        self.item.serial_size() + Self::body_size()
+    }
 }
 
 #[test]
@@ -162,8 +297,12 @@ fn deserialize() {
     );
     assert_eq!(&b"Communications\0\0"[..], &srv.name);
 
-    buf = &b"\0\x01\x14\0\x01\0\x20\x01Communications\0"[..];
+    // the name is truncated, none of the fields is changed
+    let prior = srv.clone();
+    buf = &b"\0\x01\x13\0\x02\0\x20\x01Communications\0"[..];
     assert_eq!(Err(NOT_ENOUGH_DATA), srv.deserialize(&mut buf));
+    assert_eq!(23, buf.len());
+    assert_eq!(prior, srv);
 }
 
 #[test]
@@ -173,13 +312,76 @@ fn serialize() {
 
     assert_eq!(Ok(()), srv.serialize(&mut buf));
     assert_eq!(24, buf.len());
+    assert_eq!(24, srv.serial_size());
     assert_eq!(&b"\0\x01\x14\0\x01\0\x20\x01Communications\0\0"[..], buf);
 }
 
+#[test]
+fn with_name() {
+    let srv = Services::with_name("Vendor IO").unwrap();
+    assert_eq!(&b"Vendor IO\0\0\0\0\0\0\0"[..], &srv.name);
+    assert_eq!(Services::server().capability, srv.capability);
+
+    let srv = Services::with_name("Sixteen chars ok").unwrap();
+    assert_eq!(&b"Sixteen chars ok"[..], &srv.name);
+
+    assert_eq!(Err(TOO_MUCH_DATA), Services::with_name("Seventeen chars!!"));
+}
+
+#[test]
+fn capability() {
+    let srv = Services::server();
+    assert!(srv.capability.contains(Services::EIP_ENCAPSULATION));
+    assert!(srv.capability.contains(Services::SUPPORT_CLASS_01));
+    assert!(srv
+        .capability
+        .contains(Services::EIP_ENCAPSULATION | Services::SUPPORT_CLASS_01));
+
+    let tcp = Services::EIP_ENCAPSULATION;
+    assert!(tcp.contains(Services::EIP_ENCAPSULATION));
+    assert!(!tcp.contains(Services::SUPPORT_CLASS_01));
+    assert!(!tcp.contains(Services::EIP_ENCAPSULATION | Services::SUPPORT_CLASS_01));
+    assert!(Capability::default().contains(Capability::default()));
+}
+
+#[test]
+fn list() {
+    let mut buf = BytesMut::with_capacity(100);
+    assert_eq!(Ok(()), Services::server().list(&mut buf));
+    assert_eq!(&b"\x01\0\0\x01\x14\0"[..], &buf[..6]);
+
+    let list = ServiceList {
+        services: vec![
+            Services::server(),
+            Services::with_name("Vendor IO").unwrap(),
+        ],
+    };
+    buf.clear();
+    assert_eq!(Ok(()), list.serialize(&mut buf));
+    assert_eq!(50, list.serial_size());
+    assert_eq!(50, buf.len());
+    assert_eq!(&b"\x02\0"[..], &buf[..2]); // item count
+    assert_eq!(&b"\0\x01\x14\0\x01\0\x20\x01Vendor IO"[..], &buf[26..43]);
+
+    let mut received = ServiceList::default();
+    assert_eq!(Ok(()), received.deserialize(&mut &buf[..]));
+    assert_eq!(list, received);
+    let mut data = &buf[..49];
+    assert_eq!(Err(NOT_ENOUGH_DATA), received.deserialize(&mut data));
+    assert_eq!(49, data.len());
+    assert_eq!(list, received);
+
+    // the count exceeds the data, rejected before allocating
+    let mut data = &b"\xff\xff\0\x01\x14\0\x01\0\x20\x01Communications\0\0"[..];
+    assert_eq!(Err(NOT_ENOUGH_DATA), received.deserialize(&mut data));
+    assert_eq!(26, data.len());
+}
+
 #[test]
 fn auto_traits() {
     use crate::eip::check_auto_traits;
 
     check_auto_traits::<Capability>();
+    check_auto_traits::<ServiceList>();
     check_auto_traits::<Services>();
 }
@@ -6,12 +6,22 @@ use crate::error_code::{
 use crate::encapsulation::VERSION;
 use bytes::{Buf, BufMut, BytesMut};
 use core::mem::size_of;
-use std::collections::HashSet;
+use core::time::Duration;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::time::Instant;
+
+#[derive(Clone, Debug, PartialEq)]
+struct Registration {
+    last_activity: Instant,
+    peer: Option<SocketAddr>, // The peer that registered the session
+}
 
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct Session {
     id: u32,
-    set: HashSet<u32>,
+    sessions: HashMap<u32, Registration>,
 }
 
 impl Session {
@@ -42,7 +52,8 @@ impl Session {
     /// assert_eq!(4, res.len());
     /// assert_eq!(&b"\x01\0\0\0"[..], res);
     ///
-    /// assert_eq!(true, session.check(id));
+    /// let shared = &session;
+    /// assert_eq!(true, shared.check(id));
     /// assert_eq!(Ok(()), session.unregister(id));
     ///
     /// ```
@@ -53,6 +64,54 @@ impl Session {
     /// an error variant will be returned.
     ///
     pub fn register(&mut self, req: &mut dyn Buf, res: &mut BytesMut, id: &mut u32) -> EipResult {
+        self.register_peer(req, res, id, None)
+    }
+
+    /// Request to register a new session bound to a peer
+    ///
+    /// # Arguments
+    ///
+    /// * `peer` - The socket address of the peer registering the session
+    /// * `req` - The message buffer to read from to
+    /// * `res` - The message buffer to write to
+    /// * `id` - The identifier value of the new session
+    ///
+    /// # Errors
+    ///
+    /// If the size of the buffers is not sufficient or if the encapsualtion version is incompatible
+    /// an error variant will be returned.
+    ///
+    pub fn register_from(
+        &mut self,
+        peer: SocketAddr,
+        req: &mut dyn Buf,
+        res: &mut BytesMut,
+        id: &mut u32,
+    ) -> EipResult {
+        self.register_peer(req, res, id, Some(peer))
+    }
+
+    /// Register a new session
+    ///
+    /// # Arguments
+    ///
+    /// * `req` - The message buffer to read from to
+    /// * `res` - The message buffer to write to
+    /// * `id` - The identifier value of the new session
+    /// * `peer` - The socket address of the peer, if known
+    ///
+    /// # Errors
+    ///
+    /// If the size of the buffers is not sufficient or if the encapsualtion version is incompatible
+    /// an error variant will be returned.
+    ///
+    fn register_peer(
+        &mut self,
+        req: &mut dyn Buf,
+        res: &mut BytesMut,
+        id: &mut u32,
+        peer: Option<SocketAddr>,
+    ) -> EipResult {
         let size = size_of::<u16>() + size_of::<u16>(); // protocol version + options flags
 
         if req.remaining() < size {
@@ -66,10 +125,18 @@ impl Session {
         let version = req.get_u16_le();
         let _ = req.get_u16_le(); // OptionFlags
 
+        if version > VERSION {
+            return Err(UNSUPPORTED_VERSION);
+        }
+
         // find a free session id
         loop {
             self.id += 1;
-            if self.set.insert(self.id) {
+            if let Entry::Vacant(e) = self.sessions.entry(self.id) {
+                e.insert(Registration {
+                    last_activity: Instant::now(),
+                    peer,
+                });
                 break;
             }
         }
@@ -77,11 +144,6 @@ impl Session {
 
         res.put_u16_le(VERSION);
         res.put_u16_le(0); // OptionFlags
-
-        if version > VERSION {
-            return Err(UNSUPPORTED_VERSION);
-        }
-
         Ok(())
     }
 
@@ -96,7 +158,7 @@ impl Session {
     /// If the session does not exist an error variant will be returned.
     ///
     pub fn unregister(&mut self, id: u32) -> EipResult {
-        if !self.set.remove(&id) {
+        if self.sessions.remove(&id).is_none() {
             return Err(INVALID_SESSION);
         }
         Ok(())
@@ -112,8 +174,114 @@ impl Session {
     ///
     /// True if this is a valid session, false otherwise.
     ///
-    pub fn check(&mut self, id: u32) -> bool {
-        self.set.contains(&id)
+    pub fn check(&self, id: u32) -> bool {
+        self.sessions.contains_key(&id)
+    }
+
+    /// Get the number of registered sessions
+    ///
+    /// # Returns
+    ///
+    /// The number of sessions.
+    ///
+    pub fn len(&self) -> usize {
+        self.sessions.len()
+    }
+
+    /// Test if there are no registered sessions
+    ///
+    /// # Returns
+    ///
+    /// True if there are no sessions, false otherwise.
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.sessions.is_empty()
+    }
+
+    /// Test if this is a valid session number registered by the peer
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The identifier value of the session
+    /// * `peer` - The socket address of the peer using the session
+    ///
+    /// # Returns
+    ///
+    /// True if this is a valid session of this peer, false otherwise.
+    ///
+    pub fn check_from(&self, id: u32, peer: SocketAddr) -> bool {
+        match self.sessions.get(&id) {
+            Some(r) => r.peer == Some(peer),
+            None => false,
+        }
+    }
+
+    /// Remove all sessions registered by a peer, e.g. when its connection is closed
+    ///
+    /// # Arguments
+    ///
+    /// * `peer` - The socket address of the peer
+    ///
+    /// # Returns
+    ///
+    /// The number of removed sessions.
+    ///
+    pub fn unregister_all_for_peer(&mut self, peer: SocketAddr) -> usize {
+        let before = self.sessions.len();
+        self.sessions.retain(|_, r| r.peer != Some(peer));
+        before - self.sessions.len()
+    }
+
+    /// Test if this is a valid session number and register activity on it
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The identifier value of the session
+    ///
+    /// # Returns
+    ///
+    /// True if this is a valid session, false otherwise.
+    ///
+    pub fn touch(&mut self, id: u32) -> bool {
+        match self.sessions.get_mut(&id) {
+            Some(r) => {
+                r.last_activity = Instant::now();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove the sessions without activity for longer than the timeout
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - The maximum idle time of a session
+    ///
+    /// # Returns
+    ///
+    /// The number of removed sessions.
+    ///
+    pub fn purge_expired(&mut self, timeout: Duration) -> usize {
+        self.purge_expired_at(Instant::now(), timeout)
+    }
+
+    /// Remove the sessions without activity for longer than the timeout at a moment in time
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - The moment to compare the last activity with
+    /// * `timeout` - The maximum idle time of a session
+    ///
+    /// # Returns
+    ///
+    /// The number of removed sessions.
+    ///
+    pub fn purge_expired_at(&mut self, now: Instant, timeout: Duration) -> usize {
+        let before = self.sessions.len();
+        self.sessions
+            .retain(|_, r| now.saturating_duration_since(r.last_activity) <= timeout);
+        before - self.sessions.len()
     }
 }
 
@@ -139,11 +307,116 @@ fn bounds() {
     assert_eq!(Ok(()), session.register(&mut req, &mut res, &mut id));
 
     let id_wrong = id + 1;
+    let shared = &session;
 
-    assert_eq!(false, session.check(id_wrong));
+    assert!(shared.check(id));
+    assert!(!shared.check(id_wrong));
     assert_eq!(Err(INVALID_SESSION), session.unregister(id_wrong));
 }
 
+#[test]
+fn unsupported_version() {
+    let mut res = BytesMut::with_capacity(100);
+    let mut id: u32 = 0;
+    let mut session = Session::default();
+
+    for _ in 0..3 {
+        let mut req = &b"\x02\0\0\0"[..];
+        assert_eq!(
+            Err(UNSUPPORTED_VERSION),
+            session.register(&mut req, &mut res, &mut id)
+        );
+    }
+    assert_eq!(0, session.len());
+    assert!(session.is_empty());
+    assert_eq!(0, res.len());
+    assert_eq!(0, id);
+}
+
+#[test]
+fn expiry() {
+    let mut req = &b"\x01\0\0\0\x01\0\0\0"[..];
+    let mut res = BytesMut::with_capacity(100);
+    let mut idle: u32 = 0;
+    let mut active: u32 = 0;
+    let mut session = Session::default();
+    let timeout = Duration::from_secs(60);
+
+    assert_eq!(Ok(()), session.register(&mut req, &mut res, &mut idle));
+    assert_eq!(Ok(()), session.register(&mut req, &mut res, &mut active));
+
+    assert_eq!(0, session.purge_expired(timeout));
+    assert!(session.touch(idle));
+
+    // activity on one session halfway the timeout
+    let start = Instant::now();
+    if let Some(r) = session.sessions.get_mut(&active) {
+        r.last_activity = start + Duration::from_secs(30);
+    }
+
+    let later = start + Duration::from_secs(61);
+    assert_eq!(1, session.purge_expired_at(later, timeout));
+    assert!(!session.check(idle));
+    assert!(!session.touch(idle));
+    assert!(session.check(active));
+
+    let later = start + Duration::from_secs(91);
+    assert_eq!(1, session.purge_expired_at(later, timeout));
+    assert!(!session.check(active));
+}
+
+#[test]
+fn peer() {
+    let mut req = &b"\x01\0\0\0\x01\0\0\0"[..];
+    let mut res = BytesMut::with_capacity(100);
+    let mut id: u32 = 0;
+    let mut anonymous: u32 = 0;
+    let mut session = Session::default();
+    let peer: SocketAddr = "192.168.0.1:44818".parse().unwrap();
+    let other: SocketAddr = "192.168.0.2:44818".parse().unwrap();
+
+    assert_eq!(
+        Ok(()),
+        session.register_from(peer, &mut req, &mut res, &mut id)
+    );
+    assert_eq!(Ok(()), session.register(&mut req, &mut res, &mut anonymous));
+
+    assert!(session.check_from(id, peer));
+    assert!(!session.check_from(id, other));
+    assert!(!session.check_from(anonymous, peer));
+    assert!(session.check(id));
+    assert!(session.check(anonymous));
+}
+
+#[test]
+fn unregister_all_for_peer() {
+    let mut res = BytesMut::with_capacity(100);
+    let mut ids = [0u32; 4];
+    let mut session = Session::default();
+    let peer: SocketAddr = "192.168.0.1:44818".parse().unwrap();
+    let other: SocketAddr = "192.168.0.2:44818".parse().unwrap();
+
+    for (n, from) in [Some(peer), Some(other), Some(peer), None]
+        .iter()
+        .enumerate()
+    {
+        let mut req = &b"\x01\0\0\0"[..];
+        let r = match from {
+            Some(p) => session.register_from(*p, &mut req, &mut res, &mut ids[n]),
+            None => session.register(&mut req, &mut res, &mut ids[n]),
+        };
+        assert_eq!(Ok(()), r);
+    }
+
+    assert_eq!(2, session.unregister_all_for_peer(peer));
+    assert_eq!(2, session.len());
+    assert!(!session.check(ids[0]));
+    assert!(session.check_from(ids[1], other));
+    assert!(!session.check(ids[2]));
+    assert!(session.check(ids[3]));
+    assert_eq!(0, session.unregister_all_for_peer(peer));
+}
+
 #[test]
 fn auto_traits() {
     use crate::eip::check_auto_traits;
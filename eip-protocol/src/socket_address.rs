@@ -1,14 +1,23 @@
 use crate::eip::{EipResult, Serializing};
-use crate::error_code::{NOT_ENOUGH_DATA, REPLY_DATA_TOO_LARGE};
+use crate::error_code::{ErrorCode, MESSAGE_FORMAT_ERROR, NOT_ENOUGH_DATA, REPLY_DATA_TOO_LARGE};
+use crate::item::{Id, Item};
 use bytes::{Buf, BufMut, BytesMut};
-use core::mem::{size_of, size_of_val};
+use core::mem::size_of;
+use core::net::{Ipv4Addr, SocketAddrV4};
 
 const ZERO_LEN: usize = 8;
 
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Family(i16);
 
+/// The socket address of the Common Packet Format socket address items.
+/// Note: unlike all other EtherNet/IP structures it is big-endian (network byte order),
+/// it mirrors the BSD sockaddr_in. All byte order handling is in serialize_be() and
+/// deserialize_be(), never use the little-endian buffer functions here.
+
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SocketAddress {
     pub family: Family,
     pub port: u16,
@@ -16,6 +25,12 @@ pub struct SocketAddress {
     zero: [u8; ZERO_LEN],
 }
 
+impl From<SocketAddrV4> for SocketAddress {
+    fn from(val: SocketAddrV4) -> Self {
+        Self::server(u32::from(*val.ip()), val.port())
+    }
+}
+
 impl SocketAddress {
     pub const AF_INET: Family = Family(2);
 
@@ -58,11 +73,105 @@ impl SocketAddress {
         }
     }
 
-    const fn serial_size(&self) -> usize {
-        size_of::<i16>() // family
-        + size_of::<u16>() // port
-        + size_of::<u32>() // addr
-        + ZERO_LEN // zero
+    /// Get the IP address
+    ///
+    /// # Returns
+    ///
+    /// * The IPv4 address
+    ///
+    pub fn ip(&self) -> Ipv4Addr {
+        Ipv4Addr::from(self.addr)
+    }
+
+    /// Set the IP address
+    ///
+    /// # Arguments
+    ///
+    /// * `ip` - The IPv4 address
+    ///
+    pub fn set_ip(&mut self, ip: Ipv4Addr) {
+        self.addr = u32::from(ip);
+    }
+
+    /// Convert to a standard socket address
+    ///
+    /// # Returns
+    ///
+    /// * The IPv4 socket address
+    ///
+    pub fn to_socket_addr_v4(&self) -> SocketAddrV4 {
+        SocketAddrV4::new(self.ip(), self.port)
+    }
+
+    /// Serialize as a Common Packet Format socket address item
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to write to
+    /// * `type_id` - Item::SOCKET_OT or Item::SOCKET_TO
+    ///
+    /// # Errors
+    ///
+    /// If there is not enough room or the type is invalid an error variant will be returned.
+    ///
+    pub fn serialize_item(&self, buf: &mut BytesMut, type_id: Id) -> EipResult {
+        if type_id != Item::SOCKET_OT && type_id != Item::SOCKET_TO {
+            return Err(MESSAGE_FORMAT_ERROR);
+        }
+        Item::new(type_id, self.serial_size()).serialize(buf)?;
+        self.serialize(buf)
+    }
+
+    /// Deserialize a Common Packet Format socket address item
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to read from
+    ///
+    /// # Returns
+    ///
+    /// * The item type identification, Item::SOCKET_OT or Item::SOCKET_TO
+    ///
+    /// # Errors
+    ///
+    /// If there is not enough data or the type or length is invalid an error variant will be returned.
+    ///
+    pub fn deserialize_item(&mut self, buf: &mut dyn Buf) -> Result<Id, ErrorCode> {
+        let mut item = Item::default();
+        item.deserialize(buf)?;
+        if (item.type_id != Item::SOCKET_OT && item.type_id != Item::SOCKET_TO)
+            || item.len != self.serial_size()
+        {
+            return Err(MESSAGE_FORMAT_ERROR);
+        }
+        self.deserialize(buf)?;
+        Ok(item.type_id)
+    }
+
+    /// Read all fields in big-endian, the caller checks the remaining data
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to read from
+    ///
+    fn deserialize_be(&mut self, buf: &mut dyn Buf) {
+        self.family = Family(buf.get_i16());
+        self.port = buf.get_u16();
+        self.addr = buf.get_u32();
+        buf.copy_to_slice(&mut self.zero);
+    }
+
+    /// Write all fields in big-endian, the caller checks the room
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to write to
+    ///
+    fn serialize_be(&self, buf: &mut BytesMut) {
+        buf.put_i16(self.family.0);
+        buf.put_u16(self.port);
+        buf.put_u32(self.addr);
+        buf.put_slice(&self.zero);
     }
 }
 
@@ -82,12 +191,7 @@ impl Serializing for SocketAddress {
         if buf.remaining() < self.serial_size() {
             return Err(NOT_ENOUGH_DATA);
         }
-        self.family = Family(buf.get_i16());
-        self.port = buf.get_u16();
-        self.addr = buf.get_u32();
-        for n in 0..size_of_val(&self.zero) {
-            self.zero[n] = buf.get_u8();
-        }
+        self.deserialize_be(buf);
         Ok(())
     }
 
@@ -106,12 +210,22 @@ impl Serializing for SocketAddress {
         if buf.remaining_mut() < self.serial_size() {
             return Err(REPLY_DATA_TOO_LARGE);
         }
-        buf.put_i16(self.family.0);
-        buf.put_u16(self.port);
-        buf.put_u32(self.addr);
-        buf.put(&self.zero[..]);
+        self.serialize_be(buf);
         Ok(())
     }
+
+    /// Get the serialized size in Bytes.
+    ///
+    /// # Returns
+    ///
+    /// * The number of bytes when serialized
+    ///
+    fn serial_size(&self) -> usize {
+        size_of::<i16>() // family
+        + size_of::<u16>() // port
+        + size_of::<u32>() // addr
+        + ZERO_LEN // zero
+    }
 }
 
 #[test]
@@ -144,6 +258,79 @@ fn serialize() {
     assert_eq!(&b"\0\x02\xaf\x12\x12\x34\x56\x78\0\0\0\0\0\0\0\0"[..], buf);
 }
 
+#[test]
+fn byte_order() {
+    let sa = SocketAddress::server(0xc0a80001, 0x1234);
+    let mut buf = BytesMut::with_capacity(100);
+    assert_eq!(Ok(()), sa.serialize(&mut buf));
+    assert_eq!(&b"\0\x02"[..], &buf[..2]); // AF_INET
+    assert_eq!(&b"\0\x02\x12\x34\xc0\xa8\0\x01\0\0\0\0\0\0\0\0"[..], buf);
+
+    let mut received = SocketAddress::default();
+    assert_eq!(Ok(()), received.deserialize(&mut &buf[..]));
+    assert_eq!(SocketAddress::AF_INET, received.family);
+    assert_eq!(0x1234, received.port);
+    assert_eq!(sa, received);
+}
+
+#[test]
+fn socket_addr_v4() {
+    let v4: SocketAddrV4 = "192.168.0.1:2222".parse().unwrap();
+    let sa = SocketAddress::from(v4);
+    assert_eq!(SocketAddress::AF_INET, sa.family);
+    assert_eq!(0xc0a80001, sa.addr);
+    assert_eq!(2222, sa.port);
+    assert_eq!(v4, sa.to_socket_addr_v4());
+
+    let mut buf = BytesMut::with_capacity(100);
+    assert_eq!(Ok(()), sa.serialize(&mut buf));
+    assert_eq!(&b"\0\x02\x08\xae\xc0\xa8\0\x01"[..], &buf[..8]);
+}
+
+#[test]
+fn ip() {
+    let mut sa = SocketAddress::server(0, 44818);
+    sa.set_ip(Ipv4Addr::new(192, 168, 1, 10));
+    assert_eq!(0xc0a8010a, sa.addr);
+    assert_eq!(Ipv4Addr::new(192, 168, 1, 10), sa.ip());
+
+    let mut buf = BytesMut::with_capacity(100);
+    assert_eq!(Ok(()), sa.serialize(&mut buf));
+    assert_eq!(&b"\xc0\xa8\x01\x0a"[..], &buf[4..8]);
+}
+
+#[test]
+fn socket_item() {
+    let sa = SocketAddress::server(0xc0a80001, 2222);
+    let mut buf = BytesMut::with_capacity(100);
+
+    assert_eq!(Ok(()), sa.serialize_item(&mut buf, Item::SOCKET_TO));
+    assert_eq!(20, buf.len());
+    assert_eq!(
+        &b"\x01\x80\x10\0\0\x02\x08\xae\xc0\xa8\0\x01\0\0\0\0\0\0\0\0"[..],
+        buf
+    );
+
+    let mut received = SocketAddress::default();
+    assert_eq!(
+        Ok(Item::SOCKET_TO),
+        received.deserialize_item(&mut &buf[..])
+    );
+    assert_eq!(sa, received);
+
+    // invalid length
+    let mut data = &b"\x01\x80\x0f\0\0\x02\x08\xae\xc0\xa8\0\x01\0\0\0\0\0\0\0"[..];
+    assert_eq!(
+        Err(MESSAGE_FORMAT_ERROR),
+        received.deserialize_item(&mut data)
+    );
+
+    assert_eq!(
+        Err(MESSAGE_FORMAT_ERROR),
+        sa.serialize_item(&mut buf, Item::CONNECTED_DATA)
+    );
+}
+
 #[test]
 fn auto_traits() {
     use crate::eip::check_auto_traits;
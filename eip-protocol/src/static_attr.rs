@@ -1,15 +1,23 @@
 #![allow(dead_code)]
 use crate::attr::{AccessCode, Uint};
-use crate::eip::{EipResult, Serializing};
-use crate::error_code::ATTRIBUTE_NOT_SUPPORTED;
-use bytes::{Buf, BytesMut};
+use crate::eip::{self, EipResult, Serializing};
+use crate::error_code::{ATTRIBUTE_NOT_GETTABLE, ATTRIBUTE_NOT_SUPPORTED};
+use bytes::{Buf, BufMut, BytesMut};
 
 // This is a set of static attributes that is commonly applicable
 
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Attr(u16);
 
+impl From<u16> for Attr {
+    fn from(val: u16) -> Self {
+        Self(val)
+    }
+}
+
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StaticAttr {
     pub revision: Uint,
     pub max_instance: Uint,
@@ -82,6 +90,27 @@ impl StaticAttr {
         Ok(())
     }
 
+    /// Serialize the reply data of the Get_Attributes_All service,
+    /// all attributes in identifier order. A non getable attribute is zero filled.
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The message buffer to write to
+    ///
+    /// # Errors
+    ///
+    /// If there is not enough room an error variant will be returned.
+    ///
+    pub fn serialize_all_attributes(&self, buf: &mut BytesMut) -> EipResult {
+        for n in 1..Self::ATTRIBUTE_END.0 {
+            match self.serialize_attribute_single(buf, Attr(n)) {
+                Err(ATTRIBUTE_NOT_GETTABLE) => buf.put_bytes(0, Uint::default().serial_size()),
+                r => r?,
+            }
+        }
+        Ok(())
+    }
+
     /// Deserialize one specific attribute
     ///
     /// # Arguments
@@ -114,12 +143,15 @@ impl Serializing for StaticAttr {
     /// # Errors
     ///
     /// If one of the attributes is non existent or is not set-able, an error variant will be returned.
+    /// All attributes keep their prior value then and no input is consumed.
     ///
     fn deserialize(&mut self, buf: &mut dyn Buf) -> EipResult {
-        for n in 1..Self::ATTRIBUTE_END.0 {
-            self.deserialize_attribute_single(buf, Attr(n))?;
-        }
-        Ok(())
+        eip::deserialize_atomic(self, buf, |received, buf| {
+            for n in 1..Self::ATTRIBUTE_END.0 {
+                received.deserialize_attribute_single(buf, Attr(n))?;
+            }
+            Ok(())
+        })
     }
 
     /// Serialize all attributes
@@ -139,10 +171,24 @@ impl Serializing for StaticAttr {
         }
         Ok(())
     }
+
+    /// Get the serialized size in Bytes.
+    ///
+    /// # Returns
+    ///
+    /// * The number of bytes when serialized
+    ///
+    fn serial_size(&self) -> usize {
+        self.revision.serial_size()
+            + self.max_instance.serial_size()
+            + self.number_of_instances.serial_size()
+    }
 }
 
 #[test]
 fn deserialize() {
+    use crate::error_code::NOT_ENOUGH_DATA;
+
     let mut sa = StaticAttr::default();
     let mut buf = &b"\x02\x01\x04\x03\x06\x05"[..];
     assert_eq!(Ok(()), sa.deserialize(&mut buf));
@@ -151,6 +197,13 @@ fn deserialize() {
     assert_eq!(0x0304, sa.max_instance.get());
     assert_eq!(0x0506, sa.number_of_instances.get());
 
+    // the last attribute is truncated, none is changed
+    let prior = sa.clone();
+    buf = &b"\x0a\0\x0b\0\x0c"[..];
+    assert_eq!(Err(NOT_ENOUGH_DATA), sa.deserialize(&mut buf));
+    assert_eq!(5, buf.len());
+    assert_eq!(prior, sa);
+
     sa = StaticAttr::default();
     buf = &b"\x06\x07"[..];
     assert_eq!(
@@ -179,6 +232,16 @@ fn serialize() {
     assert_eq!(&b"\x34\x12"[..], buf);
 }
 
+#[test]
+fn serialize_all_attributes() {
+    let mut sa = StaticAttr::new(0x1234, 2, 3);
+    sa.max_instance = Uint::new(2, AccessCode::new(AccessCode::NONE));
+
+    let mut buf = BytesMut::with_capacity(10);
+    assert_eq!(Ok(()), sa.serialize_all_attributes(&mut buf));
+    assert_eq!(&b"\x34\x12\0\0\x03\0"[..], buf);
+}
+
 #[test]
 fn auto_traits() {
     use crate::eip::check_auto_traits;
@@ -0,0 +1,198 @@
+use crate::cpf::CommonPacketFormat;
+use crate::eip::Serializing;
+use crate::encapsulation::{self, Encapsulation};
+use crate::item::Item;
+use crate::message::EipMessage;
+use crate::message_router::{Request, Response, Service};
+use crate::send_data::SendData;
+use alloc::vec;
+use bytes::{Buf, BufMut, BytesMut};
+
+// Complete request and reply frames, laid out by hand after the frame formats of the
+// specification, not captured from a device. Each one is parsed layer by layer and serialized
+// again. The result must match byte for byte, a difference is a protocol bug, e.g. padding or
+// byte order, not a test to update.
+
+// RegisterSession, protocol version 1 and no options
+const REGISTER_SESSION_REQUEST: &[u8] =
+    b"\x65\0\x04\0\0\0\0\0\0\0\0\0\x01\x02\x03\x04\x05\x06\x07\x08\0\0\0\0\x01\0\0\0";
+const REGISTER_SESSION_REPLY: &[u8] =
+    b"\x65\0\x04\0\x2a\0\x01\x00\0\0\0\0\x01\x02\x03\x04\x05\x06\x07\x08\0\0\0\0\x01\0\0\0";
+
+// SendRRData, Get_Attribute_Single of the Identity vendor id
+const GET_VENDOR_ID_REQUEST: &[u8] = b"\x6f\0\x18\0\x2a\0\x01\0\0\0\0\0\x01\x02\x03\x04\x05\x06\x07\x08\0\0\0\0\0\0\0\0\x0a\0\x02\0\0\0\0\0\xb2\0\x08\0\x0e\x03\x20\x01\x24\x01\x30\x01";
+const GET_VENDOR_ID_REPLY: &[u8] = b"\x6f\0\x16\0\x2a\0\x01\0\0\0\0\0\x01\x02\x03\x04\x05\x06\x07\x08\0\0\0\0\0\0\0\0\0\0\x02\0\0\0\0\0\xb2\0\x06\0\x8e\0\0\0\x01\0";
+
+// SendRRData, Get_Attribute_Single of the Identity product name, an odd length without padding
+const GET_PRODUCT_NAME_REQUEST: &[u8] = b"\x6f\0\x18\0\x2a\0\x01\0\0\0\0\0\x01\x02\x03\x04\x05\x06\x07\x08\0\0\0\0\0\0\0\0\x0a\0\x02\0\0\0\0\0\xb2\0\x08\0\x0e\x03\x20\x01\x24\x01\x30\x07";
+const GET_PRODUCT_NAME_REPLY: &[u8] = b"\x6f\0\x1e\0\x2a\0\x01\0\0\0\0\0\x01\x02\x03\x04\x05\x06\x07\x08\0\0\0\0\0\0\0\0\0\0\x02\0\0\0\0\0\xb2\0\x0e\0\x8e\0\0\0\x09OpENer PC";
+
+// SendRRData, Get_Attribute_Single of a non existent attribute, one additional status word
+const GET_UNKNOWN_REQUEST: &[u8] = b"\x6f\0\x18\0\x2a\0\x01\0\0\0\0\0\x01\x02\x03\x04\x05\x06\x07\x08\0\0\0\0\0\0\0\0\x0a\0\x02\0\0\0\0\0\xb2\0\x08\0\x0e\x03\x20\x01\x24\x01\x30\x63";
+const GET_UNKNOWN_REPLY: &[u8] = b"\x6f\0\x16\0\x2a\0\x01\0\0\0\0\0\x01\x02\x03\x04\x05\x06\x07\x08\0\0\0\0\0\0\0\0\0\0\x02\0\0\0\0\0\xb2\0\x06\0\x8e\0\x14\x01\x63\0";
+
+// SendRRData, Get_Attribute_Single of the vendor id of Identity instance 256, a 16 bit segment
+const GET_INSTANCE_256_REQUEST: &[u8] = b"\x6f\0\x1a\0\x2a\0\x01\0\0\0\0\0\x01\x02\x03\x04\x05\x06\x07\x08\0\0\0\0\0\0\0\0\x0a\0\x02\0\0\0\0\0\xb2\0\x0a\0\x0e\x04\x20\x01\x25\0\0\x01\x30\x01";
+
+// SendRRData, Read Tag of one element of the tag "Counter", an odd length symbol with pad byte
+const READ_TAG_REQUEST: &[u8] = b"\x6f\0\x1e\0\x2a\0\x01\0\0\0\0\0\x01\x02\x03\x04\x05\x06\x07\x08\0\0\0\0\0\0\0\0\x0a\0\x02\0\0\0\0\0\xb2\0\x0e\0\x4c\x05\x91\x07Counter\0\x01\0";
+const READ_TAG_REPLY: &[u8] = b"\x6f\0\x1a\0\x2a\0\x01\0\0\0\0\0\x01\x02\x03\x04\x05\x06\x07\x08\0\0\0\0\0\0\0\0\0\0\x02\0\0\0\0\0\xb2\0\x0a\0\xcc\0\0\0\xc4\0\x2a\0\0\0";
+
+/// Parse a RegisterSession frame and serialize it again
+fn round_trip_register_session(frame: &[u8]) -> BytesMut {
+    let mut buf = frame;
+    let mut enc = Encapsulation::default();
+    assert_eq!(Ok(()), enc.deserialize(&mut buf));
+    assert_eq!(Ok(()), enc.validate());
+    assert_eq!(Encapsulation::REGISTER_SESSION, enc.command);
+    assert_eq!(Ok(()), enc.validate_len(buf.remaining()));
+    let version = buf.get_u16_le();
+    let options = buf.get_u16_le();
+    assert_eq!(1, version);
+    assert_eq!(0, options);
+
+    let mut body = BytesMut::with_capacity(4);
+    body.put_u16_le(version);
+    body.put_u16_le(options);
+    let mut expected = BytesMut::with_capacity(4);
+    assert_eq!(
+        Ok(()),
+        encapsulation::register_session_request(&mut expected)
+    );
+    assert_eq!(expected, body);
+
+    let mut out = BytesMut::with_capacity(frame.len());
+    assert_eq!(Ok(()), enc.serialize_with_payload(&mut out, &body));
+    out
+}
+
+/// Parse a SendRRData frame and serialize it again, each layer on its own
+///
+/// # Returns
+///
+/// * The frame serialized again and the Message Router data of the unconnected data item
+///
+fn round_trip_rr_data(frame: &[u8]) -> (BytesMut, BytesMut) {
+    let mut buf = frame;
+    let mut enc = Encapsulation::default();
+    assert_eq!(Ok(()), enc.deserialize(&mut buf));
+    assert_eq!(Ok(()), enc.validate());
+    assert_eq!(Encapsulation::SEND_RR_DATA, enc.command);
+    assert_eq!(Ok(()), enc.validate_len(buf.remaining()));
+
+    let mut send_data = SendData::default();
+    assert_eq!(Ok(()), send_data.deserialize(&mut &buf[..]));
+    assert_eq!(0, send_data.interface_handle());
+    assert_eq!(2, send_data.item_count);
+
+    let mut cpf = CommonPacketFormat::default();
+    assert_eq!(Ok(()), cpf.deserialize(&mut &buf[6..]));
+    assert_eq!(Some(&[][..]), cpf.find(&Item::NULL_ADDRESS).map(|b| &b[..]));
+    let data = BytesMut::from(&cpf.find(&Item::UNCONNECTED_DATA).unwrap()[..]);
+
+    let mut msg = EipMessage::default();
+    assert_eq!(Ok(()), msg.deserialize(&mut &frame[..]));
+    assert_eq!(enc, msg.encapsulation);
+    assert_eq!(cpf, msg.cpf);
+    (msg.to_bytes().unwrap(), data)
+}
+
+#[test]
+fn register_session() {
+    assert_eq!(
+        REGISTER_SESSION_REQUEST,
+        round_trip_register_session(REGISTER_SESSION_REQUEST)
+    );
+    assert_eq!(
+        REGISTER_SESSION_REPLY,
+        round_trip_register_session(REGISTER_SESSION_REPLY)
+    );
+
+    let mut reply = Encapsulation::default();
+    assert_eq!(Ok(()), reply.deserialize(&mut &REGISTER_SESSION_REPLY[..]));
+    let mut request = Encapsulation::default();
+    assert_eq!(
+        Ok(()),
+        request.deserialize(&mut &REGISTER_SESSION_REQUEST[..])
+    );
+    assert_eq!(
+        Ok(0x0001002a),
+        encapsulation::parse_register_session_reply(&reply)
+    );
+    assert_eq!(request.context, reply.context);
+}
+
+#[test]
+fn get_attribute_single() {
+    for (request_frame, reply_frame, instance, attribute) in [
+        (GET_VENDOR_ID_REQUEST, GET_VENDOR_ID_REPLY, 1, 1),
+        (GET_PRODUCT_NAME_REQUEST, GET_PRODUCT_NAME_REPLY, 1, 7),
+        (GET_UNKNOWN_REQUEST, GET_UNKNOWN_REPLY, 1, 0x63),
+        (GET_INSTANCE_256_REQUEST, GET_VENDOR_ID_REPLY, 0x100, 1),
+    ] {
+        let (out, data) = round_trip_rr_data(request_frame);
+        assert_eq!(request_frame, out);
+        let mut request = Request::default();
+        assert_eq!(Ok(()), request.deserialize(&mut &data[..]));
+        assert_eq!(
+            Request::get_attribute_single(0x01, instance, attribute),
+            request
+        );
+        assert_eq!(data, request.to_bytes().unwrap());
+
+        let (out, data) = round_trip_rr_data(reply_frame);
+        assert_eq!(reply_frame, out);
+        let mut response = Response::default();
+        assert_eq!(Ok(()), response.deserialize(&mut &data[..]));
+        assert_eq!(
+            Request::GET_ATTRIBUTE_SINGLE.as_response(),
+            response.service
+        );
+        assert_eq!(data, response.to_bytes().unwrap());
+    }
+}
+
+#[test]
+fn get_attribute_single_reply_data() {
+    use crate::error_code::{ATTRIBUTE_NOT_SUPPORTED, SUCCESS};
+
+    let mut response = Response::default();
+    let (_, data) = round_trip_rr_data(GET_VENDOR_ID_REPLY);
+    assert_eq!(Ok(()), response.deserialize(&mut &data[..]));
+    assert_eq!(SUCCESS, response.general_status);
+    assert_eq!(&b"\x01\0"[..], response.data); // Rockwell Automation
+
+    let (_, data) = round_trip_rr_data(GET_PRODUCT_NAME_REPLY);
+    assert_eq!(Ok(()), response.deserialize(&mut &data[..]));
+    assert_eq!(&b"\x09OpENer PC"[..], response.data); // SHORT_STRING
+
+    let (_, data) = round_trip_rr_data(GET_UNKNOWN_REPLY);
+    assert_eq!(Ok(()), response.deserialize(&mut &data[..]));
+    assert_eq!(ATTRIBUTE_NOT_SUPPORTED, response.general_status);
+    assert_eq!(vec![0x63], response.additional_status);
+    assert!(response.data.is_empty());
+}
+
+#[test]
+fn read_tag() {
+    use crate::error_code::SUCCESS;
+
+    let (out, data) = round_trip_rr_data(READ_TAG_REQUEST);
+    assert_eq!(READ_TAG_REQUEST, out);
+    let mut buf = &data[..];
+    let mut request = Request::default();
+    assert_eq!(Ok(()), request.deserialize(&mut buf));
+    assert_eq!(Service::from(0x4c), request.service);
+    assert_eq!(Some("Counter"), request.symbol.as_deref());
+    assert_eq!(&b"\x01\0"[..], buf); // element count, the request data
+    assert_eq!(data[..12], request.to_bytes().unwrap());
+
+    let (out, data) = round_trip_rr_data(READ_TAG_REPLY);
+    assert_eq!(READ_TAG_REPLY, out);
+    let mut response = Response::default();
+    assert_eq!(Ok(()), response.deserialize(&mut &data[..]));
+    assert_eq!(Service::from(0x4c).as_response(), response.service);
+    assert_eq!(SUCCESS, response.general_status);
+    assert_eq!(&b"\xc4\0\x2a\0\0\0"[..], response.data); // DINT 42
+    assert_eq!(data, response.to_bytes().unwrap());
+}